@@ -5,6 +5,7 @@ import type {
   LogEntry,
   MetricData,
   GrafanaDashboard,
+  GrafanaCatalog,
 } from '@/types';
 
 const apiClient = axios.create({
@@ -60,10 +61,10 @@ export const api = {
 
   // Grafana
   getGrafanaDashboards: async (): Promise<GrafanaDashboard[]> => {
-    const { data } = await apiClient.get<GrafanaDashboard[]>(
+    const { data } = await apiClient.get<GrafanaCatalog>(
       '/admin/grafana/dashboards'
     );
-    return data;
+    return data.dashboards;
   },
 };
 
@@ -62,4 +62,12 @@ export interface GrafanaDashboard {
   title: string;
   url: string;
   tags: string[];
+  folder?: string;
+}
+
+export interface GrafanaCatalog {
+  status: 'healthy' | 'degraded' | 'down';
+  dashboards: GrafanaDashboard[];
+  fetchedAt?: string;
+  message?: string;
 }
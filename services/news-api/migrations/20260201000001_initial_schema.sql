@@ -0,0 +1,59 @@
+-- Baseline schema, equivalent to what the former `db::init_schema` created.
+--
+-- Every statement is idempotent so that databases which were initialized by
+-- `init_schema` before migrations existed are adopted as-is.
+
+CREATE TABLE IF NOT EXISTS crawl_stats (
+    id SERIAL PRIMARY KEY,
+    date DATE NOT NULL,
+    total_crawled INT DEFAULT 0,
+    success_count INT DEFAULT 0,
+    failed_count INT DEFAULT 0,
+    created_at TIMESTAMP DEFAULT NOW()
+);
+
+CREATE TABLE IF NOT EXISTS categories (
+    id SERIAL PRIMARY KEY,
+    name VARCHAR(100) NOT NULL UNIQUE,
+    article_count INT DEFAULT 0
+);
+
+ALTER TABLE categories ADD COLUMN IF NOT EXISTS last_synced_at TIMESTAMPTZ;
+
+ALTER TABLE categories
+    ADD COLUMN IF NOT EXISTS parent_id INT REFERENCES categories(id) ON DELETE SET NULL;
+
+CREATE TABLE IF NOT EXISTS category_aliases (
+    alias VARCHAR(100) PRIMARY KEY,
+    category_id INT NOT NULL REFERENCES categories(id) ON DELETE CASCADE
+);
+
+CREATE TABLE IF NOT EXISTS sources (
+    id SERIAL PRIMARY KEY,
+    name VARCHAR(200) NOT NULL UNIQUE,
+    homepage TEXT,
+    feed_urls TEXT[] NOT NULL DEFAULT '{}',
+    language VARCHAR(10) NOT NULL DEFAULT 'ko',
+    default_category VARCHAR(100),
+    crawl_interval_minutes INT NOT NULL DEFAULT 60,
+    enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE TABLE IF NOT EXISTS crawl_runs (
+    id BIGSERIAL PRIMARY KEY,
+    source_id INT REFERENCES sources(id) ON DELETE SET NULL,
+    started_at TIMESTAMPTZ NOT NULL,
+    finished_at TIMESTAMPTZ,
+    pages_fetched INT NOT NULL DEFAULT 0,
+    articles_new INT NOT NULL DEFAULT 0,
+    articles_updated INT NOT NULL DEFAULT 0,
+    articles_failed INT NOT NULL DEFAULT 0,
+    error_samples TEXT[] NOT NULL DEFAULT '{}',
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS crawl_runs_source_started_idx ON crawl_runs (source_id, started_at);
+
+CREATE INDEX IF NOT EXISTS crawl_runs_started_idx ON crawl_runs (started_at);
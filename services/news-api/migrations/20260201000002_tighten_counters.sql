@@ -0,0 +1,45 @@
+-- Constraints that `CREATE TABLE IF NOT EXISTS` could never add to existing tables.
+
+-- crawl_stats: one row per day. Merge duplicates written by hand before
+-- daily rows were derived from crawl_runs, keeping the oldest row.
+UPDATE crawl_stats c
+SET total_crawled = d.total_crawled,
+    success_count = d.success_count,
+    failed_count = d.failed_count
+FROM (
+    SELECT date,
+           MIN(id) AS keep_id,
+           SUM(COALESCE(total_crawled, 0)) AS total_crawled,
+           SUM(COALESCE(success_count, 0)) AS success_count,
+           SUM(COALESCE(failed_count, 0)) AS failed_count
+    FROM crawl_stats
+    GROUP BY date
+    HAVING COUNT(*) > 1
+) d
+WHERE c.id = d.keep_id;
+
+DELETE FROM crawl_stats c
+USING (SELECT date, MIN(id) AS keep_id FROM crawl_stats GROUP BY date) d
+WHERE c.date = d.date AND c.id <> d.keep_id;
+
+CREATE UNIQUE INDEX IF NOT EXISTS crawl_stats_date_key ON crawl_stats (date);
+
+-- Counters are never NULL
+UPDATE crawl_stats
+SET total_crawled = COALESCE(total_crawled, 0),
+    success_count = COALESCE(success_count, 0),
+    failed_count = COALESCE(failed_count, 0)
+WHERE total_crawled IS NULL OR success_count IS NULL OR failed_count IS NULL;
+
+ALTER TABLE crawl_stats
+    ALTER COLUMN total_crawled SET NOT NULL,
+    ALTER COLUMN success_count SET NOT NULL,
+    ALTER COLUMN failed_count SET NOT NULL;
+
+UPDATE categories SET article_count = 0 WHERE article_count IS NULL;
+
+ALTER TABLE categories ALTER COLUMN article_count SET NOT NULL;
+
+-- Timestamps are stored with time zone like every other table
+ALTER TABLE crawl_stats
+    ALTER COLUMN created_at TYPE TIMESTAMPTZ USING created_at AT TIME ZONE 'UTC';
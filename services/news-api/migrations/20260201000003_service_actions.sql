@@ -0,0 +1,16 @@
+-- Audit trail of container lifecycle requests from the admin API.
+-- Denied and failed requests are recorded too.
+CREATE TABLE IF NOT EXISTS service_actions (
+    id BIGSERIAL PRIMARY KEY,
+    service_id VARCHAR(100) NOT NULL,
+    container VARCHAR(255),
+    action VARCHAR(20) NOT NULL CHECK (action IN ('start', 'stop', 'restart')),
+    outcome VARCHAR(20) NOT NULL CHECK (outcome IN ('succeeded', 'unchanged', 'failed', 'denied')),
+    message TEXT,
+    client_addr VARCHAR(64),
+    duration_ms INT NOT NULL DEFAULT 0,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_service_actions_service_created
+    ON service_actions (service_id, created_at DESC);
@@ -0,0 +1,15 @@
+-- Log entries captured by the API, written when `[logs] persist = true`.
+-- Rows older than `retention_days` are pruned by the API.
+CREATE TABLE IF NOT EXISTS log_entries (
+    id BIGSERIAL PRIMARY KEY,
+    logged_at TIMESTAMPTZ NOT NULL,
+    level VARCHAR(5) NOT NULL CHECK (level IN ('trace', 'debug', 'info', 'warn', 'error')),
+    service VARCHAR(100) NOT NULL,
+    target VARCHAR(255) NOT NULL,
+    message TEXT NOT NULL,
+    metadata JSONB NOT NULL DEFAULT '{}'
+);
+
+CREATE INDEX IF NOT EXISTS idx_log_entries_logged_at ON log_entries (logged_at DESC);
+CREATE INDEX IF NOT EXISTS idx_log_entries_service_logged_at
+    ON log_entries (service, logged_at DESC);
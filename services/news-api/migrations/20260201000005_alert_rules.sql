@@ -0,0 +1,36 @@
+-- Alert rules evaluated by the API, with the state of their latest evaluation.
+CREATE TABLE IF NOT EXISTS alert_rules (
+    id SERIAL PRIMARY KEY,
+    name VARCHAR(200) NOT NULL UNIQUE,
+    metric VARCHAR(50) NOT NULL
+        CHECK (metric IN ('crawl_failure_rate', 'articles_ingested', 'service_down', 'disk_usage')),
+    -- Service ID for service_down, mount point for disk_usage
+    target VARCHAR(255),
+    operator VARCHAR(2) NOT NULL CHECK (operator IN ('>', '>=', '<', '<=')),
+    threshold DOUBLE PRECISION NOT NULL,
+    window_minutes INT NOT NULL DEFAULT 60,
+    severity VARCHAR(10) NOT NULL DEFAULT 'warning'
+        CHECK (severity IN ('info', 'warning', 'critical')),
+    enabled BOOLEAN NOT NULL DEFAULT TRUE,
+    state VARCHAR(10) NOT NULL DEFAULT 'ok' CHECK (state IN ('ok', 'firing')),
+    last_value DOUBLE PRECISION,
+    last_error TEXT,
+    last_evaluated_at TIMESTAMPTZ,
+    fired_at TIMESTAMPTZ,
+    resolved_at TIMESTAMPTZ,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+-- Every transition between ok and firing
+CREATE TABLE IF NOT EXISTS alert_events (
+    id BIGSERIAL PRIMARY KEY,
+    rule_id INT NOT NULL REFERENCES alert_rules(id) ON DELETE CASCADE,
+    state VARCHAR(10) NOT NULL CHECK (state IN ('firing', 'resolved')),
+    value DOUBLE PRECISION,
+    message TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT NOW()
+);
+
+CREATE INDEX IF NOT EXISTS idx_alert_events_rule_created ON alert_events (rule_id, created_at DESC);
+CREATE INDEX IF NOT EXISTS idx_alert_events_created ON alert_events (created_at DESC);
@@ -38,14 +38,21 @@ until curl -s http://localhost:9200/_cluster/health > /dev/null 2>&1; do
 done
 echo "✅ OpenSearch is ready"
 
-# Load test data
+# The CLI reads DATABASE_URL and OPENSEARCH_URL from .env
+if [ ! -f .env ]; then
+    cp .env.example .env
+    echo "📝 Created .env from .env.example"
+fi
+
+# Apply migrations and load test data
 echo ""
-echo "📊 Loading test data into PostgreSQL..."
-docker-compose exec -T postgres psql -U baram -d baram < test-data.sql
+echo "🗄️  Applying database migrations..."
+cargo run --quiet -- migrate
 
 echo ""
-echo "📊 Loading test data into OpenSearch..."
-bash opensearch-test-data.sh > /dev/null 2>&1
+echo "📊 Loading test data into PostgreSQL and OpenSearch..."
+cargo run --quiet -- index create
+cargo run --quiet -- seed
 
 echo ""
 echo "✨ Setup complete! You can now:"
@@ -0,0 +1,389 @@
+//! Alert rule evaluation.
+//!
+//! Enabled rules in `alert_rules` are evaluated on a schedule and on request
+//! from the admin API. Each rule measures one value (crawl failure rate from
+//! `crawl_runs`, recent article count from the search index, the latest probe
+//! of a service, or disk usage) and compares it with its threshold. A rule
+//! whose state changes between `ok` and `firing` gets an `alert_events` row;
+//! a rule whose value cannot be measured keeps its state and records why.
+//! Events are passed on to the webhooks subscribed to them.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use crate::{
+    error::ApiResult,
+    host_metrics,
+    models::{AlertEvent, AlertMetric, AlertRule, HealthStatus, ServiceHealth},
+    webhooks::Notification,
+    AppState,
+};
+
+pub const ALERT_RULE_COLUMNS: &str = "id, name, metric, target, operator, threshold, \
+     window_minutes, severity, enabled, state, last_value, last_error, last_evaluated_at, \
+     fired_at, resolved_at, created_at, updated_at";
+
+/// Evaluate every enabled rule and return the state changes, oldest first
+pub async fn evaluate_all(state: &AppState) -> ApiResult<Vec<AlertEvent>> {
+    let rules: Vec<AlertRule> = sqlx::query_as(&format!(
+        "SELECT {ALERT_RULE_COLUMNS} FROM alert_rules WHERE enabled ORDER BY id"
+    ))
+    .fetch_all(&state.db.pool)
+    .await?;
+
+    if rules.iter().any(|r| r.metric == AlertMetric::ServiceDown) {
+        state.services.ensure_probed(&state.db, &state.search).await;
+    }
+    let services: HashMap<String, Option<ServiceHealth>> = state
+        .services
+        .list()
+        .await
+        .into_iter()
+        .map(|s| (s.id, s.health))
+        .collect();
+
+    let mut events = Vec::new();
+    for rule in &rules {
+        match measure(state, rule, &services).await {
+            Ok(value) => {
+                if let Some(event) = record_value(state, rule, value).await? {
+                    events.push(event);
+                }
+            }
+            Err(message) => {
+                tracing::warn!(rule = rule.name, "Alert rule could not be evaluated: {message}");
+                sqlx::query(
+                    r#"
+                    UPDATE alert_rules
+                    SET last_error = $2, last_evaluated_at = NOW()
+                    WHERE id = $1
+                    "#,
+                )
+                .bind(rule.id)
+                .bind(&message)
+                .execute(&state.db.pool)
+                .await?;
+            }
+        }
+    }
+
+    for event in &events {
+        if let Err(e) = state.webhooks.notify(&state.db, &Notification::alert(event)).await {
+            tracing::warn!(rule = event.rule_name, "Failed to queue alert notification: {e}");
+        }
+    }
+
+    Ok(events)
+}
+
+/// Current value of the rule's metric
+async fn measure(
+    state: &AppState,
+    rule: &AlertRule,
+    services: &HashMap<String, Option<ServiceHealth>>,
+) -> Result<f64, String> {
+    match rule.metric {
+        AlertMetric::CrawlFailureRate => {
+            let (failed, total): (i64, i64) = sqlx::query_as(
+                r#"
+                SELECT COALESCE(SUM(articles_failed), 0)::BIGINT,
+                       COALESCE(SUM(articles_new + articles_updated + articles_failed), 0)::BIGINT
+                FROM crawl_runs
+                WHERE started_at >= NOW() - make_interval(mins => $1)
+                "#,
+            )
+            .bind(rule.window_minutes)
+            .fetch_one(&state.db.pool)
+            .await
+            .map_err(|e| e.to_string())?;
+
+            Ok(if total == 0 {
+                0.0
+            } else {
+                failed as f64 / total as f64 * 100.0
+            })
+        }
+        AlertMetric::ArticlesIngested => state
+            .search
+            .count_articles_since(i64::from(rule.window_minutes))
+            .await
+            .map(|count| count as f64)
+            .map_err(|e| e.to_string()),
+        AlertMetric::ServiceDown => {
+            let id = rule.target.as_deref().unwrap_or_default();
+            match services.get(id) {
+                Some(Some(health)) => Ok(f64::from(u8::from(health.status == HealthStatus::Down))),
+                Some(None) => Err(format!("Service {id} has not been probed yet")),
+                None => Err(format!("Service {id} is not registered")),
+            }
+        }
+        AlertMetric::DiskUsage => {
+            let path = rule.target.as_deref().unwrap_or("/");
+            host_metrics::filesystem_usage(path)
+                .await
+                .map(|fs| fs.percentage())
+                .map_err(|e| format!("Failed to read usage of {path}: {e}"))
+        }
+    }
+}
+
+/// Store a measured value and, if the rule changed state, the event
+async fn record_value(
+    state: &AppState,
+    rule: &AlertRule,
+    value: f64,
+) -> ApiResult<Option<AlertEvent>> {
+    let firing = rule.operator.holds(value, rule.threshold);
+    let new_state = if firing { "firing" } else { "ok" };
+
+    let mut tx = state.db.pool.begin().await?;
+
+    // The row lock keeps a concurrent evaluation from recording the same change
+    let previous: Option<String> = sqlx::query_scalar(
+        r#"
+        WITH previous AS (
+            SELECT id, state FROM alert_rules WHERE id = $1 FOR UPDATE
+        )
+        UPDATE alert_rules r
+        SET state = $2,
+            fired_at = CASE WHEN r.state = 'ok' AND $2 = 'firing' THEN NOW() ELSE r.fired_at END,
+            resolved_at = CASE WHEN r.state = 'firing' AND $2 = 'ok' THEN NOW()
+                               ELSE r.resolved_at END,
+            last_value = $3,
+            last_error = NULL,
+            last_evaluated_at = NOW()
+        FROM previous
+        WHERE r.id = previous.id
+        RETURNING previous.state
+        "#,
+    )
+    .bind(rule.id)
+    .bind(new_state)
+    .bind(value)
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    // Deleted since the rules were loaded, or unchanged
+    if previous.is_none_or(|p| p == new_state) {
+        tx.commit().await?;
+        return Ok(None);
+    }
+
+    let (event_state, message) = if firing {
+        (
+            "firing",
+            format!(
+                "{}: {} is {}, {} {}",
+                rule.name,
+                rule.metric.as_str(),
+                format_value(value),
+                rule.operator.as_str(),
+                format_value(rule.threshold)
+            ),
+        )
+    } else {
+        (
+            "resolved",
+            format!(
+                "{}: {} is back to {}",
+                rule.name,
+                rule.metric.as_str(),
+                format_value(value)
+            ),
+        )
+    };
+
+    let event: AlertEvent = sqlx::query_as(
+        r#"
+        WITH inserted AS (
+            INSERT INTO alert_events (rule_id, state, value, message)
+            VALUES ($1, $2, $3, $4)
+            RETURNING id, rule_id, state, value, message, created_at
+        )
+        SELECT inserted.id, inserted.rule_id, $5::VARCHAR AS rule_name,
+               $6::VARCHAR AS severity, inserted.state, inserted.value, inserted.message,
+               inserted.created_at
+        FROM inserted
+        "#,
+    )
+    .bind(rule.id)
+    .bind(event_state)
+    .bind(value)
+    .bind(&message)
+    .bind(&rule.name)
+    .bind(&rule.severity)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    if firing {
+        tracing::warn!(rule = rule.name, severity = rule.severity, value, "Alert firing");
+    } else {
+        tracing::info!(rule = rule.name, value, "Alert resolved");
+    }
+
+    Ok(Some(event))
+}
+
+/// Value without trailing zeros, rounded to two decimals
+fn format_value(value: f64) -> String {
+    let rounded = format!("{value:.2}");
+    rounded
+        .trim_end_matches('0')
+        .trim_end_matches('.')
+        .to_string()
+}
+
+/// Spawn the periodic evaluation loop
+pub fn spawn(state: AppState, interval: Duration) {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+        loop {
+            ticker.tick().await;
+
+            if let Err(e) = evaluate_all(&state).await {
+                tracing::warn!("Alert evaluation failed: {e}");
+            }
+        }
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{config::Config, db::Database, models::alert::Comparison};
+    use chrono::{DateTime, Utc};
+
+    #[test]
+    fn comparison_holds_at_the_boundary() {
+        let cases = [
+            (Comparison::Above, 10.0, false),
+            (Comparison::Above, 10.01, true),
+            (Comparison::AtLeast, 10.0, true),
+            (Comparison::AtLeast, 9.99, false),
+            (Comparison::Below, 10.0, false),
+            (Comparison::Below, 9.99, true),
+            (Comparison::AtMost, 10.0, true),
+            (Comparison::AtMost, 10.01, false),
+        ];
+        for (operator, value, expected) in cases {
+            assert_eq!(
+                operator.holds(value, 10.0),
+                expected,
+                "{value} {} 10",
+                operator.as_str()
+            );
+        }
+    }
+
+    #[test]
+    fn values_are_formatted_without_trailing_zeros() {
+        assert_eq!(format_value(12.0), "12");
+        assert_eq!(format_value(12.5), "12.5");
+        assert_eq!(format_value(12.345), "12.35");
+        assert_eq!(format_value(0.0), "0");
+    }
+
+    /// State on the database in `DATABASE_URL`, if one is configured
+    async fn database_state() -> Option<AppState> {
+        let Ok(url) = std::env::var("DATABASE_URL") else {
+            eprintln!("DATABASE_URL is not set, skipping");
+            return None;
+        };
+        let pool = sqlx::PgPool::connect(&url).await.unwrap();
+        crate::db::run_migrations(&pool).await.unwrap();
+
+        let mut config = Config::default();
+        config.auth.jwt_secret = "0123456789abcdef0123456789abcdef-test".to_string();
+        let mut state = AppState::for_tests(config);
+        state.db = Database { pool };
+        Some(state)
+    }
+
+    async fn load_rule(state: &AppState, id: i32) -> AlertRule {
+        sqlx::query_as(&format!(
+            "SELECT {ALERT_RULE_COLUMNS} FROM alert_rules WHERE id = $1"
+        ))
+        .bind(id)
+        .fetch_one(&state.db.pool)
+        .await
+        .unwrap()
+    }
+
+    fn times(rule: &AlertRule) -> (Option<DateTime<Utc>>, Option<DateTime<Utc>>) {
+        (rule.fired_at, rule.resolved_at)
+    }
+
+    #[tokio::test]
+    async fn rule_fires_stays_firing_and_resolves() {
+        let Some(state) = database_state().await else {
+            return;
+        };
+        let id: i32 = sqlx::query_scalar(
+            r#"
+            INSERT INTO alert_rules (name, metric, operator, threshold, severity)
+            VALUES ($1, 'crawl_failure_rate', '>=', 10, 'critical')
+            RETURNING id
+            "#,
+        )
+        .bind(format!("test-cycle-{}", std::process::id()))
+        .fetch_one(&state.db.pool)
+        .await
+        .unwrap();
+
+        let rule = load_rule(&state, id).await;
+        assert_eq!((rule.state.as_str(), times(&rule)), ("ok", (None, None)));
+
+        // ok -> firing at the threshold
+        let event = record_value(&state, &rule, 10.0).await.unwrap().unwrap();
+        assert_eq!((event.rule_id, event.state.as_str()), (id, "firing"));
+        assert_eq!(event.severity, "critical");
+        assert!(event.message.ends_with("crawl_failure_rate is 10, >= 10"), "{}", event.message);
+        let rule = load_rule(&state, id).await;
+        assert_eq!((rule.state.as_str(), rule.last_value), ("firing", Some(10.0)));
+        let fired_at = rule.fired_at.expect("fired_at is set");
+        assert_eq!(rule.resolved_at, None);
+
+        // Still above the threshold: no new event, same firing period
+        assert!(record_value(&state, &rule, 25.0).await.unwrap().is_none());
+        let rule = load_rule(&state, id).await;
+        assert_eq!((rule.state.as_str(), rule.last_value), ("firing", Some(25.0)));
+        assert_eq!(times(&rule), (Some(fired_at), None));
+
+        // firing -> ok
+        let event = record_value(&state, &rule, 2.5).await.unwrap().unwrap();
+        assert_eq!(event.state, "resolved");
+        assert!(event.message.ends_with("is back to 2.5"), "{}", event.message);
+        let rule = load_rule(&state, id).await;
+        assert_eq!(rule.state, "ok");
+        assert_eq!(rule.fired_at, Some(fired_at));
+        assert!(rule.resolved_at.is_some_and(|t| t >= fired_at));
+
+        // Staying ok records nothing
+        assert!(record_value(&state, &rule, 0.0).await.unwrap().is_none());
+
+        let events: Vec<(String, Option<f64>)> = sqlx::query_as(
+            "SELECT state, value FROM alert_events WHERE rule_id = $1 ORDER BY id",
+        )
+        .bind(id)
+        .fetch_all(&state.db.pool)
+        .await
+        .unwrap();
+        assert_eq!(
+            events,
+            [
+                ("firing".to_string(), Some(10.0)),
+                ("resolved".to_string(), Some(2.5))
+            ]
+        );
+
+        sqlx::query("DELETE FROM alert_rules WHERE id = $1")
+            .bind(id)
+            .execute(&state.db.pool)
+            .await
+            .unwrap();
+    }
+}
@@ -0,0 +1,600 @@
+//! Authentication for the admin API.
+//!
+//! Users sign in with a username and password (Argon2id hashes in `users`)
+//! and receive a short-lived HS256 access token plus a single-use refresh
+//! token. Access tokens are verified without touching the database, so a
+//! disabled user keeps access until theirs expires. Refresh tokens are stored
+//! as SHA-256 hashes and rotated on every use; presenting one that was
+//! already exchanged revokes all of the user's sessions. The log stream,
+//! which browsers open without headers, takes a separate short-lived ticket
+//! in its URL instead, so access tokens never appear in request lines.
+
+use std::sync::{Arc, LazyLock};
+use std::time::Duration;
+
+use argon2::{
+    password_hash::{PasswordHash, PasswordHasher, PasswordVerifier, SaltString},
+    Argon2,
+};
+use axum::{
+    async_trait,
+    extract::{FromRequestParts, Query},
+    http::{header, request::Parts, Method, Uri},
+};
+use chrono::Utc;
+use jsonwebtoken::{Algorithm, DecodingKey, EncodingKey, Header, Validation};
+use rand::Rng;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use sqlx::FromRow;
+
+use crate::{
+    api_keys::ApiKeyAuth,
+    config::AuthConfig,
+    db::Database,
+    error::{ApiError, ApiResult},
+    models::{StreamTicketResponse, TokenResponse, User, UserRole, SCOPE_ADMIN_READ},
+    AppState,
+};
+
+pub const USER_COLUMNS: &str = "id, username, role, enabled, last_login_at, created_at, updated_at";
+
+/// Shortest password accepted when setting one
+pub const MIN_PASSWORD_LENGTH: usize = 12;
+
+/// Verified against when the username is unknown, so both cases take as long
+static DUMMY_HASH: LazyLock<String> = LazyLock::new(|| {
+    let salt = SaltString::generate(&mut rand::thread_rng());
+    Argon2::default()
+        .hash_password(b"not-a-real-password", &salt)
+        .map(|hash| hash.to_string())
+        .unwrap_or_default()
+});
+
+/// Route a stream ticket opens
+pub const STREAM_TICKET_ROUTE: &str = "/api/admin/logs/stream";
+
+/// Audience of stream tickets; access tokens carry none, and a token with an
+/// unexpected audience is rejected, so neither works in place of the other
+const STREAM_TICKET_AUDIENCE: &str = "log-stream";
+
+/// Long enough to open the stream right after requesting the ticket
+const STREAM_TICKET_TTL: Duration = Duration::from_secs(30);
+
+/// Access token and stream ticket claims
+#[derive(Debug, Serialize, Deserialize)]
+struct Claims {
+    /// User ID
+    sub: String,
+    name: String,
+    role: UserRole,
+    iss: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    aud: Option<String>,
+    iat: i64,
+    exp: i64,
+}
+
+/// Login lookup; the only query that reads the password hash
+#[derive(Debug, FromRow)]
+struct Credentials {
+    id: i32,
+    password_hash: String,
+    enabled: bool,
+}
+
+#[derive(Clone)]
+pub struct Auth {
+    inner: Arc<Inner>,
+}
+
+struct Inner {
+    enabled: bool,
+    issuer: String,
+    encoding: EncodingKey,
+    decoding: DecodingKey,
+    validation: Validation,
+    ticket_validation: Validation,
+    access_ttl: Duration,
+    refresh_ttl: Duration,
+}
+
+impl Auth {
+    pub fn new(config: &AuthConfig) -> Self {
+        let mut validation = Validation::new(Algorithm::HS256);
+        validation.set_issuer(&[&config.issuer]);
+        validation.set_required_spec_claims(&["exp", "iss", "sub"]);
+
+        let mut ticket_validation = validation.clone();
+        ticket_validation.set_audience(&[STREAM_TICKET_AUDIENCE]);
+        ticket_validation.set_required_spec_claims(&["exp", "iss", "sub", "aud"]);
+
+        Self {
+            inner: Arc::new(Inner {
+                enabled: config.enabled,
+                issuer: config.issuer.clone(),
+                encoding: EncodingKey::from_secret(config.jwt_secret.as_bytes()),
+                decoding: DecodingKey::from_secret(config.jwt_secret.as_bytes()),
+                validation,
+                ticket_validation,
+                access_ttl: config.access_token_ttl(),
+                refresh_ttl: config.refresh_token_ttl(),
+            }),
+        }
+    }
+
+    pub fn enabled(&self) -> bool {
+        self.inner.enabled
+    }
+
+    /// Check a username and password and start a session
+    pub async fn login(
+        &self,
+        db: &Database,
+        username: &str,
+        password: &str,
+    ) -> ApiResult<TokenResponse> {
+        self.ensure_enabled()?;
+
+        let credentials: Option<Credentials> =
+            sqlx::query_as("SELECT id, password_hash, enabled FROM users WHERE username = $1")
+                .bind(username)
+                .fetch_optional(&db.pool)
+                .await?;
+
+        let hash = credentials
+            .as_ref()
+            .map_or_else(|| DUMMY_HASH.clone(), |c| c.password_hash.clone());
+        let valid = verify_password(password, hash).await?;
+
+        let id = match credentials {
+            Some(c) if valid && c.enabled => c.id,
+            _ => {
+                tracing::warn!(username, "Failed login");
+                return Err(ApiError::Unauthorized("Invalid username or password".to_string()));
+            }
+        };
+
+        let user: User = sqlx::query_as(&format!(
+            "UPDATE users SET last_login_at = NOW() WHERE id = $1 RETURNING {USER_COLUMNS}"
+        ))
+        .bind(id)
+        .fetch_one(&db.pool)
+        .await?;
+
+        tracing::info!(username, "User signed in");
+        self.issue(db, user).await
+    }
+
+    /// Exchange a refresh token for a new token pair
+    pub async fn refresh(&self, db: &Database, token: &str) -> ApiResult<TokenResponse> {
+        self.ensure_enabled()?;
+        let token_hash = hash_token(token);
+
+        let user_id: Option<i32> = sqlx::query_scalar(
+            r#"
+            UPDATE refresh_tokens
+            SET revoked_at = NOW()
+            WHERE token_hash = $1 AND revoked_at IS NULL AND expires_at > NOW()
+            RETURNING user_id
+            "#,
+        )
+        .bind(&token_hash)
+        .fetch_optional(&db.pool)
+        .await?;
+
+        let Some(user_id) = user_id else {
+            let reused: Option<i32> = sqlx::query_scalar(
+                "SELECT user_id FROM refresh_tokens \
+                 WHERE token_hash = $1 AND revoked_at IS NOT NULL",
+            )
+            .bind(&token_hash)
+            .fetch_optional(&db.pool)
+            .await?;
+
+            if let Some(user_id) = reused {
+                tracing::warn!(user_id, "Revoked refresh token reused, ending all sessions");
+                revoke_all(db, user_id).await?;
+            }
+            return Err(ApiError::Unauthorized("Invalid or expired refresh token".to_string()));
+        };
+
+        let user: Option<User> = sqlx::query_as(&format!(
+            "SELECT {USER_COLUMNS} FROM users WHERE id = $1 AND enabled"
+        ))
+        .bind(user_id)
+        .fetch_optional(&db.pool)
+        .await?;
+
+        match user {
+            Some(user) => self.issue(db, user).await,
+            None => Err(ApiError::Unauthorized("Account is disabled".to_string())),
+        }
+    }
+
+    /// Revoke a refresh token; unknown tokens are ignored
+    pub async fn logout(&self, db: &Database, token: &str) -> ApiResult<()> {
+        sqlx::query(
+            "UPDATE refresh_tokens SET revoked_at = NOW() \
+             WHERE token_hash = $1 AND revoked_at IS NULL",
+        )
+        .bind(hash_token(token))
+        .execute(&db.pool)
+        .await?;
+
+        Ok(())
+    }
+
+    /// Issue a ticket that opens the log stream as `user`
+    pub fn stream_ticket(&self, user: &AuthUser) -> ApiResult<StreamTicketResponse> {
+        self.ensure_enabled()?;
+
+        let ttl = STREAM_TICKET_TTL.as_secs();
+        let ticket = self.sign(user, Some(STREAM_TICKET_AUDIENCE), ttl)?;
+
+        Ok(StreamTicketResponse {
+            ticket,
+            expires_in: ttl,
+        })
+    }
+
+    /// Verify an access token
+    fn verify(&self, token: &str) -> ApiResult<AuthUser> {
+        self.decode(token, &self.inner.validation)
+            .map_err(|e| ApiError::Unauthorized(format!("Invalid access token: {e}")))
+    }
+
+    /// Verify a stream ticket
+    fn verify_ticket(&self, ticket: &str) -> ApiResult<AuthUser> {
+        self.decode(ticket, &self.inner.ticket_validation)
+            .map_err(|e| ApiError::Unauthorized(format!("Invalid stream ticket: {e}")))
+    }
+
+    fn decode(&self, token: &str, validation: &Validation) -> Result<AuthUser, String> {
+        let claims = jsonwebtoken::decode::<Claims>(token, &self.inner.decoding, validation)
+            .map_err(|e| e.to_string())?
+            .claims;
+
+        let id = claims.sub.parse().map_err(|_| "invalid subject".to_string())?;
+
+        Ok(AuthUser {
+            id,
+            username: claims.name,
+            role: claims.role,
+        })
+    }
+
+    fn sign(&self, user: &AuthUser, audience: Option<&str>, ttl: u64) -> ApiResult<String> {
+        let now = Utc::now().timestamp();
+        let claims = Claims {
+            sub: user.id.to_string(),
+            name: user.username.clone(),
+            role: user.role,
+            iss: self.inner.issuer.clone(),
+            aud: audience.map(String::from),
+            iat: now,
+            exp: now + ttl as i64,
+        };
+
+        jsonwebtoken::encode(&Header::default(), &claims, &self.inner.encoding)
+            .map_err(|e| ApiError::InternalServerError(format!("Failed to sign token: {e}")))
+    }
+
+    /// Sign an access token and store a new refresh token for the user
+    async fn issue(&self, db: &Database, user: User) -> ApiResult<TokenResponse> {
+        let access_ttl = self.inner.access_ttl.as_secs();
+        let refresh_ttl = self.inner.refresh_ttl.as_secs();
+
+        let signed_in = AuthUser {
+            id: user.id,
+            username: user.username.clone(),
+            role: user.role,
+        };
+        let access_token = self.sign(&signed_in, None, access_ttl)?;
+
+        let refresh_token = hex::encode(rand::thread_rng().gen::<[u8; 32]>());
+
+        let mut tx = db.pool.begin().await?;
+        sqlx::query("DELETE FROM refresh_tokens WHERE user_id = $1 AND expires_at < NOW()")
+            .bind(user.id)
+            .execute(&mut *tx)
+            .await?;
+        sqlx::query(
+            r#"
+            INSERT INTO refresh_tokens (user_id, token_hash, expires_at)
+            VALUES ($1, $2, NOW() + make_interval(secs => $3))
+            "#,
+        )
+        .bind(user.id)
+        .bind(hash_token(&refresh_token))
+        .bind(refresh_ttl as f64)
+        .execute(&mut *tx)
+        .await?;
+        tx.commit().await?;
+
+        Ok(TokenResponse {
+            access_token,
+            token_type: "Bearer".to_string(),
+            expires_in: access_ttl,
+            refresh_token,
+            refresh_expires_in: refresh_ttl,
+            user,
+        })
+    }
+
+    fn ensure_enabled(&self) -> ApiResult<()> {
+        if self.inner.enabled {
+            Ok(())
+        } else {
+            Err(ApiError::NotFound("Authentication is disabled".to_string()))
+        }
+    }
+}
+
+/// Revoke every active refresh token of a user
+pub async fn revoke_all(db: &Database, user_id: i32) -> ApiResult<u64> {
+    let result = sqlx::query(
+        "UPDATE refresh_tokens SET revoked_at = NOW() WHERE user_id = $1 AND revoked_at IS NULL",
+    )
+    .bind(user_id)
+    .execute(&db.pool)
+    .await?;
+
+    Ok(result.rows_affected())
+}
+
+/// Argon2id hash in PHC string format
+pub async fn hash_password(password: &str) -> ApiResult<String> {
+    let password = password.to_string();
+    tokio::task::spawn_blocking(move || {
+        let salt = SaltString::generate(&mut rand::thread_rng());
+        Argon2::default()
+            .hash_password(password.as_bytes(), &salt)
+            .map(|hash| hash.to_string())
+            .map_err(|e| ApiError::InternalServerError(format!("Failed to hash password: {e}")))
+    })
+    .await
+    .map_err(|e| ApiError::InternalServerError(format!("Password hashing task failed: {e}")))?
+}
+
+/// Hashing is deliberately slow, so it runs off the async workers
+async fn verify_password(password: &str, hash: String) -> ApiResult<bool> {
+    let password = password.to_string();
+    tokio::task::spawn_blocking(move || {
+        PasswordHash::new(&hash).is_ok_and(|parsed| {
+            Argon2::default()
+                .verify_password(password.as_bytes(), &parsed)
+                .is_ok()
+        })
+    })
+    .await
+    .map_err(|e| ApiError::InternalServerError(format!("Password check task failed: {e}")))
+}
+
+/// Refresh tokens are random, so an unsalted hash is enough
+fn hash_token(token: &str) -> String {
+    hex::encode(Sha256::digest(token.as_bytes()))
+}
+
+/// The signed-in user making a request
+///
+/// Rejects requests without a valid access token with 401, and requests made
+/// with an API key with 403. When authentication is disabled every other
+/// request acts as an anonymous admin.
+#[derive(Debug, Clone, Serialize)]
+pub struct AuthUser {
+    pub id: i32,
+    pub username: String,
+    pub role: UserRole,
+}
+
+impl AuthUser {
+    fn anonymous() -> Self {
+        Self {
+            id: 0,
+            username: "anonymous".to_string(),
+            role: UserRole::Admin,
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct TicketQuery {
+    ticket: Option<String>,
+}
+
+#[async_trait]
+impl FromRequestParts<AppState> for AuthUser {
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &AppState) -> Result<Self, ApiError> {
+        // Already verified by the router-level check
+        if let Some(user) = parts.extensions.get::<AuthUser>() {
+            return Ok(user.clone());
+        }
+        if let Some(key) = parts.extensions.get::<ApiKeyAuth>() {
+            return Err(ApiError::Forbidden(format!(
+                "API key {} cannot act as a user",
+                key.name
+            )));
+        }
+
+        let user = if !state.auth.enabled() {
+            Self::anonymous()
+        } else if let Some(token) = access_token(parts) {
+            state.auth.verify(&token)?
+        } else if let Some(ticket) = stream_ticket(parts) {
+            state.auth.verify_ticket(&ticket)?
+        } else {
+            return Err(ApiError::Unauthorized("Missing access token".to_string()));
+        };
+
+        parts.extensions.insert(user.clone());
+        Ok(user)
+    }
+}
+
+/// Bearer token from the Authorization header
+fn access_token(parts: &Parts) -> Option<String> {
+    let value = parts.headers.get(header::AUTHORIZATION)?.to_str().ok()?;
+    let (scheme, token) = value.split_once(' ')?;
+    scheme
+        .eq_ignore_ascii_case("bearer")
+        .then(|| token.trim().to_string())
+}
+
+/// `ticket` query parameter of a log stream request
+///
+/// Browsers cannot set headers on an `EventSource`, so the stream takes a
+/// ticket from `POST /api/auth/stream-ticket` in its URL. No other route does.
+fn stream_ticket(parts: &Parts) -> Option<String> {
+    if parts.uri.path() != STREAM_TICKET_ROUTE {
+        return None;
+    }
+
+    Query::<TicketQuery>::try_from_uri(&parts.uri)
+        .ok()
+        .and_then(|Query(q)| q.ticket)
+}
+
+/// `uri` with the value of every `ticket` or `access_token` parameter
+/// replaced, for request logs
+pub fn redact_uri(uri: &Uri) -> String {
+    let Some(query) = uri.query() else {
+        return uri.to_string();
+    };
+
+    let query: Vec<String> = query
+        .split('&')
+        .map(|pair| match pair.split_once('=') {
+            Some((name, _)) if matches!(name, "ticket" | "access_token") => {
+                format!("{name}=[redacted]")
+            }
+            _ => pair.to_string(),
+        })
+        .collect();
+    format!("{}?{}", uri.path(), query.join("&"))
+}
+
+/// Gate for every /api/admin route: signed-in users and keys with
+/// `admin:read` may read, only admins may change anything
+pub struct AdminAccess;
+
+#[async_trait]
+impl FromRequestParts<AppState> for AdminAccess {
+    type Rejection = ApiError;
+
+    async fn from_request_parts(parts: &mut Parts, state: &AppState) -> Result<Self, ApiError> {
+        let read_only = matches!(parts.method, Method::GET | Method::HEAD);
+
+        if let Some(key) = parts.extensions.get::<ApiKeyAuth>() {
+            key.require(SCOPE_ADMIN_READ)?;
+            if !read_only {
+                return Err(ApiError::Forbidden(format!(
+                    "API key {} has read-only access",
+                    key.name
+                )));
+            }
+            return Ok(Self);
+        }
+
+        let user = AuthUser::from_request_parts(parts, state).await?;
+        if user.role != UserRole::Admin && !read_only {
+            return Err(ApiError::Forbidden(format!(
+                "{} has read-only access",
+                user.username
+            )));
+        }
+
+        Ok(Self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::http::Request;
+
+    fn auth() -> Auth {
+        Auth::new(&AuthConfig {
+            jwt_secret: "0123456789abcdef0123456789abcdef-test".to_string(),
+            ..AuthConfig::default()
+        })
+    }
+
+    fn alice() -> AuthUser {
+        AuthUser {
+            id: 7,
+            username: "alice".to_string(),
+            role: UserRole::Viewer,
+        }
+    }
+
+    fn parts(uri: &str) -> Parts {
+        Request::builder().uri(uri).body(()).unwrap().into_parts().0
+    }
+
+    #[test]
+    fn stream_ticket_is_not_an_access_token() {
+        let auth = auth();
+        let ticket = auth.stream_ticket(&alice()).unwrap();
+
+        assert_eq!(ticket.expires_in, 30);
+        let user = auth.verify_ticket(&ticket.ticket).unwrap();
+        assert_eq!((user.id, user.username.as_str()), (7, "alice"));
+        assert!(matches!(auth.verify(&ticket.ticket), Err(ApiError::Unauthorized(_))));
+    }
+
+    #[test]
+    fn access_token_is_not_a_stream_ticket() {
+        let auth = auth();
+        let token = auth.sign(&alice(), None, 900).unwrap();
+
+        assert_eq!(auth.verify(&token).unwrap().id, 7);
+        assert!(matches!(auth.verify_ticket(&token), Err(ApiError::Unauthorized(_))));
+    }
+
+    #[test]
+    fn expired_ticket_is_rejected() {
+        let auth = auth();
+        let claims = Claims {
+            sub: "7".to_string(),
+            name: "alice".to_string(),
+            role: UserRole::Viewer,
+            iss: "news-api".to_string(),
+            aud: Some(STREAM_TICKET_AUDIENCE.to_string()),
+            iat: Utc::now().timestamp() - 600,
+            exp: Utc::now().timestamp() - 300,
+        };
+        let ticket =
+            jsonwebtoken::encode(&Header::default(), &claims, &auth.inner.encoding).unwrap();
+
+        assert!(auth.verify_ticket(&ticket).is_err());
+    }
+
+    #[test]
+    fn ticket_is_only_read_on_the_stream_route() {
+        assert_eq!(
+            stream_ticket(&parts("/api/admin/logs/stream?level=warn&ticket=abc")),
+            Some("abc".to_string())
+        );
+        assert_eq!(stream_ticket(&parts("/api/admin/logs?ticket=abc")), None);
+        assert_eq!(stream_ticket(&parts("/api/admin/logs/stream/x?ticket=abc")), None);
+        assert_eq!(stream_ticket(&parts("/api/admin/logs/stream?access_token=abc")), None);
+    }
+
+    #[test]
+    fn redact_uri_hides_credentials() {
+        let uri: Uri = "/api/admin/logs/stream?level=warn&ticket=eyJ.abc&access_token=eyJ.def"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            redact_uri(&uri),
+            "/api/admin/logs/stream?level=warn&ticket=[redacted]&access_token=[redacted]"
+        );
+
+        let uri: Uri = "/api/news?page=2&tickets=1".parse().unwrap();
+        assert_eq!(redact_uri(&uri), "/api/news?page=2&tickets=1");
+        assert_eq!(redact_uri(&"/api/health".parse().unwrap()), "/api/health");
+    }
+}
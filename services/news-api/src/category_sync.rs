@@ -0,0 +1,211 @@
+//! Keeps `categories.article_count` in sync with the search index.
+//!
+//! Counts come from a terms aggregation on `category`, with alias labels
+//! counted towards their canonical category. Newly seen categories are
+//! inserted and categories missing from the index are set to zero. The job
+//! runs on a schedule and can be triggered from the admin API; a lock makes
+//! sure only one sync runs at a time.
+
+use chrono::Utc;
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Mutex;
+
+use crate::{
+    category_tree::CategoryTree,
+    db::Database,
+    error::{ApiError, ApiResult},
+    models::{CategoryDrift, CategoryDriftResponse, CategorySyncReport},
+    response_cache::CacheRoute,
+    search::SearchClient,
+    AppState,
+};
+
+#[derive(Clone, Default)]
+pub struct CategorySync {
+    lock: Arc<Mutex<()>>,
+}
+
+impl CategorySync {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Recompute counts now, failing with `Conflict` if a sync is already running
+    pub async fn trigger(
+        &self,
+        db: &Database,
+        search: &SearchClient,
+    ) -> ApiResult<CategorySyncReport> {
+        let _guard = self
+            .lock
+            .try_lock()
+            .map_err(|_| ApiError::Conflict("Category sync is already running".to_string()))?;
+
+        sync_category_counts(db, search).await
+    }
+
+    /// Recompute counts, waiting for a running sync to finish first
+    pub async fn run(
+        &self,
+        db: &Database,
+        search: &SearchClient,
+    ) -> ApiResult<CategorySyncReport> {
+        let _guard = self.lock.lock().await;
+
+        sync_category_counts(db, search).await
+    }
+}
+
+/// Spawn the periodic sync loop
+pub fn spawn(state: AppState, interval: Duration) {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+        loop {
+            ticker.tick().await;
+
+            match state.category_sync.run(&state.db, &state.search).await {
+                Ok(report) => {
+                    state.cache.invalidate(&[CacheRoute::Categories]);
+                    tracing::info!(
+                        inserted = report.inserted,
+                        updated = report.updated,
+                        zeroed = report.zeroed,
+                        "Category counts synced"
+                    );
+                }
+                Err(e) => tracing::warn!("Category sync failed: {e}"),
+            }
+        }
+    });
+}
+
+async fn sync_category_counts(
+    db: &Database,
+    search: &SearchClient,
+) -> ApiResult<CategorySyncReport> {
+    let counts = canonical_counts(db, search).await?;
+    let synced_at = Utc::now();
+
+    let mut tx = db.pool.begin().await?;
+    let mut inserted = 0;
+    let mut updated = 0;
+
+    for (name, count) in &counts {
+        let was_inserted: bool = sqlx::query_scalar(
+            r#"
+            INSERT INTO categories (name, article_count, last_synced_at)
+            VALUES ($1, $2, $3)
+            ON CONFLICT (name) DO UPDATE
+            SET article_count = EXCLUDED.article_count,
+                last_synced_at = EXCLUDED.last_synced_at
+            RETURNING (xmax = 0)
+            "#,
+        )
+        .bind(name)
+        .bind(i32::try_from(*count).unwrap_or(i32::MAX))
+        .bind(synced_at)
+        .fetch_one(&mut *tx)
+        .await?;
+
+        if was_inserted {
+            inserted += 1;
+        } else {
+            updated += 1;
+        }
+    }
+
+    let names: Vec<&str> = counts.keys().map(String::as_str).collect();
+    let zeroed = sqlx::query(
+        r#"
+        UPDATE categories
+        SET article_count = 0, last_synced_at = $2
+        WHERE NOT (name = ANY($1))
+        "#,
+    )
+    .bind(&names)
+    .bind(synced_at)
+    .execute(&mut *tx)
+    .await?
+    .rows_affected() as i64;
+
+    tx.commit().await?;
+
+    Ok(CategorySyncReport {
+        synced_at,
+        inserted,
+        updated,
+        zeroed,
+    })
+}
+
+/// Index counts keyed by canonical category name
+///
+/// Labels that are aliases of a category are counted towards that category;
+/// unknown labels are kept as-is so that the sync registers them.
+async fn canonical_counts(
+    db: &Database,
+    search: &SearchClient,
+) -> ApiResult<HashMap<String, i64>> {
+    let tree = CategoryTree::load(&db.pool).await?;
+
+    let mut counts: HashMap<String, i64> = HashMap::new();
+    for (label, count) in search.get_category_counts().await? {
+        let name = tree
+            .resolve(&label)
+            .map(|category| category.name.clone())
+            .unwrap_or(label);
+        *counts.entry(name).or_default() += count;
+    }
+
+    Ok(counts)
+}
+
+/// Compare stored counts against the index without writing anything
+pub async fn category_drift(
+    db: &Database,
+    search: &SearchClient,
+) -> ApiResult<CategoryDriftResponse> {
+    let mut index_counts = canonical_counts(db, search).await?;
+
+    let rows: Vec<(String, i32)> = sqlx::query_as("SELECT name, article_count FROM categories")
+        .fetch_all(&db.pool)
+        .await?;
+
+    let last_synced_at = sqlx::query_scalar("SELECT MAX(last_synced_at) FROM categories")
+        .fetch_one(&db.pool)
+        .await?;
+
+    let mut drift = Vec::new();
+    for (name, db_count) in rows {
+        let index_count = index_counts.remove(&name);
+        let difference = index_count.unwrap_or(0) - db_count as i64;
+        if difference != 0 {
+            drift.push(CategoryDrift {
+                name,
+                db_count: Some(db_count),
+                index_count,
+                difference,
+            });
+        }
+    }
+    for (name, index_count) in index_counts {
+        drift.push(CategoryDrift {
+            name,
+            db_count: None,
+            index_count: Some(index_count),
+            difference: index_count,
+        });
+    }
+    drift.sort_by_key(|d| Reverse(d.difference.abs()));
+
+    Ok(CategoryDriftResponse {
+        in_sync: drift.is_empty(),
+        last_synced_at,
+        drift,
+    })
+}
@@ -0,0 +1,171 @@
+//! In-memory view of the category hierarchy and its aliases.
+//!
+//! Sources label articles inconsistently ("IT", "IT/과학", "과학"), so labels
+//! are resolved to a canonical category either by name or through the
+//! `category_aliases` table. A category covers itself, its aliases and every
+//! descendant, which is what `/api/news?category=` filters on and what the
+//! rolled-up counts of `/api/categories` add up.
+
+use std::collections::HashMap;
+
+use crate::{
+    db::DbPool,
+    models::{Category, CategoryNode},
+};
+
+pub struct CategoryTree {
+    categories: Vec<Category>,
+    aliases: HashMap<i32, Vec<String>>,
+    by_label: HashMap<String, usize>,
+}
+
+impl CategoryTree {
+    /// Load all categories and aliases from Postgres
+    pub async fn load(pool: &DbPool) -> Result<Self, sqlx::Error> {
+        let categories: Vec<Category> = sqlx::query_as(
+            r#"
+            SELECT id, name, parent_id, article_count, last_synced_at
+            FROM categories
+            ORDER BY article_count DESC, name
+            "#,
+        )
+        .fetch_all(pool)
+        .await?;
+
+        let alias_rows: Vec<(String, i32)> =
+            sqlx::query_as("SELECT alias, category_id FROM category_aliases ORDER BY alias")
+                .fetch_all(pool)
+                .await?;
+
+        Ok(Self::new(categories, alias_rows))
+    }
+
+    fn new(categories: Vec<Category>, alias_rows: Vec<(String, i32)>) -> Self {
+        let mut by_label: HashMap<String, usize> = categories
+            .iter()
+            .enumerate()
+            .map(|(i, c)| (c.name.clone(), i))
+            .collect();
+
+        let mut aliases: HashMap<i32, Vec<String>> = HashMap::new();
+        for (alias, category_id) in alias_rows {
+            if let Some(i) = categories.iter().position(|c| c.id == category_id) {
+                by_label.insert(alias.clone(), i);
+                aliases.entry(category_id).or_default().push(alias);
+            }
+        }
+
+        Self {
+            categories,
+            aliases,
+            by_label,
+        }
+    }
+
+    pub fn len(&self) -> usize {
+        self.categories.len()
+    }
+
+    pub fn contains(&self, id: i32) -> bool {
+        self.get(id).is_some()
+    }
+
+    /// Canonical category for a label, matched by name or alias
+    pub fn resolve(&self, label: &str) -> Option<&Category> {
+        self.by_label.get(label).map(|&i| &self.categories[i])
+    }
+
+    /// Whether `ancestor_id` is `id` itself or one of its ancestors
+    pub fn is_ancestor_or_self(&self, ancestor_id: i32, id: i32) -> bool {
+        let mut current = Some(id);
+        // Bounded walk so a corrupt cycle cannot loop forever
+        for _ in 0..=self.categories.len() {
+            match current {
+                Some(c) if c == ancestor_id => return true,
+                Some(c) => current = self.get(c).and_then(|cat| cat.parent_id),
+                None => return false,
+            }
+        }
+        false
+    }
+
+    /// Whether a category is a leaf without aliases, which can be merged into
+    /// another category as one of its aliases
+    pub fn is_mergeable(&self, id: i32) -> bool {
+        !self.aliases.contains_key(&id) && !self.categories.iter().any(|c| c.parent_id == Some(id))
+    }
+
+    /// Every index label covered by a category: names and aliases of its subtree
+    ///
+    /// Unknown labels are passed through unchanged.
+    pub fn expand(&self, label: &str) -> Vec<String> {
+        let Some(root) = self.resolve(label) else {
+            return vec![label.to_string()];
+        };
+
+        let mut labels = Vec::new();
+        for category in &self.categories {
+            if self.is_ancestor_or_self(root.id, category.id) {
+                labels.push(category.name.clone());
+                if let Some(aliases) = self.aliases.get(&category.id) {
+                    labels.extend(aliases.iter().cloned());
+                }
+            }
+        }
+        labels
+    }
+
+    /// Build the category forest with counts rolled up from descendants
+    pub fn into_nodes(self) -> Vec<CategoryNode> {
+        let mut children: HashMap<Option<i32>, Vec<&Category>> = HashMap::new();
+        for category in &self.categories {
+            // Parents that no longer exist turn their children into roots
+            let parent = category.parent_id.filter(|id| self.contains(*id));
+            children.entry(parent).or_default().push(category);
+        }
+
+        let roots = children.get(&None).cloned().unwrap_or_default();
+        roots
+            .into_iter()
+            .map(|category| self.node(category, &children, 0))
+            .collect()
+    }
+
+    fn node(
+        &self,
+        category: &Category,
+        children: &HashMap<Option<i32>, Vec<&Category>>,
+        depth: usize,
+    ) -> CategoryNode {
+        let child_nodes: Vec<CategoryNode> = if depth < self.categories.len() {
+            children
+                .get(&Some(category.id))
+                .map(|list| {
+                    list.iter()
+                        .map(|child| self.node(child, children, depth + 1))
+                        .collect()
+                })
+                .unwrap_or_default()
+        } else {
+            Vec::new()
+        };
+
+        let total_count = category.article_count as i64
+            + child_nodes.iter().map(|c| c.total_count).sum::<i64>();
+
+        CategoryNode {
+            id: category.id,
+            name: category.name.clone(),
+            parent_id: category.parent_id,
+            aliases: self.aliases.get(&category.id).cloned().unwrap_or_default(),
+            article_count: category.article_count,
+            total_count,
+            last_synced_at: category.last_synced_at,
+            children: child_nodes,
+        }
+    }
+
+    fn get(&self, id: i32) -> Option<&Category> {
+        self.categories.iter().find(|c| c.id == id)
+    }
+}
@@ -0,0 +1,181 @@
+use chrono::NaiveDate;
+use clap::{Args, Parser, Subcommand};
+use std::path::PathBuf;
+
+use crate::models::UserRole;
+
+/// Barami News API server and operations tool
+#[derive(Debug, Parser)]
+#[command(name = "news-api", version, about)]
+pub struct Cli {
+    /// TOML config file (overrides NEWS_API_CONFIG)
+    #[arg(long, global = true, value_name = "PATH")]
+    pub config: Option<PathBuf>,
+
+    /// Print the effective configuration with secrets redacted and exit
+    #[arg(long, global = true)]
+    pub print_config: bool,
+
+    #[command(subcommand)]
+    pub command: Option<Command>,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// Run the HTTP server (default)
+    Serve,
+
+    /// Apply pending database migrations
+    Migrate,
+
+    /// Manage the OpenSearch article index
+    Index {
+        #[command(subcommand)]
+        command: IndexCommand,
+    },
+
+    /// Load sample categories, sources, crawl stats and articles
+    Seed,
+
+    /// Generate reproducible synthetic articles and crawl stats
+    Generate(GenerateArgs),
+
+    /// Export all articles as NDJSON
+    Export(ExportArgs),
+
+    /// Statistics maintenance
+    Stats {
+        #[command(subcommand)]
+        command: StatsCommand,
+    },
+
+    /// Manage accounts that can sign in to the admin API
+    Users {
+        #[command(subcommand)]
+        command: UsersCommand,
+    },
+}
+
+#[derive(Debug, Subcommand)]
+pub enum IndexCommand {
+    /// Create the index with the article mapping if it does not exist
+    Create {
+        /// Index name (defaults to the configured index)
+        #[arg(long)]
+        name: Option<String>,
+    },
+
+    /// Copy all articles into a new index created with the current mapping
+    Reindex {
+        /// Destination index
+        #[arg(long)]
+        dest: String,
+
+        /// Source index (defaults to the configured index)
+        #[arg(long)]
+        source: Option<String>,
+    },
+
+    /// Fill in `source_id` on articles indexed with only a source name
+    BackfillSourceIds {
+        /// Index name (defaults to the configured index)
+        #[arg(long)]
+        name: Option<String>,
+    },
+
+    /// Check that the index exists and its mapping matches what the API expects
+    Verify {
+        /// Index name (defaults to the configured index)
+        #[arg(long)]
+        name: Option<String>,
+    },
+}
+
+#[derive(Debug, Args)]
+pub struct GenerateArgs {
+    /// Number of articles to generate
+    #[arg(long, default_value_t = 10_000)]
+    pub articles: u64,
+
+    /// Random seed; the same seed and options always produce the same data
+    #[arg(long, default_value_t = 42)]
+    pub seed: u64,
+
+    /// Number of days the articles are spread over
+    #[arg(long, default_value_t = 30)]
+    pub days: u32,
+
+    /// Last day of the range (defaults to today; pin it for reproducible runs)
+    #[arg(long)]
+    pub end_date: Option<NaiveDate>,
+
+    /// Articles per bulk request
+    #[arg(long, default_value_t = 1000)]
+    pub batch_size: usize,
+
+    /// Write the articles as NDJSON to this file instead of indexing them
+    #[arg(long, short)]
+    pub output: Option<PathBuf>,
+
+    /// Leave crawl_stats alone
+    #[arg(long)]
+    pub skip_stats: bool,
+
+    /// Do not resync category counts afterwards
+    #[arg(long)]
+    pub skip_categories: bool,
+}
+
+#[derive(Debug, Args)]
+pub struct ExportArgs {
+    /// Output file (defaults to stdout)
+    #[arg(long, short)]
+    pub output: Option<PathBuf>,
+
+    /// Documents fetched per scroll page
+    #[arg(long, default_value_t = 500)]
+    pub batch_size: i64,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum StatsCommand {
+    /// Rebuild daily crawl_stats from crawl_runs and resync category counts
+    Recompute {
+        /// First day to rebuild (defaults to the earliest crawl run)
+        #[arg(long)]
+        from: Option<NaiveDate>,
+
+        /// Last day to rebuild (defaults to the latest crawl run)
+        #[arg(long)]
+        to: Option<NaiveDate>,
+
+        /// Only rebuild crawl_stats, leave category counts alone
+        #[arg(long)]
+        skip_categories: bool,
+    },
+}
+
+/// Passwords are read from NEWS_API_PASSWORD, or from the first line of stdin
+#[derive(Debug, Subcommand)]
+pub enum UsersCommand {
+    /// Create an account
+    Add {
+        username: String,
+
+        /// `admin` (full access) or `viewer` (read-only)
+        #[arg(long, default_value = "viewer")]
+        role: UserRole,
+    },
+
+    /// Set a new password and end the user's sessions
+    Passwd { username: String },
+
+    /// Block sign-in and end the user's sessions
+    Disable { username: String },
+
+    /// Allow sign-in again
+    Enable { username: String },
+
+    /// List accounts
+    List,
+}
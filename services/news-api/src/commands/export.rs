@@ -0,0 +1,41 @@
+use std::fs::File;
+use std::io::{BufWriter, Write};
+
+use crate::{cli::ExportArgs, search::SearchClient};
+
+/// `news-api export` - write every article as one JSON object per line
+pub async fn run(search: &SearchClient, args: &ExportArgs) -> anyhow::Result<()> {
+    let output: Box<dyn Write> = match &args.output {
+        Some(path) => Box::new(File::create(path)?),
+        None => Box::new(std::io::stdout().lock()),
+    };
+    let mut writer = BufWriter::new(output);
+
+    let mut page = search.scroll_start(args.batch_size.clamp(1, 10_000)).await?;
+    let mut exported = 0usize;
+
+    loop {
+        if page.articles.is_empty() {
+            break;
+        }
+        for article in &page.articles {
+            serde_json::to_writer(&mut writer, article)?;
+            writer.write_all(b"\n")?;
+        }
+        exported += page.articles.len();
+
+        match page.scroll_id.as_deref() {
+            Some(scroll_id) => page = search.scroll_next(scroll_id).await?,
+            None => break,
+        }
+    }
+
+    if let Some(scroll_id) = page.scroll_id.as_deref() {
+        search.scroll_clear(scroll_id).await?;
+    }
+
+    writer.flush()?;
+    eprintln!("Exported {exported} articles");
+
+    Ok(())
+}
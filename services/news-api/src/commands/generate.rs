@@ -0,0 +1,534 @@
+//! Deterministic synthetic articles and crawl stats for development and load tests.
+//!
+//! Every article is drawn from its own ChaCha stream of `--seed`, keyed by its
+//! position, so a seed always produces the same articles regardless of batch
+//! size, and a larger `--articles` count extends a smaller run instead of
+//! reshuffling it. Documents get fixed IDs (`synthetic-<seed>-<n>`), so running
+//! the same generation twice overwrites instead of duplicating.
+
+use chrono::{Datelike, Duration, NaiveDate, TimeZone, Utc, Weekday};
+use rand::distributions::WeightedIndex;
+use rand::prelude::*;
+use rand_chacha::ChaCha8Rng;
+use std::collections::{BTreeMap, HashMap};
+use std::fs::File;
+use std::io::{BufWriter, Write};
+
+use crate::{
+    category_sync::CategorySync,
+    cli::GenerateArgs,
+    db::{self, Database},
+    models::Article,
+    search::SearchClient,
+};
+
+/// Stream offset of the per-day crawl failure draws, far away from article streams
+const STATS_STREAM: u64 = 1 << 63;
+
+struct SourceSpec {
+    name: &'static str,
+    homepage: &'static str,
+    language: Language,
+    default_category: &'static str,
+    /// Relative publishing volume
+    weight: u32,
+}
+
+#[derive(Clone, Copy, PartialEq)]
+enum Language {
+    Korean,
+    English,
+}
+
+impl Language {
+    fn code(self) -> &'static str {
+        match self {
+            Self::Korean => "ko",
+            Self::English => "en",
+        }
+    }
+}
+
+const SOURCES: &[SourceSpec] = &[
+    SourceSpec {
+        name: "뉴스1",
+        homepage: "https://www.news1.kr",
+        language: Language::Korean,
+        default_category: "정치",
+        weight: 30,
+    },
+    SourceSpec {
+        name: "이코노미",
+        homepage: "https://economy.example.com",
+        language: Language::Korean,
+        default_category: "경제",
+        weight: 20,
+    },
+    SourceSpec {
+        name: "테크뉴스",
+        homepage: "https://tech.example.com",
+        language: Language::Korean,
+        default_category: "IT/과학",
+        weight: 15,
+    },
+    SourceSpec {
+        name: "바람일보",
+        homepage: "https://daily.example.com",
+        language: Language::Korean,
+        default_category: "사회",
+        weight: 25,
+    },
+    SourceSpec {
+        name: "스포츠온",
+        homepage: "https://sports.example.com",
+        language: Language::Korean,
+        default_category: "스포츠",
+        weight: 12,
+    },
+    SourceSpec {
+        name: "연예플러스",
+        homepage: "https://entertainment.example.com",
+        language: Language::Korean,
+        default_category: "연예",
+        weight: 10,
+    },
+    SourceSpec {
+        name: "Seoul Wire",
+        homepage: "https://seoulwire.example.com",
+        language: Language::English,
+        default_category: "국제",
+        weight: 12,
+    },
+    SourceSpec {
+        name: "Asia Tech Review",
+        homepage: "https://asiatech.example.com",
+        language: Language::English,
+        default_category: "IT/과학",
+        weight: 8,
+    },
+    SourceSpec {
+        name: "Global Markets Daily",
+        homepage: "https://markets.example.com",
+        language: Language::English,
+        default_category: "경제",
+        weight: 8,
+    },
+];
+
+struct Topic {
+    category: &'static str,
+    /// Alternative labels sources use for the same category
+    labels: &'static [&'static str],
+    weight: u32,
+    ko_subjects: &'static [&'static str],
+    ko_events: &'static [&'static str],
+    en_subjects: &'static [&'static str],
+    en_events: &'static [&'static str],
+}
+
+const TOPICS: &[Topic] = &[
+    Topic {
+        category: "정치",
+        labels: &[],
+        weight: 15,
+        ko_subjects: &["국회", "정부", "여야 지도부", "대통령실", "국무총리", "선거관리위원회"],
+        ko_events: &["예산안 처리 합의", "개헌 논의 재점화", "국정감사 일정 확정", "특별법 통과"],
+        en_subjects: &["The National Assembly", "The presidential office", "Ruling party leaders"],
+        en_events: &["reach a budget deal", "debate constitutional reform", "pass a special act"],
+    },
+    Topic {
+        category: "경제",
+        labels: &[],
+        weight: 18,
+        ko_subjects: &["한국은행", "코스피", "기획재정부", "수출 기업", "금융위원회", "주요 은행"],
+        ko_events: &["기준금리 동결", "3거래일 연속 상승", "물가 안정 대책 발표", "수출 실적 회복"],
+        en_subjects: &["The Bank of Korea", "The KOSPI", "Chipmakers", "Exporters"],
+        en_events: &["hold rates steady", "extend gains", "report record exports"],
+    },
+    Topic {
+        category: "사회",
+        labels: &[],
+        weight: 16,
+        ko_subjects: &["서울시", "교육부", "경찰청", "보건복지부", "지방자치단체", "시민단체"],
+        ko_events: &["대중교통 요금 조정", "입시 제도 개편", "안전 점검 강화", "돌봄 지원 확대"],
+        en_subjects: &["Seoul city officials", "The education ministry", "Police"],
+        en_events: &["adjust transit fares", "overhaul admissions", "step up safety checks"],
+    },
+    Topic {
+        category: "국제",
+        labels: &[],
+        weight: 10,
+        ko_subjects: &["미국 정부", "유럽연합", "일본", "중국", "유엔 안전보장이사회", "G20 정상"],
+        ko_events: &["무역 협상 재개", "공동 성명 채택", "제재 방안 논의", "기후 협약 이행 점검"],
+        en_subjects: &["Washington", "The European Union", "Tokyo", "G20 leaders"],
+        en_events: &["resume trade talks", "adopt a joint statement", "weigh new sanctions"],
+    },
+    Topic {
+        category: "스포츠",
+        labels: &[],
+        weight: 10,
+        ko_subjects: &["국가대표팀", "프로야구 구단", "K리그", "올림픽 선수단", "프로농구"],
+        ko_events: &["결승 진출 확정", "역전승 거둬", "신인 드래프트 마감", "시즌 개막 앞둬"],
+        en_subjects: &["The national team", "KBO clubs", "K League sides"],
+        en_events: &["reach the final", "complete a comeback win", "open the new season"],
+    },
+    Topic {
+        category: "연예",
+        labels: &[],
+        weight: 8,
+        ko_subjects: &["인기 아이돌 그룹", "드라마 제작진", "영화 배급사", "유명 배우"],
+        ko_events: &["신곡 발표", "시청률 최고치 경신", "해외 영화제 초청", "월드 투어 예고"],
+        en_subjects: &["A K-pop group", "Drama producers", "A film distributor"],
+        en_events: &["drop a new single", "hit record ratings", "announce a world tour"],
+    },
+    Topic {
+        category: "IT/과학",
+        labels: &["IT", "과학", "IT과학"],
+        weight: 14,
+        ko_subjects: &["국내 연구진", "반도체 업계", "과학기술정보통신부", "AI 스타트업", "통신사"],
+        ko_events: &["차세대 칩 개발", "AI 모델 공개", "6G 시범 서비스", "우주 발사체 시험 성공"],
+        en_subjects: &["Korean researchers", "Chipmakers", "An AI startup", "Carriers"],
+        en_events: &["unveil a next-gen chip", "release an AI model", "trial 6G service"],
+    },
+    Topic {
+        category: "생활/문화",
+        labels: &[],
+        weight: 9,
+        ko_subjects: &["국립중앙박물관", "여행업계", "출판계", "지역 축제 조직위", "유통업계"],
+        ko_events: &["특별전 개막", "연휴 예약 급증", "올해의 책 선정", "가을 축제 개최"],
+        en_subjects: &["The National Museum", "Travel agencies", "Local festivals"],
+        en_events: &["open a special exhibition", "expect a holiday booking surge"],
+    },
+];
+
+const KO_SENTENCES: &[&str] = &[
+    "{subject} 측은 이번 {event} 소식을 공식 발표했다.",
+    "업계에서는 {event}의 파장이 당분간 이어질 것으로 보고 있다.",
+    "{subject} 관계자는 \"구체적인 후속 조치를 조만간 내놓겠다\"고 밝혔다.",
+    "전문가들은 {event} 이후 시장과 여론의 반응에 주목하고 있다.",
+    "이번 결정은 지난달 논의된 방안을 일부 보완한 것이다.",
+    "일각에서는 추가 검토가 필요하다는 신중론도 제기됐다.",
+    "{subject}은(는) 다음 주 세부 내용을 공개할 예정이다.",
+    "관련 통계에 따르면 비슷한 사례는 최근 꾸준히 늘고 있다.",
+];
+
+const EN_SENTENCES: &[&str] = &[
+    "{subject} announced the move on Tuesday, officials said.",
+    "Analysts expect the decision to {event} to shape the coming months.",
+    "\"We will follow up with concrete measures soon,\" a spokesperson said.",
+    "The plan builds on proposals discussed last month.",
+    "Critics warned that further review would be needed.",
+    "Details are expected to be released next week.",
+    "Similar cases have risen steadily in recent years, data show.",
+];
+
+const KO_SURNAMES: &[&str] = &["김", "이", "박", "최", "정", "강", "조", "윤", "장", "임"];
+const KO_GIVEN: &[&str] = &["민준", "서연", "지훈", "하은", "도윤", "수아", "현우", "지민"];
+const EN_FIRST: &[&str] = &["James", "Min-ji", "Sarah", "Daniel", "Ji-won", "Emily", "Kevin"];
+const EN_LAST: &[&str] = &["Kim", "Lee", "Park", "Choi", "Smith", "Jung", "Yoon"];
+
+/// Share of articles a source files under its own default category
+const DEFAULT_CATEGORY_SHARE: f64 = 0.6;
+
+/// Share of articles a source labels with an alias instead of the canonical name
+const ALIAS_LABEL_SHARE: f64 = 0.3;
+
+/// Offset of Korea Standard Time from UTC, in hours
+const KST_OFFSET_HOURS: i64 = 9;
+
+/// Relative publishing volume per hour of the day in KST
+const HOUR_WEIGHTS: [u32; 24] = [
+    2, 1, 1, 1, 1, 2, 4, 7, 9, 10, 10, 9, 8, 9, 10, 10, 9, 8, 7, 6, 5, 4, 3, 2,
+];
+
+/// `news-api generate` - write synthetic articles and matching crawl stats
+pub async fn run(db: &Database, search: &SearchClient, args: &GenerateArgs) -> anyhow::Result<()> {
+    if args.days == 0 {
+        anyhow::bail!("--days must be at least 1");
+    }
+
+    let end_date = args.end_date.unwrap_or_else(|| Utc::now().date_naive());
+    let plan = Plan::new(args.seed, args.days, end_date);
+    let source_ids = register_sources(db).await?;
+
+    let mut output = match &args.output {
+        Some(path) => Some(BufWriter::new(File::create(path)?)),
+        None => {
+            let all_source_ids = db::source_ids(&db.pool).await?;
+            if search.create_index(search.index_name(), &all_source_ids).await? {
+                println!("Created index {}", search.index_name());
+            }
+            None
+        }
+    };
+
+    let batch_size = args.batch_size.clamp(1, 10_000) as u64;
+    let mut crawled_per_day: BTreeMap<NaiveDate, i64> = BTreeMap::new();
+    let mut written = 0u64;
+    let mut failed = 0usize;
+
+    let mut start = 0u64;
+    while start < args.articles {
+        let end = (start + batch_size).min(args.articles);
+        let articles: Vec<Article> = (start..end)
+            .map(|n| plan.article(n, &source_ids))
+            .collect();
+
+        for article in &articles {
+            if let Some(crawled_at) = article.crawled_at {
+                *crawled_per_day.entry(crawled_at.date_naive()).or_default() += 1;
+            }
+        }
+
+        match output.as_mut() {
+            Some(writer) => {
+                for article in &articles {
+                    serde_json::to_writer(&mut *writer, article)?;
+                    writer.write_all(b"\n")?;
+                }
+            }
+            None => {
+                let report = search.bulk_index(&articles).await?;
+                failed += report.failed;
+                if let Some(error) = report.first_error {
+                    tracing::warn!("{} articles failed to index: {error}", report.failed);
+                }
+            }
+        }
+
+        written += end - start;
+        start = end;
+        if written.is_multiple_of(100_000) || written == args.articles {
+            println!("Generated {written}/{} articles", args.articles);
+        }
+    }
+
+    if let Some(mut writer) = output {
+        writer.flush()?;
+    }
+
+    if !args.skip_stats {
+        write_crawl_stats(db, &plan, &crawled_per_day).await?;
+        println!("Wrote crawl_stats for {} day(s)", crawled_per_day.len());
+    }
+
+    if args.output.is_none() && !args.skip_categories {
+        let report = CategorySync::new().run(db, search).await?;
+        println!(
+            "Synced category counts: {} inserted, {} updated, {} zeroed",
+            report.inserted, report.updated, report.zeroed
+        );
+    }
+
+    if failed > 0 {
+        anyhow::bail!("{failed} of {written} articles failed to index");
+    }
+
+    Ok(())
+}
+
+/// Make sure every synthetic source is registered and return their IDs by name
+async fn register_sources(db: &Database) -> anyhow::Result<HashMap<String, i32>> {
+    for source in SOURCES {
+        sqlx::query(
+            r#"
+            INSERT INTO sources (name, homepage, language, default_category)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (name) DO NOTHING
+            "#,
+        )
+        .bind(source.name)
+        .bind(source.homepage)
+        .bind(source.language.code())
+        .bind(source.default_category)
+        .execute(&db.pool)
+        .await?;
+    }
+
+    let names: Vec<&str> = SOURCES.iter().map(|s| s.name).collect();
+    let ids: Vec<(String, i32)> =
+        sqlx::query_as("SELECT name, id FROM sources WHERE name = ANY($1)")
+            .bind(&names)
+            .fetch_all(&db.pool)
+            .await?;
+
+    Ok(ids.into_iter().collect())
+}
+
+/// Overwrite crawl_stats for the generated days
+///
+/// Every generated article counts as a successful crawl; failures are drawn
+/// per day so the success rate varies realistically.
+async fn write_crawl_stats(
+    db: &Database,
+    plan: &Plan,
+    crawled_per_day: &BTreeMap<NaiveDate, i64>,
+) -> anyhow::Result<()> {
+    let mut tx = db.pool.begin().await?;
+
+    for (date, success) in crawled_per_day {
+        let mut rng = plan.rng(STATS_STREAM + date.num_days_from_ce() as u64);
+        let failure_rate: f64 = rng.gen_range(0.005..0.06);
+        let failed = (*success as f64 * failure_rate).round() as i64;
+        let total = success + failed;
+
+        sqlx::query(
+            r#"
+            INSERT INTO crawl_stats (date, total_crawled, success_count, failed_count)
+            VALUES ($1, $2, $3, $4)
+            ON CONFLICT (date) DO UPDATE
+            SET total_crawled = EXCLUDED.total_crawled,
+                success_count = EXCLUDED.success_count,
+                failed_count = EXCLUDED.failed_count
+            "#,
+        )
+        .bind(date)
+        .bind(i32::try_from(total).unwrap_or(i32::MAX))
+        .bind(i32::try_from(*success).unwrap_or(i32::MAX))
+        .bind(i32::try_from(failed).unwrap_or(i32::MAX))
+        .execute(&mut *tx)
+        .await?;
+    }
+
+    tx.commit().await?;
+
+    Ok(())
+}
+
+/// Fixed distributions shared by every article of a run
+struct Plan {
+    seed: u64,
+    first_day: NaiveDate,
+    day_weights: WeightedIndex<u32>,
+    hour_weights: WeightedIndex<u32>,
+    source_weights: WeightedIndex<u32>,
+    topic_weights: WeightedIndex<u32>,
+}
+
+impl Plan {
+    fn new(seed: u64, days: u32, end_date: NaiveDate) -> Self {
+        let first_day = end_date - Duration::days(days as i64 - 1);
+
+        // Weekends are quieter than weekdays
+        let day_weights = (0..days).map(|offset| {
+            match (first_day + Duration::days(offset as i64)).weekday() {
+                Weekday::Sat | Weekday::Sun => 6,
+                _ => 10,
+            }
+        });
+
+        Self {
+            seed,
+            first_day,
+            day_weights: WeightedIndex::new(day_weights).expect("at least one day"),
+            hour_weights: WeightedIndex::new(HOUR_WEIGHTS).expect("positive hour weights"),
+            source_weights: WeightedIndex::new(SOURCES.iter().map(|s| s.weight))
+                .expect("positive source weights"),
+            topic_weights: WeightedIndex::new(TOPICS.iter().map(|t| t.weight))
+                .expect("positive topic weights"),
+        }
+    }
+
+    fn rng(&self, stream: u64) -> ChaCha8Rng {
+        let mut rng = ChaCha8Rng::seed_from_u64(self.seed);
+        rng.set_stream(stream);
+        rng
+    }
+
+    /// Article number `n` of this run
+    fn article(&self, n: u64, source_ids: &HashMap<String, i32>) -> Article {
+        let mut rng = self.rng(n);
+
+        let source = &SOURCES[self.source_weights.sample(&mut rng)];
+        let topic = if rng.gen_bool(DEFAULT_CATEGORY_SHARE) {
+            TOPICS
+                .iter()
+                .find(|t| t.category == source.default_category)
+                .unwrap_or(&TOPICS[0])
+        } else {
+            &TOPICS[self.topic_weights.sample(&mut rng)]
+        };
+        let label = match topic.labels.choose(&mut rng) {
+            Some(alias) if rng.gen_bool(ALIAS_LABEL_SHARE) => alias,
+            _ => topic.category,
+        };
+
+        // `day` and the hour are Korean local time; timestamps are stored in UTC
+        let day = self.first_day + Duration::days(self.day_weights.sample(&mut rng) as i64);
+        let kst_hour = self.hour_weights.sample(&mut rng) as i64;
+        let published_at = Utc.from_utc_datetime(&day.and_hms_opt(0, 0, 0).expect("midnight"))
+            + Duration::hours(kst_hour - KST_OFFSET_HOURS)
+            + Duration::seconds(rng.gen_range(0..3600));
+        let crawled_at = published_at + Duration::seconds(rng.gen_range(30..5400));
+
+        let text = match source.language {
+            Language::Korean => korean_text(&mut rng, topic),
+            Language::English => english_text(&mut rng, topic),
+        };
+
+        Article {
+            id: Some(format!("synthetic-{}-{n}", self.seed)),
+            title: text.title,
+            content: Some(text.content),
+            summary: Some(text.summary),
+            url: Some(format!("{}/article/{}-{n}", source.homepage, self.seed)),
+            source: Some(source.name.to_string()),
+            source_id: source_ids.get(source.name).copied(),
+            category: Some(label.to_string()),
+            author: Some(text.author),
+            image_url: rng
+                .gen_bool(0.7)
+                .then(|| format!("{}/images/{}-{n}.jpg", source.homepage, self.seed)),
+            published_at: Some(published_at),
+            crawled_at: Some(crawled_at),
+        }
+    }
+}
+
+struct Text {
+    title: String,
+    summary: String,
+    content: String,
+    author: String,
+}
+
+fn korean_text(rng: &mut ChaCha8Rng, topic: &Topic) -> Text {
+    let subject = pick(rng, topic.ko_subjects);
+    let event = pick(rng, topic.ko_events);
+
+    Text {
+        title: format!("{subject}, {event}"),
+        summary: format!("{subject} {event} 관련 소식"),
+        content: paragraph(rng, KO_SENTENCES, subject, event),
+        author: format!("{}{} 기자", pick(rng, KO_SURNAMES), pick(rng, KO_GIVEN)),
+    }
+}
+
+fn english_text(rng: &mut ChaCha8Rng, topic: &Topic) -> Text {
+    let subject = pick(rng, topic.en_subjects);
+    let event = pick(rng, topic.en_events);
+
+    Text {
+        title: format!("{subject} to {event}"),
+        summary: format!("Report: {subject} to {event}"),
+        content: paragraph(rng, EN_SENTENCES, subject, event),
+        author: format!("{} {}", pick(rng, EN_FIRST), pick(rng, EN_LAST)),
+    }
+}
+
+/// A shuffled selection of template sentences filled in with the story's subject and event
+fn paragraph(rng: &mut ChaCha8Rng, templates: &[&str], subject: &str, event: &str) -> String {
+    let mut sentences = templates.to_vec();
+    sentences.shuffle(rng);
+    sentences.truncate(rng.gen_range(3..=templates.len()));
+
+    sentences
+        .iter()
+        .map(|sentence| sentence.replace("{subject}", subject).replace("{event}", event))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn pick<'a>(rng: &mut ChaCha8Rng, items: &[&'a str]) -> &'a str {
+    items.choose(rng).copied().unwrap_or_default()
+}
@@ -0,0 +1,123 @@
+use anyhow::bail;
+use std::collections::HashMap;
+
+use crate::{
+    db::{self, Database},
+    search::{
+        index::{source_pipeline_id, EXPECTED_FIELD_TYPES},
+        SearchClient,
+    },
+};
+
+/// `news-api index create`
+pub async fn create(
+    db: &Database,
+    search: &SearchClient,
+    name: Option<&str>,
+) -> anyhow::Result<()> {
+    let index = name.unwrap_or(search.index_name());
+    let source_ids = db::source_ids(&db.pool).await?;
+
+    if search.create_index(index, &source_ids).await? {
+        println!("Created index {index}");
+    } else {
+        println!("Index {index} already exists");
+    }
+
+    Ok(())
+}
+
+/// `news-api index reindex`
+pub async fn reindex(
+    db: &Database,
+    search: &SearchClient,
+    source: Option<&str>,
+    dest: &str,
+) -> anyhow::Result<()> {
+    let source = source.unwrap_or(search.index_name());
+
+    if source == dest {
+        bail!("Source and destination index must differ");
+    }
+    if !search.index_exists(source).await? {
+        bail!("Source index {source} does not exist");
+    }
+    if search.create_index(dest, &db::source_ids(&db.pool).await?).await? {
+        println!("Created index {dest}");
+    }
+
+    let copied = search.reindex(source, dest).await?;
+    let source_count = search.count(source).await?;
+    println!("Reindexed {copied} documents from {source} into {dest}");
+
+    if copied != source_count {
+        bail!("{source} holds {source_count} documents but {copied} were copied");
+    }
+
+    Ok(())
+}
+
+/// `news-api index backfill-source-ids`
+///
+/// Brings the source pipeline up to date, makes it the index's default
+/// pipeline and runs it over articles indexed without a `source_id`.
+pub async fn backfill_source_ids(
+    db: &Database,
+    search: &SearchClient,
+    name: Option<&str>,
+) -> anyhow::Result<()> {
+    let index = name.unwrap_or(search.index_name());
+
+    if !search.index_exists(index).await? {
+        bail!("Index {index} does not exist (run `news-api index create`)");
+    }
+
+    let source_ids = db::source_ids(&db.pool).await?;
+    search.put_source_pipeline(index, &source_ids).await?;
+    search.set_default_pipeline(index).await?;
+    println!(
+        "Pipeline {} maps {} source(s) and is the default of {index}",
+        source_pipeline_id(index),
+        source_ids.len()
+    );
+
+    let updated = search.backfill_source_ids(index).await?;
+    println!("Set source_id on {updated} article(s)");
+
+    Ok(())
+}
+
+/// `news-api index verify`
+pub async fn verify(search: &SearchClient, name: Option<&str>) -> anyhow::Result<()> {
+    let index = name.unwrap_or(search.index_name());
+
+    if !search.index_exists(index).await? {
+        bail!("Index {index} does not exist (run `news-api index create`)");
+    }
+
+    let field_types: HashMap<String, String> =
+        search.get_field_types(index).await?.into_iter().collect();
+
+    let mut problems = Vec::new();
+    for (field, expected) in EXPECTED_FIELD_TYPES {
+        match field_types.get(*field) {
+            Some(actual) if actual == expected => {}
+            Some(actual) => problems.push(format!("{field}: expected {expected}, found {actual}")),
+            None => problems.push(format!("{field}: missing (expected {expected})")),
+        }
+    }
+
+    let count = search.count(index).await?;
+    println!("Index {index}: {count} documents");
+
+    if !problems.is_empty() {
+        for problem in &problems {
+            println!("  mapping mismatch - {problem}");
+        }
+        bail!("Index {index} mapping has {} problem(s)", problems.len());
+    }
+
+    println!("Mapping OK");
+
+    Ok(())
+}
@@ -0,0 +1,11 @@
+//! Operational subcommands of the `news-api` binary.
+//!
+//! They share `Database` and `SearchClient` with the server, so maintenance
+//! runs against exactly the same configuration and queries.
+
+pub mod export;
+pub mod generate;
+pub mod index;
+pub mod seed;
+pub mod stats;
+pub mod users;
@@ -0,0 +1,88 @@
+use serde_json::json;
+use sqlx::Executor;
+
+use crate::{
+    db::{self, Database},
+    models::Article,
+    search::SearchClient,
+};
+
+/// Sample categories, sources and crawl stats for development
+const SEED_SQL: &str = include_str!("../../test-data.sql");
+
+/// `news-api seed` - load development data into Postgres and OpenSearch
+///
+/// Safe to run repeatedly: rows are upserted and articles keep fixed IDs.
+pub async fn run(db: &Database, search: &SearchClient) -> anyhow::Result<()> {
+    // Multiple statements need the simple query protocol, i.e. no bind parameters
+    db.pool.execute(SEED_SQL).await?;
+    println!("Loaded sample categories, sources and crawl stats");
+
+    // Link articles to the seeded sources by name
+    let source_ids = db::source_ids(&db.pool).await?;
+
+    if search.create_index(search.index_name(), &source_ids).await? {
+        println!("Created index {}", search.index_name());
+    }
+
+    let articles: Vec<Article> = sample_articles()
+        .into_iter()
+        .map(|mut article| {
+            article.source_id = article
+                .source
+                .as_ref()
+                .and_then(|name| source_ids.get(name).copied());
+            article
+        })
+        .collect();
+
+    let report = search.bulk_index(&articles).await?;
+    println!("Indexed {} sample articles", report.indexed);
+    if let Some(error) = report.first_error {
+        anyhow::bail!("{} sample articles failed to index: {error}", report.failed);
+    }
+
+    Ok(())
+}
+
+fn sample_articles() -> Vec<Article> {
+    serde_json::from_value(json!([
+        {
+            "id": "sample-1",
+            "title": "정부, 새로운 경제 정책 발표",
+            "content": "정부가 오늘 새로운 경제 활성화 정책을 발표했습니다. 주요 내용으로는...",
+            "summary": "정부의 새로운 경제 정책 발표",
+            "category": "정치",
+            "source": "뉴스1",
+            "url": "https://example.com/article/1",
+            "author": "김기자",
+            "published_at": "2026-01-15T09:00:00Z",
+            "crawled_at": "2026-01-15T09:05:00Z"
+        },
+        {
+            "id": "sample-2",
+            "title": "주식시장 상승세 지속",
+            "content": "코스피가 3거래일 연속 상승세를 이어가고 있습니다...",
+            "summary": "주식시장 연일 상승",
+            "category": "경제",
+            "source": "이코노미",
+            "url": "https://example.com/article/2",
+            "author": "이기자",
+            "published_at": "2026-01-15T08:30:00Z",
+            "crawled_at": "2026-01-15T08:35:00Z"
+        },
+        {
+            "id": "sample-3",
+            "title": "AI 기술 발전으로 산업 혁신 가속화",
+            "content": "인공지능 기술의 급격한 발전으로 다양한 산업 분야에서 혁신이 일어나고 있습니다...",
+            "summary": "AI 기술이 산업 혁신 주도",
+            "category": "IT/과학",
+            "source": "테크뉴스",
+            "url": "https://example.com/article/3",
+            "author": "박기자",
+            "published_at": "2026-01-15T10:00:00Z",
+            "crawled_at": "2026-01-15T10:05:00Z"
+        }
+    ]))
+    .expect("sample articles match the Article model")
+}
@@ -0,0 +1,50 @@
+use chrono::NaiveDate;
+
+use crate::{category_sync::CategorySync, db, db::Database, search::SearchClient};
+
+/// `news-api stats recompute`
+pub async fn recompute(
+    db: &Database,
+    search: &SearchClient,
+    from: Option<NaiveDate>,
+    to: Option<NaiveDate>,
+    skip_categories: bool,
+) -> anyhow::Result<()> {
+    // Only days that have runs are rebuilt, so older hand-written rows survive
+    let dates: Vec<NaiveDate> = sqlx::query_scalar(
+        r#"
+        SELECT DISTINCT (started_at AT TIME ZONE 'UTC')::DATE AS day
+        FROM crawl_runs
+        WHERE ($1::DATE IS NULL OR started_at >= $1::TIMESTAMP AT TIME ZONE 'UTC')
+          AND ($2::DATE IS NULL OR started_at < ($2 + 1)::TIMESTAMP AT TIME ZONE 'UTC')
+        ORDER BY day
+        "#,
+    )
+    .bind(from)
+    .bind(to)
+    .fetch_all(&db.pool)
+    .await?;
+
+    let mut tx = db.pool.begin().await?;
+    for date in &dates {
+        db::derive_crawl_stats(&mut tx, *date).await?;
+    }
+    tx.commit().await?;
+
+    match (dates.first(), dates.last()) {
+        (Some(first), Some(last)) => {
+            println!("Rebuilt crawl_stats for {} day(s), {first} to {last}", dates.len())
+        }
+        _ => println!("No crawl runs in range, crawl_stats unchanged"),
+    }
+
+    if !skip_categories {
+        let report = CategorySync::new().run(db, search).await?;
+        println!(
+            "Synced category counts: {} inserted, {} updated, {} zeroed",
+            report.inserted, report.updated, report.zeroed
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,130 @@
+use anyhow::{bail, Context};
+use std::io::{BufRead, IsTerminal, Write};
+
+use crate::{
+    auth::{self, MIN_PASSWORD_LENGTH, USER_COLUMNS},
+    db::Database,
+    models::{User, UserRole},
+};
+
+/// `news-api users add`
+pub async fn add(db: &Database, username: &str, role: UserRole) -> anyhow::Result<()> {
+    if username.is_empty() || username.len() > 100 {
+        bail!("Username must be 1 to 100 characters");
+    }
+    let hash = auth::hash_password(&read_password()?).await?;
+
+    let result =
+        sqlx::query("INSERT INTO users (username, password_hash, role) VALUES ($1, $2, $3)")
+            .bind(username)
+            .bind(&hash)
+            .bind(role.as_str())
+            .execute(&db.pool)
+            .await;
+
+    match result {
+        Ok(_) => println!("Created {} {username}", role.as_str()),
+        Err(sqlx::Error::Database(e)) if e.is_unique_violation() => {
+            bail!("User {username} already exists")
+        }
+        Err(e) => return Err(e.into()),
+    }
+
+    Ok(())
+}
+
+/// `news-api users passwd`
+pub async fn passwd(db: &Database, username: &str) -> anyhow::Result<()> {
+    let hash = auth::hash_password(&read_password()?).await?;
+
+    let user_id: Option<i32> = sqlx::query_scalar(
+        r#"
+        UPDATE users SET password_hash = $2, updated_at = NOW()
+        WHERE username = $1
+        RETURNING id
+        "#,
+    )
+    .bind(username)
+    .bind(&hash)
+    .fetch_optional(&db.pool)
+    .await?;
+
+    let Some(user_id) = user_id else {
+        bail!("User {username} does not exist");
+    };
+    let revoked = auth::revoke_all(db, user_id).await?;
+    println!("Changed password of {username}, ended {revoked} session(s)");
+
+    Ok(())
+}
+
+/// `news-api users disable` and `news-api users enable`
+pub async fn set_enabled(db: &Database, username: &str, enabled: bool) -> anyhow::Result<()> {
+    let user_id: Option<i32> = sqlx::query_scalar(
+        "UPDATE users SET enabled = $2, updated_at = NOW() WHERE username = $1 RETURNING id",
+    )
+    .bind(username)
+    .bind(enabled)
+    .fetch_optional(&db.pool)
+    .await?;
+
+    let Some(user_id) = user_id else {
+        bail!("User {username} does not exist");
+    };
+    if enabled {
+        println!("Enabled {username}");
+    } else {
+        let revoked = auth::revoke_all(db, user_id).await?;
+        println!("Disabled {username}, ended {revoked} session(s)");
+    }
+
+    Ok(())
+}
+
+/// `news-api users list`
+pub async fn list(db: &Database) -> anyhow::Result<()> {
+    let users: Vec<User> =
+        sqlx::query_as(&format!("SELECT {USER_COLUMNS} FROM users ORDER BY username"))
+            .fetch_all(&db.pool)
+            .await?;
+
+    for user in &users {
+        let last_login = user
+            .last_login_at
+            .map_or_else(|| "never".to_string(), |t| t.to_rfc3339());
+        println!(
+            "{:<24} {:<7} {:<9} last login {last_login}",
+            user.username,
+            user.role.as_str(),
+            if user.enabled { "enabled" } else { "disabled" },
+        );
+    }
+    if users.is_empty() {
+        println!("No users; create one with `news-api users add <name> --role admin`");
+    }
+
+    Ok(())
+}
+
+/// Password from NEWS_API_PASSWORD, or the first line of stdin
+fn read_password() -> anyhow::Result<String> {
+    let password = match std::env::var("NEWS_API_PASSWORD") {
+        Ok(password) => password,
+        Err(_) => {
+            let stdin = std::io::stdin();
+            if stdin.is_terminal() {
+                eprint!("Password: ");
+                std::io::stderr().flush()?;
+            }
+            let mut line = String::new();
+            stdin.lock().read_line(&mut line).context("Failed to read password")?;
+            line.trim_end_matches(['\r', '\n']).to_string()
+        }
+    };
+
+    if password.chars().count() < MIN_PASSWORD_LENGTH {
+        bail!("Password must be at least {MIN_PASSWORD_LENGTH} characters");
+    }
+
+    Ok(password)
+}
@@ -1,6 +1,10 @@
+use chrono::{Datelike, NaiveDate, NaiveTime};
+use std::collections::HashMap;
+use sqlx::migrate::{MigrateError, Migrator};
 use sqlx::postgres::PgPoolOptions;
-use sqlx::{Pool, Postgres};
-use std::time::Duration;
+use sqlx::{PgConnection, Pool, Postgres};
+
+use crate::config::DatabaseConfig;
 
 pub type DbPool = Pool<Postgres>;
 
@@ -10,13 +14,14 @@ pub struct Database {
 }
 
 impl Database {
-    pub async fn new(database_url: &str) -> Result<Self, sqlx::Error> {
+    pub async fn new(config: &DatabaseConfig) -> Result<Self, sqlx::Error> {
         tracing::info!("Connecting to PostgreSQL database...");
 
         let pool = PgPoolOptions::new()
-            .max_connections(10)
-            .acquire_timeout(Duration::from_secs(5))
-            .connect(database_url)
+            .max_connections(config.max_connections)
+            .min_connections(config.min_connections)
+            .acquire_timeout(config.acquire_timeout())
+            .connect(&config.url)
             .await?;
 
         tracing::info!("Successfully connected to PostgreSQL database");
@@ -29,38 +34,72 @@ impl Database {
     }
 }
 
-// Initialize database schema
-pub async fn init_schema(pool: &DbPool) -> Result<(), sqlx::Error> {
-    tracing::info!("Initializing database schema...");
+/// Versioned schema migrations embedded from `migrations/`
+pub static MIGRATOR: Migrator = sqlx::migrate!();
 
-    sqlx::query(
-        r#"
-        CREATE TABLE IF NOT EXISTS crawl_stats (
-            id SERIAL PRIMARY KEY,
-            date DATE NOT NULL,
-            total_crawled INT DEFAULT 0,
-            success_count INT DEFAULT 0,
-            failed_count INT DEFAULT 0,
-            created_at TIMESTAMP DEFAULT NOW()
-        )
-        "#,
-    )
-    .execute(pool)
-    .await?;
+// Apply pending migrations
+//
+// sqlx holds a Postgres advisory lock for the duration, so replicas starting at
+// the same time apply each migration exactly once.
+pub async fn run_migrations(pool: &DbPool) -> Result<(), MigrateError> {
+    tracing::info!("Applying database migrations...");
+
+    MIGRATOR.run(pool).await?;
 
+    tracing::info!("Database migrations applied successfully");
+
+    Ok(())
+}
+
+/// Advisory lock namespace of per-day crawl_stats rebuilds
+const CRAWL_STATS_LOCK: i32 = 0x6372_7374;
+
+/// Rebuild the crawl_stats row of one (UTC) day from its crawl_runs
+///
+/// Successful articles are new plus updated ones; the day total also counts failures.
+pub async fn derive_crawl_stats(
+    conn: &mut PgConnection,
+    date: NaiveDate,
+) -> Result<(), sqlx::Error> {
+    // Concurrent rebuilds of one day would each miss the other's run
+    let day = date.num_days_from_ce();
+    sqlx::query("SELECT pg_advisory_xact_lock($1, $2)")
+        .bind(CRAWL_STATS_LOCK)
+        .bind(day)
+        .execute(&mut *conn)
+        .await?;
+
+    // A range on started_at rather than a cast, so crawl_runs_started_idx is used
+    let start = date.and_time(NaiveTime::MIN).and_utc();
     sqlx::query(
         r#"
-        CREATE TABLE IF NOT EXISTS categories (
-            id SERIAL PRIMARY KEY,
-            name VARCHAR(100) NOT NULL UNIQUE,
-            article_count INT DEFAULT 0
-        )
+        INSERT INTO crawl_stats (date, total_crawled, success_count, failed_count)
+        SELECT $1,
+               COALESCE(SUM(articles_new + articles_updated + articles_failed), 0),
+               COALESCE(SUM(articles_new + articles_updated), 0),
+               COALESCE(SUM(articles_failed), 0)
+        FROM crawl_runs
+        WHERE started_at >= $2 AND started_at < $3
+        ON CONFLICT (date) DO UPDATE
+        SET total_crawled = EXCLUDED.total_crawled,
+            success_count = EXCLUDED.success_count,
+            failed_count = EXCLUDED.failed_count
         "#,
     )
-    .execute(pool)
+    .bind(date)
+    .bind(start)
+    .bind(start + chrono::Duration::days(1))
+    .execute(&mut *conn)
     .await?;
 
-    tracing::info!("Database schema initialized successfully");
-
     Ok(())
 }
+
+/// Registered source IDs by name, for the source ingest pipeline
+pub async fn source_ids(pool: &DbPool) -> Result<HashMap<String, i32>, sqlx::Error> {
+    let rows: Vec<(String, i32)> = sqlx::query_as("SELECT name, id FROM sources")
+        .fetch_all(pool)
+        .await?;
+
+    Ok(rows.into_iter().collect())
+}
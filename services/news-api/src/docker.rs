@@ -0,0 +1,301 @@
+//! Minimal Docker Engine API client over the daemon's Unix socket.
+//!
+//! Only the container lifecycle calls the admin API needs are implemented.
+//! Each request opens its own connection, which keeps the client stateless and
+//! is cheap on a local socket.
+
+use http_body_util::{BodyExt, Empty};
+use hyper::body::Bytes;
+use hyper::{Method, Request, StatusCode};
+use hyper_util::rt::TokioIo;
+use serde::Deserialize;
+use std::path::PathBuf;
+use std::time::Duration;
+use tokio::net::UnixStream;
+
+use crate::config::DockerConfig;
+use crate::error::{ApiError, ApiResult};
+
+/// Engine API version requested; supported by Docker 20.10 and later
+const API_VERSION: &str = "v1.41";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContainerAction {
+    Start,
+    Stop,
+    Restart,
+}
+
+impl ContainerAction {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Start => "start",
+            Self::Stop => "stop",
+            Self::Restart => "restart",
+        }
+    }
+}
+
+/// What the daemon did with a lifecycle request
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ActionOutcome {
+    Done,
+    /// The container was already in the requested state
+    Unchanged,
+}
+
+#[derive(Clone)]
+pub struct DockerClient {
+    socket_path: PathBuf,
+    timeout: Duration,
+    stop_timeout_secs: u64,
+}
+
+#[derive(Deserialize)]
+struct ErrorBody {
+    message: String,
+}
+
+impl DockerClient {
+    pub fn new(config: &DockerConfig) -> Self {
+        Self {
+            socket_path: config.socket_path.clone(),
+            timeout: config.timeout(),
+            stop_timeout_secs: config.stop_timeout_secs,
+        }
+    }
+
+    /// Start, stop or restart a container by name or ID
+    pub async fn container_action(
+        &self,
+        container: &str,
+        action: ContainerAction,
+    ) -> ApiResult<ActionOutcome> {
+        let path = match action {
+            ContainerAction::Start => format!("/containers/{container}/start"),
+            ContainerAction::Stop | ContainerAction::Restart => format!(
+                "/containers/{container}/{}?t={}",
+                action.as_str(),
+                self.stop_timeout_secs
+            ),
+        };
+
+        // Stopping waits for the container to exit, so allow for the grace period
+        let timeout = self.timeout + Duration::from_secs(self.stop_timeout_secs);
+        let (status, body) = self.request(Method::POST, &path, timeout).await?;
+
+        match status {
+            StatusCode::NO_CONTENT | StatusCode::OK => Ok(ActionOutcome::Done),
+            StatusCode::NOT_MODIFIED => Ok(ActionOutcome::Unchanged),
+            StatusCode::NOT_FOUND => Err(ApiError::NotFound(format!(
+                "Container {container} not found"
+            ))),
+            status => Err(ApiError::Docker(format!(
+                "{} {container} failed with status {status}: {}",
+                action.as_str(),
+                error_message(&body)
+            ))),
+        }
+    }
+
+    async fn request(
+        &self,
+        method: Method,
+        path: &str,
+        timeout: Duration,
+    ) -> ApiResult<(StatusCode, Bytes)> {
+        tokio::time::timeout(timeout, self.send(method, path))
+            .await
+            .map_err(|_| {
+                ApiError::Docker(format!("No response from Docker within {}s", timeout.as_secs()))
+            })?
+    }
+
+    async fn send(&self, method: Method, path: &str) -> ApiResult<(StatusCode, Bytes)> {
+        let stream = UnixStream::connect(&self.socket_path).await.map_err(|e| {
+            ApiError::Docker(format!(
+                "Failed to connect to {}: {e}",
+                self.socket_path.display()
+            ))
+        })?;
+
+        let (mut sender, connection) = hyper::client::conn::http1::handshake(TokioIo::new(stream))
+            .await
+            .map_err(|e| ApiError::Docker(format!("Docker handshake failed: {e}")))?;
+        tokio::spawn(async move {
+            if let Err(e) = connection.await {
+                tracing::debug!("Docker connection closed: {e}");
+            }
+        });
+
+        let request = Request::builder()
+            .method(method)
+            .uri(format!("/{API_VERSION}{path}"))
+            .header("Host", "docker")
+            .body(Empty::<Bytes>::new())
+            .map_err(|e| ApiError::Docker(format!("Invalid Docker request: {e}")))?;
+
+        let response = sender
+            .send_request(request)
+            .await
+            .map_err(|e| ApiError::Docker(format!("Docker request failed: {e}")))?;
+
+        let status = response.status();
+        let body = response
+            .into_body()
+            .collect()
+            .await
+            .map_err(|e| ApiError::Docker(format!("Failed to read Docker response: {e}")))?
+            .to_bytes();
+
+        Ok((status, body))
+    }
+}
+
+fn error_message(body: &[u8]) -> String {
+    serde_json::from_slice::<ErrorBody>(body)
+        .map(|e| e.message)
+        .unwrap_or_else(|_| String::from_utf8_lossy(body).trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+    use tokio::io::{AsyncReadExt, AsyncWriteExt};
+    use tokio::net::UnixListener;
+    use tokio::task::JoinHandle;
+
+    static SOCKETS: AtomicUsize = AtomicUsize::new(0);
+
+    /// Fake daemon answering one request with `status` and `body`, returning
+    /// the request line it received
+    fn fake_daemon(status: &str, body: &str) -> (DockerClient, JoinHandle<String>) {
+        let socket_path = std::env::temp_dir().join(format!(
+            "news-api-docker-{}-{}.sock",
+            std::process::id(),
+            SOCKETS.fetch_add(1, Ordering::Relaxed)
+        ));
+        let _ = std::fs::remove_file(&socket_path);
+        let listener = UnixListener::bind(&socket_path).expect("bind fake Docker socket");
+
+        let response = format!(
+            "HTTP/1.1 {status}\r\nContent-Type: application/json\r\n\
+             Content-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        );
+        let path = socket_path.clone();
+        let server = tokio::spawn(async move {
+            let (mut stream, _) = listener.accept().await.expect("accept");
+            let mut request = Vec::new();
+            let mut buf = [0; 1024];
+            while !request.windows(4).any(|w| w == b"\r\n\r\n") {
+                let n = stream.read(&mut buf).await.expect("read request");
+                assert!(n > 0, "connection closed mid-request");
+                request.extend_from_slice(&buf[..n]);
+            }
+            stream.write_all(response.as_bytes()).await.expect("write response");
+            stream.shutdown().await.ok();
+            let _ = std::fs::remove_file(path);
+
+            let request = String::from_utf8_lossy(&request).into_owned();
+            request.lines().next().unwrap_or_default().to_string()
+        });
+
+        let client = DockerClient::new(&DockerConfig {
+            socket_path,
+            timeout_secs: 5,
+            stop_timeout_secs: 3,
+            ..DockerConfig::default()
+        });
+        (client, server)
+    }
+
+    #[tokio::test]
+    async fn start_without_content_is_done() {
+        let (client, server) = fake_daemon("204 No Content", "");
+
+        let outcome = client.container_action("crawler", ContainerAction::Start).await;
+
+        assert_eq!(outcome.unwrap(), ActionOutcome::Done);
+        assert_eq!(
+            server.await.unwrap(),
+            "POST /v1.41/containers/crawler/start HTTP/1.1"
+        );
+    }
+
+    #[tokio::test]
+    async fn stop_passes_the_grace_period() {
+        let (client, server) = fake_daemon("204 No Content", "");
+
+        let outcome = client.container_action("crawler", ContainerAction::Stop).await;
+
+        assert_eq!(outcome.unwrap(), ActionOutcome::Done);
+        assert_eq!(
+            server.await.unwrap(),
+            "POST /v1.41/containers/crawler/stop?t=3 HTTP/1.1"
+        );
+    }
+
+    #[tokio::test]
+    async fn not_modified_is_unchanged() {
+        let (client, server) = fake_daemon("304 Not Modified", "");
+
+        let outcome = client.container_action("crawler", ContainerAction::Start).await;
+        server.await.unwrap();
+
+        assert_eq!(outcome.unwrap(), ActionOutcome::Unchanged);
+    }
+
+    #[tokio::test]
+    async fn missing_container_is_not_found() {
+        let body = r#"{"message":"No such container: ghost"}"#;
+        let (client, server) = fake_daemon("404 Not Found", body);
+
+        let outcome = client.container_action("ghost", ContainerAction::Restart).await;
+        server.await.unwrap();
+
+        match outcome {
+            Err(ApiError::NotFound(message)) => assert_eq!(message, "Container ghost not found"),
+            other => panic!("expected NotFound, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn other_status_carries_the_daemon_message() {
+        let body = r#"{"message":"cannot restart: device busy"}"#;
+        let (client, server) = fake_daemon("500 Internal Server Error", body);
+
+        let outcome = client.container_action("crawler", ContainerAction::Restart).await;
+        server.await.unwrap();
+
+        match outcome {
+            Err(ApiError::Docker(message)) => assert_eq!(
+                message,
+                "restart crawler failed with status 500 Internal Server Error: \
+                 cannot restart: device busy"
+            ),
+            other => panic!("expected Docker error, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn missing_socket_is_a_docker_error() {
+        let client = DockerClient::new(&DockerConfig {
+            socket_path: PathBuf::from("/nonexistent/docker.sock"),
+            ..DockerConfig::default()
+        });
+
+        let outcome = client.container_action("crawler", ContainerAction::Start).await;
+
+        assert!(matches!(outcome, Err(ApiError::Docker(m)) if m.contains("Failed to connect")));
+    }
+
+    #[test]
+    fn error_message_prefers_the_json_message() {
+        assert_eq!(error_message(br#"{"message":"conflict"}"#), "conflict");
+        assert_eq!(error_message(b"  plain text failure\n"), "plain text failure");
+        assert_eq!(error_message(br#"{"error":"other shape"}"#), r#"{"error":"other shape"}"#);
+        assert_eq!(error_message(b""), "");
+    }
+}
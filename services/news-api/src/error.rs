@@ -1,9 +1,10 @@
 use axum::{
-    http::StatusCode,
+    http::{header, HeaderValue, StatusCode},
     response::{IntoResponse, Response},
     Json,
 };
 use serde_json::json;
+use std::time::Duration;
 use thiserror::Error;
 
 #[derive(Error, Debug)]
@@ -20,9 +21,33 @@ pub enum ApiError {
     #[error("Bad request: {0}")]
     BadRequest(String),
 
+    #[error("Validation error: {0}")]
+    Validation(#[from] validator::ValidationErrors),
+
+    #[error("Conflict: {0}")]
+    Conflict(String),
+
+    #[error("Unauthorized: {0}")]
+    Unauthorized(String),
+
+    #[error("Forbidden: {0}")]
+    Forbidden(String),
+
+    #[error("Rate limit exceeded, retry in {retry_after:?}")]
+    TooManyRequests { retry_after: Duration },
+
     #[error("Internal server error: {0}")]
     InternalServerError(String),
 
+    #[error("Docker error: {0}")]
+    Docker(String),
+
+    #[error("Prometheus error: {0}")]
+    Prometheus(String),
+
+    #[error("Grafana error: {0}")]
+    Grafana(String),
+
     #[error("HTTP client error: {0}")]
     HttpClient(#[from] reqwest::Error),
 
@@ -32,6 +57,11 @@ pub enum ApiError {
 
 impl IntoResponse for ApiError {
     fn into_response(self) -> Response {
+        let challenge = matches!(self, Self::Unauthorized(_));
+        let retry_after = match self {
+            Self::TooManyRequests { retry_after } => Some(retry_after),
+            _ => None,
+        };
         let (status, error_message) = match self {
             Self::Database(ref e) => {
                 tracing::error!("Database error: {e:?}");
@@ -46,10 +76,30 @@ impl IntoResponse for ApiError {
             }
             Self::NotFound(ref msg) => (StatusCode::NOT_FOUND, msg.clone()),
             Self::BadRequest(ref msg) => (StatusCode::BAD_REQUEST, msg.clone()),
+            Self::Validation(ref e) => (StatusCode::BAD_REQUEST, e.to_string()),
+            Self::Conflict(ref msg) => (StatusCode::CONFLICT, msg.clone()),
+            Self::Unauthorized(ref msg) => (StatusCode::UNAUTHORIZED, msg.clone()),
+            Self::Forbidden(ref msg) => (StatusCode::FORBIDDEN, msg.clone()),
+            Self::TooManyRequests { .. } => (
+                StatusCode::TOO_MANY_REQUESTS,
+                "Rate limit exceeded".to_string(),
+            ),
             Self::InternalServerError(ref msg) => {
                 tracing::error!("Internal server error: {msg}");
                 (StatusCode::INTERNAL_SERVER_ERROR, msg.clone())
             }
+            Self::Docker(ref msg) => {
+                tracing::error!("Docker error: {msg}");
+                (StatusCode::BAD_GATEWAY, msg.clone())
+            }
+            Self::Prometheus(ref msg) => {
+                tracing::error!("Prometheus error: {msg}");
+                (StatusCode::BAD_GATEWAY, msg.clone())
+            }
+            Self::Grafana(ref msg) => {
+                tracing::error!("Grafana error: {msg}");
+                (StatusCode::BAD_GATEWAY, msg.clone())
+            }
             Self::HttpClient(ref e) => {
                 tracing::error!("HTTP client error: {e:?}");
                 (
@@ -71,7 +121,20 @@ impl IntoResponse for ApiError {
             "status": status.as_u16(),
         }));
 
-        (status, body).into_response()
+        let mut response = (status, body).into_response();
+        if challenge {
+            response
+                .headers_mut()
+                .insert(header::WWW_AUTHENTICATE, HeaderValue::from_static("Bearer"));
+        }
+        if let Some(retry_after) = retry_after {
+            // Whole seconds, rounded up so a client waiting that long succeeds
+            let secs = retry_after.as_secs() + u64::from(retry_after.subsec_nanos() > 0);
+            response
+                .headers_mut()
+                .insert(header::RETRY_AFTER, HeaderValue::from(secs.max(1)));
+        }
+        response
     }
 }
 
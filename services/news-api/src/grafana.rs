@@ -0,0 +1,203 @@
+//! Grafana dashboard catalog for the admin Grafana page.
+//!
+//! Dashboards are listed through Grafana's search API, authenticated with a
+//! service-account token, and cached for `grafana.cache_ttl_secs`. When
+//! Grafana cannot be reached the last catalog is served and marked stale; a
+//! failed refresh is not retried before the TTL passes again, so an outage
+//! does not make every request wait for the timeout.
+
+use chrono::{DateTime, Utc};
+use reqwest::Client;
+use serde::Deserialize;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::Mutex;
+
+use crate::config::GrafanaConfig;
+use crate::error::{ApiError, ApiResult};
+
+/// Dashboard as listed by Grafana, before a time range is applied
+#[derive(Debug, Clone)]
+pub struct DashboardEntry {
+    pub uid: String,
+    pub title: String,
+    /// URL slug of the title, e.g. `api-metrics`
+    pub slug: String,
+    pub tags: Vec<String>,
+    pub folder: Option<String>,
+}
+
+/// Cached catalog and whether it reflects Grafana's current state
+#[derive(Debug, Clone)]
+pub struct Catalog {
+    pub dashboards: Vec<DashboardEntry>,
+    /// When the dashboards were fetched; `None` if Grafana never answered
+    pub fetched_at: Option<DateTime<Utc>>,
+    /// Why the last refresh failed, if it did
+    pub error: Option<String>,
+}
+
+#[derive(Clone)]
+pub struct GrafanaClient {
+    client: Client,
+    base_url: String,
+    token: Option<String>,
+    cache_ttl: Duration,
+    cache: Arc<Mutex<CacheState>>,
+}
+
+#[derive(Default)]
+struct CacheState {
+    catalog: Option<Catalog>,
+    /// Last refresh attempt, successful or not
+    checked: Option<Instant>,
+}
+
+#[derive(Deserialize)]
+struct SearchHit {
+    uid: String,
+    title: String,
+    #[serde(default)]
+    url: String,
+    #[serde(default)]
+    tags: Vec<String>,
+    #[serde(rename = "folderTitle")]
+    folder_title: Option<String>,
+}
+
+impl GrafanaClient {
+    pub fn new(config: &GrafanaConfig) -> Self {
+        let client = Client::builder()
+            .timeout(config.timeout())
+            .build()
+            .unwrap_or_default();
+
+        Self {
+            client,
+            base_url: config.url.trim_end_matches('/').to_string(),
+            token: config.token.clone().filter(|t| !t.is_empty()),
+            cache_ttl: Duration::from_secs(config.cache_ttl_secs),
+            cache: Arc::default(),
+        }
+    }
+
+    /// Cached catalog, refreshed first if the TTL has passed
+    ///
+    /// Concurrent callers wait for a single refresh instead of each querying
+    /// Grafana.
+    pub async fn catalog(&self, force_refresh: bool) -> Catalog {
+        let mut cache = self.cache.lock().await;
+
+        let expired = cache.checked.is_none_or(|t| t.elapsed() >= self.cache_ttl);
+        if force_refresh || expired {
+            let result = self.search().await;
+            cache.checked = Some(Instant::now());
+            let previous = cache.catalog.take();
+            cache.catalog = Some(match result {
+                Ok(dashboards) => Catalog {
+                    dashboards,
+                    fetched_at: Some(Utc::now()),
+                    error: None,
+                },
+                Err(e) => {
+                    tracing::warn!("Failed to refresh Grafana dashboards: {e}");
+                    let (dashboards, fetched_at) = previous
+                        .map(|c| (c.dashboards, c.fetched_at))
+                        .unwrap_or_default();
+                    Catalog {
+                        dashboards,
+                        fetched_at,
+                        error: Some(match e {
+                            ApiError::Grafana(message) => message,
+                            other => other.to_string(),
+                        }),
+                    }
+                }
+            });
+        }
+
+        cache.catalog.clone().unwrap_or(Catalog {
+            dashboards: Vec::new(),
+            fetched_at: None,
+            error: None,
+        })
+    }
+
+    async fn search(&self) -> ApiResult<Vec<DashboardEntry>> {
+        let url = format!("{}/api/search", self.base_url);
+
+        let mut request = self
+            .client
+            .get(&url)
+            .query(&[("type", "dash-db"), ("limit", "5000")]);
+        if let Some(token) = &self.token {
+            request = request.bearer_auth(token);
+        }
+
+        let response = request
+            .send()
+            .await
+            .map_err(|e| ApiError::Grafana(format!("Failed to reach Grafana: {e}")))?;
+
+        let status = response.status();
+        if !status.is_success() {
+            let hint = match status.as_u16() {
+                401 | 403 => " (check grafana.token)",
+                _ => "",
+            };
+            return Err(ApiError::Grafana(format!(
+                "Grafana search failed with status {status}{hint}"
+            )));
+        }
+
+        let hits: Vec<SearchHit> = response
+            .json()
+            .await
+            .map_err(|e| ApiError::Grafana(format!("Failed to parse Grafana search: {e}")))?;
+
+        Ok(hits
+            .into_iter()
+            .map(|hit| DashboardEntry {
+                // Grafana's URL ends in the slug, e.g. /grafana/d/<uid>/<slug>
+                slug: hit
+                    .url
+                    .split_once(&format!("/d/{}/", hit.uid))
+                    .map(|(_, slug)| slug)
+                    .filter(|slug| !slug.is_empty())
+                    .unwrap_or("dashboard")
+                    .to_string(),
+                uid: hit.uid,
+                title: hit.title,
+                tags: hit.tags,
+                folder: hit.folder_title,
+            })
+            .collect())
+    }
+}
+
+/// Whether `value` is a Grafana time: `now`, relative like `now-6h` or
+/// `now-1d/d`, or epoch milliseconds
+pub fn is_time_expression(value: &str) -> bool {
+    if !value.is_empty() && value.bytes().all(|b| b.is_ascii_digit()) {
+        return true;
+    }
+
+    let Some(mut rest) = value.strip_prefix("now") else {
+        return false;
+    };
+    let is_unit = |c: char| matches!(c, 's' | 'm' | 'h' | 'd' | 'w' | 'M' | 'y');
+
+    if let Some(offset) = rest.strip_prefix(['-', '+']) {
+        let digits = offset.bytes().take_while(u8::is_ascii_digit).count();
+        let mut chars = offset[digits..].chars();
+        if digits == 0 || !chars.next().is_some_and(is_unit) {
+            return false;
+        }
+        rest = chars.as_str();
+    }
+    if let Some(round) = rest.strip_prefix('/') {
+        let mut chars = round.chars();
+        return chars.next().is_some_and(is_unit) && chars.as_str().is_empty();
+    }
+    rest.is_empty()
+}
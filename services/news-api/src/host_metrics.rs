@@ -0,0 +1,248 @@
+//! Host resource metrics read straight from the kernel.
+//!
+//! CPU and memory come from `/proc/stat` and `/proc/meminfo`, filesystems from
+//! `/proc/mounts` plus `statvfs(3)`. Inside a container these describe the
+//! container's view of the host, which is what the admin dashboard shows.
+
+use std::collections::HashMap;
+use std::ffi::CString;
+use std::io;
+use std::time::Duration;
+
+/// Filesystem types that do not hold data worth reporting
+const PSEUDO_FILESYSTEMS: &[&str] = &[
+    "autofs",
+    "binfmt_misc",
+    "bpf",
+    "cgroup",
+    "cgroup2",
+    "configfs",
+    "debugfs",
+    "devpts",
+    "devtmpfs",
+    "fusectl",
+    "hugetlbfs",
+    "mqueue",
+    "nsfs",
+    "proc",
+    "pstore",
+    "rpc_pipefs",
+    "securityfs",
+    "squashfs",
+    "sysfs",
+    "tmpfs",
+    "tracefs",
+];
+
+#[derive(Debug, Clone, Copy)]
+pub struct CpuUsage {
+    /// Busy time across all cores over the sample window, 0-100
+    pub percentage: f64,
+    pub cores: usize,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct MemoryUsage {
+    pub total_bytes: u64,
+    pub available_bytes: u64,
+}
+
+impl MemoryUsage {
+    pub fn used_bytes(&self) -> u64 {
+        self.total_bytes.saturating_sub(self.available_bytes)
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct FilesystemUsage {
+    pub mount_point: String,
+    pub device: String,
+    pub fs_type: String,
+    pub total_bytes: u64,
+    pub used_bytes: u64,
+    /// Space available to unprivileged users, which excludes reserved blocks
+    pub available_bytes: u64,
+}
+
+impl FilesystemUsage {
+    /// Used share of the space usable by non-root users, as `df` reports it
+    pub fn percentage(&self) -> f64 {
+        let usable = self.used_bytes + self.available_bytes;
+        if usable == 0 {
+            0.0
+        } else {
+            self.used_bytes as f64 / usable as f64 * 100.0
+        }
+    }
+}
+
+/// Sample `/proc/stat` twice, `window` apart
+pub async fn cpu_usage(window: Duration) -> io::Result<CpuUsage> {
+    let (first, cores) = read_cpu_times().await?;
+    tokio::time::sleep(window).await;
+    let (second, _) = read_cpu_times().await?;
+
+    let total = second.total.saturating_sub(first.total);
+    let idle = second.idle.saturating_sub(first.idle);
+    let percentage = if total == 0 {
+        0.0
+    } else {
+        (total - idle.min(total)) as f64 / total as f64 * 100.0
+    };
+
+    Ok(CpuUsage { percentage, cores })
+}
+
+struct CpuTimes {
+    idle: u64,
+    total: u64,
+}
+
+/// Aggregate jiffies of the `cpu` line and the number of `cpuN` lines
+async fn read_cpu_times() -> io::Result<(CpuTimes, usize)> {
+    let content = tokio::fs::read_to_string("/proc/stat").await?;
+
+    let line = content
+        .lines()
+        .find(|line| line.starts_with("cpu "))
+        .ok_or_else(|| invalid_data("/proc/stat has no cpu line"))?;
+
+    // user nice system idle iowait irq softirq steal; guest time is already
+    // included in user and nice
+    let fields: Vec<u64> = line
+        .split_whitespace()
+        .skip(1)
+        .take(8)
+        .map(|v| v.parse().unwrap_or(0))
+        .collect();
+
+    let idle = fields.get(3).copied().unwrap_or(0) + fields.get(4).copied().unwrap_or(0);
+    let total = fields.iter().sum();
+
+    let cores = content
+        .lines()
+        .filter(|line| {
+            line.strip_prefix("cpu")
+                .is_some_and(|rest| rest.starts_with(|c: char| c.is_ascii_digit()))
+        })
+        .count();
+
+    Ok((CpuTimes { idle, total }, cores.max(1)))
+}
+
+pub async fn memory_usage() -> io::Result<MemoryUsage> {
+    let content = tokio::fs::read_to_string("/proc/meminfo").await?;
+
+    let values: HashMap<&str, u64> = content
+        .lines()
+        .filter_map(|line| {
+            let (key, rest) = line.split_once(':')?;
+            let kib = rest.split_whitespace().next()?.parse().ok()?;
+            Some((key, kib))
+        })
+        .collect();
+
+    let total = values
+        .get("MemTotal")
+        .copied()
+        .ok_or_else(|| invalid_data("/proc/meminfo has no MemTotal"))?;
+    // Kernels before 3.14 lack MemAvailable
+    let available = values.get("MemAvailable").copied().unwrap_or_else(|| {
+        ["MemFree", "Buffers", "Cached"]
+            .iter()
+            .filter_map(|key| values.get(key))
+            .sum()
+    });
+
+    Ok(MemoryUsage {
+        total_bytes: total * 1024,
+        available_bytes: available * 1024,
+    })
+}
+
+/// Usage of the filesystem holding `path`
+pub async fn filesystem_usage(path: &str) -> io::Result<FilesystemUsage> {
+    let path = path.to_string();
+    tokio::task::spawn_blocking(move || statvfs(&path, String::new(), String::new()))
+        .await
+        .map_err(io::Error::other)?
+}
+
+/// Usage of every mounted filesystem that stores data
+///
+/// Bind mounts of the same device (common in containers) are reported once,
+/// under their shortest mount point.
+pub async fn filesystems() -> io::Result<Vec<FilesystemUsage>> {
+    let content = tokio::fs::read_to_string("/proc/mounts").await?;
+
+    let mut mounts: Vec<(String, String, String)> = content
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split_whitespace();
+            let device = fields.next()?;
+            let mount_point = unescape_mount_field(fields.next()?);
+            let fs_type = fields.next()?;
+            (!PSEUDO_FILESYSTEMS.contains(&fs_type))
+                .then(|| (device.to_string(), mount_point, fs_type.to_string()))
+        })
+        .collect();
+
+    mounts.sort_by_key(|(_, mount_point, _)| mount_point.len());
+    let mut seen = std::collections::HashSet::new();
+    mounts.retain(|(device, _, _)| seen.insert(device.clone()));
+
+    tokio::task::spawn_blocking(move || {
+        let mut usage: Vec<FilesystemUsage> = mounts
+            .into_iter()
+            // Mounts that vanished or are not accessible are skipped
+            .filter_map(|(device, mount_point, fs_type)| {
+                statvfs(&mount_point, device, fs_type).ok()
+            })
+            .filter(|fs| fs.total_bytes > 0)
+            .collect();
+        usage.sort_by(|a, b| a.mount_point.cmp(&b.mount_point));
+        usage
+    })
+    .await
+    .map_err(io::Error::other)
+}
+
+fn statvfs(path: &str, device: String, fs_type: String) -> io::Result<FilesystemUsage> {
+    let c_path = CString::new(path).map_err(|_| invalid_data("path contains a NUL byte"))?;
+
+    // SAFETY: `c_path` is a valid NUL-terminated string and `stat` is a plain
+    // C struct that statvfs fully initialises on success.
+    let stat = unsafe {
+        let mut stat: libc::statvfs = std::mem::zeroed();
+        if libc::statvfs(c_path.as_ptr(), &mut stat) != 0 {
+            return Err(io::Error::last_os_error());
+        }
+        stat
+    };
+
+    let fragment = stat.f_frsize as u64;
+    let total_bytes = stat.f_blocks as u64 * fragment;
+    let free_bytes = stat.f_bfree as u64 * fragment;
+
+    Ok(FilesystemUsage {
+        mount_point: path.to_string(),
+        device,
+        fs_type,
+        total_bytes,
+        used_bytes: total_bytes.saturating_sub(free_bytes),
+        available_bytes: stat.f_bavail as u64 * fragment,
+    })
+}
+
+/// `/proc/mounts` escapes spaces, tabs, newlines and backslashes as octal
+fn unescape_mount_field(field: &str) -> String {
+    field
+        .replace("\\040", " ")
+        .replace("\\011", "\t")
+        .replace("\\012", "\n")
+        .replace("\\134", "\\")
+}
+
+fn invalid_data(message: &str) -> io::Error {
+    io::Error::new(io::ErrorKind::InvalidData, message.to_string())
+}
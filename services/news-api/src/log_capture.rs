@@ -0,0 +1,563 @@
+//! In-process log capture for the admin Logs page.
+//!
+//! [`LogCaptureLayer`] sits next to the stderr formatter in the tracing
+//! registry and copies every event it sees into a bounded ring buffer, along
+//! with the fields of the spans it happened in. New entries are also
+//! broadcast to live subscribers, and can optionally be forwarded to Postgres
+//! so they outlive the buffer and restarts.
+
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use serde_json::{Map, Value};
+use std::collections::VecDeque;
+use std::fmt;
+use std::sync::{Arc, Mutex, MutexGuard};
+use std::time::Duration;
+use tokio::sync::{broadcast, mpsc};
+use tracing::field::{Field, Visit};
+use tracing::span::{Attributes, Id, Record};
+use tracing::{Event, Subscriber};
+use tracing_subscriber::layer::Context;
+use tracing_subscriber::registry::LookupSpan;
+use tracing_subscriber::Layer;
+
+use crate::{
+    config::LogsConfig,
+    db::Database,
+    models::{LogEntry, LogLevel},
+};
+
+/// Entries waiting to be written to Postgres; more are dropped, not queued
+const PERSIST_QUEUE: usize = 10_000;
+
+/// Entries a live subscriber may fall behind by before it starts missing some
+pub(crate) const LIVE_CAPACITY: usize = 1024;
+
+/// Rows per insert
+const PERSIST_BATCH: usize = 500;
+
+const PERSIST_INTERVAL: Duration = Duration::from_secs(1);
+
+const PRUNE_INTERVAL: Duration = Duration::from_secs(3600);
+
+/// Shared handle to the captured entries
+#[derive(Clone)]
+pub struct LogBuffer {
+    inner: Arc<Mutex<Ring>>,
+}
+
+struct Ring {
+    entries: VecDeque<LogEntry>,
+    capacity: usize,
+    next_id: i64,
+    level: LogLevel,
+    service: String,
+    live: broadcast::Sender<LogEntry>,
+    persist: Option<mpsc::Sender<LogEntry>>,
+}
+
+/// Live feed of new entries for one subscriber
+pub struct Subscription {
+    /// Buffered entries newer than the resume point, oldest first
+    pub backlog: Vec<LogEntry>,
+    /// Entries newer than the resume point that were already evicted
+    pub missed: u64,
+    pub receiver: broadcast::Receiver<LogEntry>,
+}
+
+/// Filters shared by the memory and Postgres queries
+#[derive(Debug, Default)]
+pub struct LogFilter {
+    pub service: Option<String>,
+    /// Least severe level to include
+    pub level: Option<LogLevel>,
+    /// Module path prefix, e.g. `news_api::status`
+    pub target: Option<String>,
+    pub from: Option<DateTime<Utc>>,
+    /// Exclusive
+    pub to: Option<DateTime<Utc>>,
+    /// Case-insensitive substring of the message
+    pub text: Option<String>,
+}
+
+impl LogFilter {
+    pub fn matches(&self, entry: &LogEntry) -> bool {
+        self.service.as_ref().is_none_or(|s| entry.service == *s)
+            && self.level.is_none_or(|l| entry.level >= l)
+            && self.target.as_ref().is_none_or(|t| entry.target.starts_with(t.as_str()))
+            && self.from.is_none_or(|from| entry.timestamp >= from)
+            && self.to.is_none_or(|to| entry.timestamp < to)
+            && self
+                .text
+                .as_ref()
+                .is_none_or(|q| entry.message.to_lowercase().contains(&q.to_lowercase()))
+    }
+}
+
+impl LogBuffer {
+    /// Buffer with default settings; call [`LogBuffer::configure`] once config is loaded
+    pub fn new() -> Self {
+        let defaults = LogsConfig::default();
+        Self {
+            inner: Arc::new(Mutex::new(Ring {
+                entries: VecDeque::new(),
+                capacity: defaults.buffer_size,
+                next_id: 1,
+                level: defaults.level,
+                service: defaults.service,
+                live: broadcast::channel(LIVE_CAPACITY).0,
+                persist: None,
+            })),
+        }
+    }
+
+    /// Apply the `[logs]` settings, trimming the buffer if it shrank
+    pub fn configure(&self, config: &LogsConfig) {
+        let mut ring = self.lock();
+        ring.capacity = config.buffer_size;
+        ring.level = config.level;
+        ring.service = config.service.clone();
+        while ring.entries.len() > ring.capacity {
+            ring.entries.pop_front();
+        }
+    }
+
+    pub fn layer(&self) -> LogCaptureLayer {
+        LogCaptureLayer {
+            buffer: self.clone(),
+        }
+    }
+
+    /// Latest `limit` matching entries, oldest first
+    pub fn query(&self, filter: &LogFilter, limit: usize) -> Vec<LogEntry> {
+        let ring = self.lock();
+        let mut entries: Vec<LogEntry> = ring
+            .entries
+            .iter()
+            .rev()
+            .filter(|e| filter.matches(e))
+            .take(limit)
+            .cloned()
+            .collect();
+        entries.reverse();
+        entries
+    }
+
+    /// Subscribe to new entries, replaying buffered ones after `last_id`
+    ///
+    /// An ID the buffer has not reached yet comes from before a restart, so
+    /// everything buffered is replayed.
+    pub fn subscribe(&self, last_id: Option<i64>) -> Subscription {
+        let ring = self.lock();
+        // Taken under the lock so no entry falls between backlog and receiver
+        let receiver = ring.live.subscribe();
+
+        let (backlog, missed) = match last_id {
+            None => (Vec::new(), 0),
+            Some(id) if id >= ring.next_id => (ring.entries.iter().cloned().collect(), 0),
+            Some(id) => {
+                let oldest = ring.entries.front().map_or(ring.next_id, |e| e.id);
+                let missed = (oldest - id - 1).max(0) as u64;
+                let backlog = ring.entries.iter().filter(|e| e.id > id).cloned().collect();
+                (backlog, missed)
+            }
+        };
+
+        Subscription {
+            backlog,
+            missed,
+            receiver,
+        }
+    }
+
+    fn push(&self, level: LogLevel, target: &str, message: String, metadata: Map<String, Value>) {
+        let mut ring = self.lock();
+        let entry = LogEntry {
+            id: ring.next_id,
+            timestamp: Utc::now(),
+            level,
+            service: ring.service.clone(),
+            target: target.to_string(),
+            message,
+            metadata,
+        };
+        ring.next_id += 1;
+
+        // Persisting sqlx's query logs or the persister's own warnings would feed
+        // back into itself
+        if let Some(tx) = &ring.persist {
+            if !target.starts_with("sqlx") && !target.starts_with(module_path!()) {
+                let _ = tx.try_send(entry.clone());
+            }
+        }
+
+        // Never blocks: subscribers that fall behind lose their oldest entries
+        if ring.live.receiver_count() > 0 {
+            let _ = ring.live.send(entry.clone());
+        }
+
+        if ring.entries.len() >= ring.capacity {
+            ring.entries.pop_front();
+        }
+        ring.entries.push_back(entry);
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Ring> {
+        // A panic while holding the lock leaves the ring consistent; keep logging
+        self.inner.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+/// Tracing layer feeding a [`LogBuffer`]
+pub struct LogCaptureLayer {
+    buffer: LogBuffer,
+}
+
+/// Span fields, stored in the span's extensions
+struct SpanFields(Map<String, Value>);
+
+impl<S> Layer<S> for LogCaptureLayer
+where
+    S: Subscriber + for<'a> LookupSpan<'a>,
+{
+    fn on_new_span(&self, attrs: &Attributes<'_>, id: &Id, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else { return };
+        let mut visitor = FieldVisitor::default();
+        attrs.record(&mut visitor);
+        span.extensions_mut().insert(SpanFields(visitor.fields));
+    }
+
+    fn on_record(&self, id: &Id, values: &Record<'_>, ctx: Context<'_, S>) {
+        let Some(span) = ctx.span(id) else { return };
+        let mut extensions = span.extensions_mut();
+        if let Some(SpanFields(fields)) = extensions.get_mut::<SpanFields>() {
+            let mut visitor = FieldVisitor {
+                fields: std::mem::take(fields),
+                message: None,
+            };
+            values.record(&mut visitor);
+            *fields = visitor.fields;
+        }
+    }
+
+    fn on_event(&self, event: &Event<'_>, ctx: Context<'_, S>) {
+        let meta = event.metadata();
+        let level = LogLevel::from(*meta.level());
+        if level < self.buffer.lock().level {
+            return;
+        }
+
+        // Outer spans first so inner span and event fields take precedence
+        let mut visitor = FieldVisitor::default();
+        if let Some(scope) = ctx.event_scope(event) {
+            for span in scope.from_root() {
+                if let Some(SpanFields(fields)) = span.extensions().get::<SpanFields>() {
+                    visitor.fields.extend(fields.clone());
+                }
+            }
+        }
+        event.record(&mut visitor);
+
+        let message = visitor.message.unwrap_or_default();
+        self.buffer.push(level, meta.target(), message, visitor.fields);
+    }
+}
+
+#[derive(Default)]
+struct FieldVisitor {
+    fields: Map<String, Value>,
+    message: Option<String>,
+}
+
+impl FieldVisitor {
+    fn insert(&mut self, field: &Field, value: Value) {
+        if field.name() == "message" {
+            self.message = Some(match value {
+                Value::String(s) => s,
+                other => other.to_string(),
+            });
+        } else {
+            self.fields.insert(field.name().to_string(), value);
+        }
+    }
+}
+
+impl Visit for FieldVisitor {
+    fn record_f64(&mut self, field: &Field, value: f64) {
+        self.insert(field, Value::from(value));
+    }
+
+    fn record_i64(&mut self, field: &Field, value: i64) {
+        self.insert(field, Value::from(value));
+    }
+
+    fn record_u64(&mut self, field: &Field, value: u64) {
+        self.insert(field, Value::from(value));
+    }
+
+    fn record_bool(&mut self, field: &Field, value: bool) {
+        self.insert(field, Value::from(value));
+    }
+
+    fn record_str(&mut self, field: &Field, value: &str) {
+        self.insert(field, Value::from(value));
+    }
+
+    fn record_error(&mut self, field: &Field, value: &(dyn std::error::Error + 'static)) {
+        self.insert(field, Value::from(value.to_string()));
+    }
+
+    fn record_debug(&mut self, field: &Field, value: &dyn fmt::Debug) {
+        self.insert(field, Value::from(format!("{value:?}")));
+    }
+}
+
+/// Forward captured entries to the `log_entries` table and prune old rows
+///
+/// Entries are written in batches; when Postgres falls behind the queue
+/// fills up and further entries are kept in memory only.
+pub fn spawn_persister(buffer: &LogBuffer, db: Database, retention_days: u32) {
+    let (tx, mut rx) = mpsc::channel(PERSIST_QUEUE);
+    buffer.lock().persist = Some(tx);
+
+    tokio::spawn(async move {
+        let mut flush = tokio::time::interval(PERSIST_INTERVAL);
+        let mut prune = tokio::time::interval(PRUNE_INTERVAL);
+        let mut batch = Vec::with_capacity(PERSIST_BATCH);
+
+        loop {
+            tokio::select! {
+                received = rx.recv_many(&mut batch, PERSIST_BATCH) => {
+                    if received == 0 {
+                        break;
+                    }
+                    if batch.len() < PERSIST_BATCH {
+                        continue;
+                    }
+                }
+                _ = flush.tick() => {}
+                _ = prune.tick(), if retention_days > 0 => {
+                    if let Err(e) = prune_entries(&db, retention_days).await {
+                        tracing::warn!("Failed to prune persisted logs: {e}");
+                    }
+                    continue;
+                }
+            }
+
+            if !batch.is_empty() {
+                if let Err(e) = insert_entries(&db, &batch).await {
+                    tracing::warn!("Failed to persist {} log entries: {e}", batch.len());
+                }
+                batch.clear();
+            }
+        }
+    });
+}
+
+async fn insert_entries(db: &Database, entries: &[LogEntry]) -> Result<(), sqlx::Error> {
+    let timestamps: Vec<DateTime<Utc>> = entries.iter().map(|e| e.timestamp).collect();
+    let levels: Vec<&str> = entries.iter().map(|e| e.level.as_str()).collect();
+    let services: Vec<&str> = entries.iter().map(|e| e.service.as_str()).collect();
+    let targets: Vec<&str> = entries.iter().map(|e| e.target.as_str()).collect();
+    let messages: Vec<&str> = entries.iter().map(|e| e.message.as_str()).collect();
+    let metadata: Vec<Value> = entries
+        .iter()
+        .map(|e| Value::Object(e.metadata.clone()))
+        .collect();
+
+    sqlx::query(
+        r#"
+        INSERT INTO log_entries (logged_at, level, service, target, message, metadata)
+        SELECT * FROM UNNEST(
+            $1::TIMESTAMPTZ[], $2::VARCHAR[], $3::VARCHAR[], $4::VARCHAR[], $5::TEXT[],
+            $6::JSONB[]
+        )
+        "#,
+    )
+    .bind(timestamps)
+    .bind(levels)
+    .bind(services)
+    .bind(targets)
+    .bind(messages)
+    .bind(metadata)
+    .execute(&db.pool)
+    .await?;
+
+    Ok(())
+}
+
+async fn prune_entries(db: &Database, retention_days: u32) -> Result<(), sqlx::Error> {
+    let cutoff = Utc::now() - ChronoDuration::days(i64::from(retention_days));
+    let deleted = sqlx::query("DELETE FROM log_entries WHERE logged_at < $1")
+        .bind(cutoff)
+        .execute(&db.pool)
+        .await?
+        .rows_affected();
+
+    if deleted > 0 {
+        tracing::info!("Pruned {deleted} persisted log entries older than {retention_days} days");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn buffer(size: usize) -> LogBuffer {
+        let buffer = LogBuffer::new();
+        buffer.configure(&LogsConfig {
+            buffer_size: size,
+            level: LogLevel::Trace,
+            ..LogsConfig::default()
+        });
+        buffer
+    }
+
+    fn push(buffer: &LogBuffer, level: LogLevel, target: &str, message: &str) {
+        buffer.push(level, target, message.to_string(), Map::new());
+    }
+
+    fn ids(entries: &[LogEntry]) -> Vec<i64> {
+        entries.iter().map(|e| e.id).collect()
+    }
+
+    #[test]
+    fn full_buffer_evicts_the_oldest_entries() {
+        let buffer = buffer(3);
+        for i in 1..=5 {
+            push(&buffer, LogLevel::Info, "news_api", &format!("entry {i}"));
+        }
+
+        let entries = buffer.query(&LogFilter::default(), 10);
+        assert_eq!(ids(&entries), [3, 4, 5]);
+        assert_eq!(entries[0].message, "entry 3");
+        assert_eq!(ids(&buffer.query(&LogFilter::default(), 2)), [4, 5]);
+
+        // Shrinking the buffer trims it right away
+        buffer.configure(&LogsConfig {
+            buffer_size: 1,
+            ..LogsConfig::default()
+        });
+        assert_eq!(ids(&buffer.query(&LogFilter::default(), 10)), [5]);
+    }
+
+    fn entry(level: LogLevel, target: &str, message: &str, minutes_ago: i64) -> LogEntry {
+        LogEntry {
+            id: 1,
+            timestamp: Utc::now() - ChronoDuration::minutes(minutes_ago),
+            level,
+            service: "news-api".to_string(),
+            target: target.to_string(),
+            message: message.to_string(),
+            metadata: Map::new(),
+        }
+    }
+
+    #[test]
+    fn filter_matches_each_dimension() {
+        let entry = entry(LogLevel::Warn, "news_api::status", "OpenSearch is SLOW", 10);
+        let now = Utc::now();
+        let matches = |filter: LogFilter| filter.matches(&entry);
+
+        assert!(matches(LogFilter::default()));
+
+        assert!(matches(LogFilter {
+            level: Some(LogLevel::Warn),
+            ..LogFilter::default()
+        }));
+        assert!(matches(LogFilter {
+            level: Some(LogLevel::Info),
+            ..LogFilter::default()
+        }));
+        assert!(!matches(LogFilter {
+            level: Some(LogLevel::Error),
+            ..LogFilter::default()
+        }));
+
+        for (target, expected) in [
+            ("news_api", true),
+            ("news_api::status", true),
+            ("news_api::routes", false),
+            ("sqlx", false),
+        ] {
+            let filter = LogFilter {
+                target: Some(target.to_string()),
+                ..LogFilter::default()
+            };
+            assert_eq!(matches(filter), expected, "{target}");
+        }
+
+        // `from` is inclusive, `to` exclusive
+        assert!(matches(LogFilter {
+            from: Some(entry.timestamp),
+            to: Some(now),
+            ..LogFilter::default()
+        }));
+        assert!(!matches(LogFilter {
+            from: Some(now - ChronoDuration::minutes(5)),
+            ..LogFilter::default()
+        }));
+        assert!(!matches(LogFilter {
+            to: Some(entry.timestamp),
+            ..LogFilter::default()
+        }));
+
+        for (text, expected) in [("slow", true), ("opensearch is", true), ("fast", false)] {
+            let filter = LogFilter {
+                text: Some(text.to_string()),
+                ..LogFilter::default()
+            };
+            assert_eq!(matches(filter), expected, "{text}");
+        }
+
+        assert!(!matches(LogFilter {
+            service: Some("crawler".to_string()),
+            ..LogFilter::default()
+        }));
+        assert!(!matches(LogFilter {
+            level: Some(LogLevel::Warn),
+            text: Some("fast".to_string()),
+            ..LogFilter::default()
+        }));
+    }
+
+    #[test]
+    fn query_applies_the_filter_before_the_limit() {
+        let buffer = buffer(10);
+        push(&buffer, LogLevel::Error, "news_api", "first error");
+        push(&buffer, LogLevel::Info, "news_api", "info");
+        push(&buffer, LogLevel::Error, "news_api", "second error");
+        push(&buffer, LogLevel::Debug, "news_api", "debug");
+
+        let errors = LogFilter {
+            level: Some(LogLevel::Error),
+            ..LogFilter::default()
+        };
+        assert_eq!(ids(&buffer.query(&errors, 10)), [1, 3]);
+        assert_eq!(ids(&buffer.query(&errors, 1)), [3]);
+    }
+
+    #[test]
+    fn persister_skips_sqlx_and_its_own_entries() {
+        let buffer = buffer(10);
+        let (tx, mut rx) = mpsc::channel(10);
+        buffer.lock().persist = Some(tx);
+
+        for target in [
+            "sqlx::query",
+            "news_api::log_capture",
+            "news_api::status",
+            "tower_http::trace::on_response",
+        ] {
+            push(&buffer, LogLevel::Warn, target, "message");
+        }
+
+        let mut persisted = Vec::new();
+        while let Ok(entry) = rx.try_recv() {
+            persisted.push(entry.target);
+        }
+        assert_eq!(persisted, ["news_api::status", "tower_http::trace::on_response"]);
+        // Everything stays visible in memory
+        assert_eq!(buffer.query(&LogFilter::default(), 10).len(), 4);
+    }
+}
@@ -0,0 +1,364 @@
+//! Prometheus metrics in the text exposition format.
+//!
+//! HTTP requests are recorded by [`HttpMetricsLayer`] around the router and
+//! OpenSearch calls by a [`SearchTimer`] in each `SearchClient` method, and
+//! response cache lookups through [`record_cache`]; all go to one
+//! process-wide registry. Values that are cheap to read at scrape
+//! time, such as pool state and article counts, are not stored here but
+//! written by the `/metrics` handler through [`write_gauge`].
+
+use axum::extract::MatchedPath;
+use axum::http::{Request, Response};
+use std::collections::HashMap;
+use std::fmt::Write;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::atomic::{AtomicI64, Ordering};
+use std::sync::{LazyLock, Mutex, MutexGuard};
+use std::task::{Context, Poll};
+use std::time::{Instant, SystemTime, UNIX_EPOCH};
+use tower::{Layer, Service};
+
+use crate::error::{ApiError, ApiResult};
+
+/// Upper bounds in seconds; the Prometheus client library defaults
+const LATENCY_BUCKETS: [f64; 11] = [0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0];
+
+static REGISTRY: LazyLock<Registry> = LazyLock::new(Registry::new);
+
+struct Registry {
+    http: Mutex<HashMap<HttpKey, Histogram>>,
+    http_in_flight: AtomicI64,
+    search: Mutex<HashMap<&'static str, SearchStats>>,
+    cache: Mutex<HashMap<&'static str, CacheStats>>,
+    /// Unix time the registry was created, i.e. process start
+    started_at: f64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+struct HttpKey {
+    method: String,
+    route: String,
+    status: u16,
+}
+
+#[derive(Default)]
+struct SearchStats {
+    latency: Histogram,
+    errors: u64,
+}
+
+#[derive(Default)]
+struct CacheStats {
+    hits: u64,
+    misses: u64,
+}
+
+#[derive(Default)]
+struct Histogram {
+    /// Cumulative count per bound of `LATENCY_BUCKETS`
+    buckets: [u64; LATENCY_BUCKETS.len()],
+    sum: f64,
+    count: u64,
+}
+
+impl Registry {
+    fn new() -> Self {
+        Self {
+            http: Mutex::default(),
+            http_in_flight: AtomicI64::new(0),
+            search: Mutex::default(),
+            cache: Mutex::default(),
+            started_at: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map_or(0.0, |d| d.as_secs_f64()),
+        }
+    }
+}
+
+impl Histogram {
+    fn observe(&mut self, secs: f64) {
+        for (bucket, bound) in self.buckets.iter_mut().zip(LATENCY_BUCKETS) {
+            if secs <= bound {
+                *bucket += 1;
+            }
+        }
+        self.sum += secs;
+        self.count += 1;
+    }
+
+    fn write(&self, out: &mut String, name: &str, labels: &str) {
+        for (count, bound) in self.buckets.iter().zip(LATENCY_BUCKETS) {
+            let _ = writeln!(out, "{name}_bucket{{{labels},le=\"{bound}\"}} {count}");
+        }
+        let _ = writeln!(out, "{name}_bucket{{{labels},le=\"+Inf\"}} {}", self.count);
+        let _ = writeln!(out, "{name}_sum{{{labels}}} {}", self.sum);
+        let _ = writeln!(out, "{name}_count{{{labels}}} {}", self.count);
+    }
+}
+
+/// Start the process clock; call once at startup
+pub fn init() {
+    LazyLock::force(&REGISTRY);
+}
+
+/// Render the HTTP, OpenSearch and cache metrics
+pub fn render(out: &mut String) {
+    write_gauge(
+        out,
+        "process_start_time_seconds",
+        "Start time of the process since the Unix epoch",
+        &[],
+        REGISTRY.started_at,
+    );
+
+    {
+        let http = lock(&REGISTRY.http);
+        let mut keys: Vec<&HttpKey> = http.keys().collect();
+        keys.sort();
+
+        write_header(out, "http_requests_total", "HTTP requests handled", "counter");
+        for key in &keys {
+            let labels = http_labels(key);
+            let _ = writeln!(out, "http_requests_total{{{labels}}} {}", http[*key].count);
+        }
+
+        let name = "http_request_duration_seconds";
+        write_header(out, name, "HTTP request latency", "histogram");
+        for key in &keys {
+            http[*key].write(out, name, &http_labels(key));
+        }
+    }
+
+    write_gauge(
+        out,
+        "http_requests_in_flight",
+        "HTTP requests being handled",
+        &[],
+        REGISTRY.http_in_flight.load(Ordering::Relaxed) as f64,
+    );
+
+    {
+        let search = lock(&REGISTRY.search);
+        let mut methods: Vec<&&str> = search.keys().collect();
+        methods.sort();
+
+        let name = "opensearch_request_duration_seconds";
+        write_header(out, name, "OpenSearch call latency per SearchClient method", "histogram");
+        for method in &methods {
+            search[**method].latency.write(out, name, &labels(&[("method", method)]));
+        }
+
+        let name = "opensearch_request_errors_total";
+        write_header(out, name, "Failed OpenSearch calls per SearchClient method", "counter");
+        for method in &methods {
+            let errors = search[**method].errors;
+            let _ = writeln!(out, "{name}{{{}}} {errors}", labels(&[("method", method)]));
+        }
+    }
+
+    let cache = lock(&REGISTRY.cache);
+    let mut routes: Vec<&&str> = cache.keys().collect();
+    routes.sort();
+
+    let name = "http_cache_hits_total";
+    write_header(out, name, "Responses served from the response cache", "counter");
+    for route in &routes {
+        let hits = cache[**route].hits;
+        let _ = writeln!(out, "{name}{{{}}} {hits}", labels(&[("route", route)]));
+    }
+
+    let name = "http_cache_misses_total";
+    write_header(out, name, "Cacheable responses that had to be loaded", "counter");
+    for route in &routes {
+        let misses = cache[**route].misses;
+        let _ = writeln!(out, "{name}{{{}}} {misses}", labels(&[("route", route)]));
+    }
+}
+
+/// Count a response cache lookup; requests that waited for another's load are hits
+pub fn record_cache(route: &'static str, hit: bool) {
+    let mut cache = lock(&REGISTRY.cache);
+    let stats = cache.entry(route).or_default();
+    if hit {
+        stats.hits += 1;
+    } else {
+        stats.misses += 1;
+    }
+}
+
+/// Write a single gauge sample with its HELP and TYPE lines
+pub fn write_gauge(out: &mut String, name: &str, help: &str, labels: &[(&str, &str)], value: f64) {
+    write_header(out, name, help, "gauge");
+    write_sample(out, name, labels, value);
+}
+
+/// Write one more sample of a gauge whose header was already written
+pub fn write_sample(out: &mut String, name: &str, sample_labels: &[(&str, &str)], value: f64) {
+    if sample_labels.is_empty() {
+        let _ = writeln!(out, "{name} {value}");
+    } else {
+        let _ = writeln!(out, "{name}{{{}}} {value}", labels(sample_labels));
+    }
+}
+
+fn write_header(out: &mut String, name: &str, help: &str, kind: &str) {
+    let _ = writeln!(out, "# HELP {name} {help}");
+    let _ = writeln!(out, "# TYPE {name} {kind}");
+}
+
+fn http_labels(key: &HttpKey) -> String {
+    labels(&[
+        ("method", &key.method),
+        ("route", &key.route),
+        ("status", &key.status.to_string()),
+    ])
+}
+
+fn labels(pairs: &[(&str, &str)]) -> String {
+    pairs
+        .iter()
+        .map(|(name, value)| {
+            let value = value
+                .replace('\\', "\\\\")
+                .replace('"', "\\\"")
+                .replace('\n', "\\n");
+            format!("{name}=\"{value}\"")
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+fn lock<T>(mutex: &Mutex<T>) -> MutexGuard<'_, T> {
+    mutex.lock().unwrap_or_else(|e| e.into_inner())
+}
+
+/// Times one OpenSearch call
+///
+/// Finish it with the call's result; a timer dropped unfinished, because an
+/// error was returned early with `?` or the call was cancelled, counts as a
+/// failed call.
+pub struct SearchTimer {
+    method: &'static str,
+    started: Instant,
+    finished: bool,
+}
+
+impl SearchTimer {
+    pub fn start(method: &'static str) -> Self {
+        Self {
+            method,
+            started: Instant::now(),
+            finished: false,
+        }
+    }
+
+    /// Record the call and pass its result through; a missing document is not
+    /// an error of the cluster
+    pub fn finish<T>(mut self, result: ApiResult<T>) -> ApiResult<T> {
+        let failed = matches!(&result, Err(e) if !matches!(e, ApiError::NotFound(_)));
+        self.record(failed);
+        self.finished = true;
+        result
+    }
+
+    fn record(&self, failed: bool) {
+        let mut search = lock(&REGISTRY.search);
+        let stats = search.entry(self.method).or_default();
+        stats.latency.observe(self.started.elapsed().as_secs_f64());
+        if failed {
+            stats.errors += 1;
+        }
+    }
+}
+
+impl Drop for SearchTimer {
+    fn drop(&mut self) {
+        if !self.finished {
+            self.record(true);
+        }
+    }
+}
+
+/// Tower layer recording request count and latency per matched route
+///
+/// Add it with `Router::route_layer` so the matched route template is known;
+/// requests that match no route are not recorded.
+#[derive(Clone, Copy, Default)]
+pub struct HttpMetricsLayer;
+
+impl<S> Layer<S> for HttpMetricsLayer {
+    type Service = HttpMetrics<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        HttpMetrics { inner }
+    }
+}
+
+#[derive(Clone)]
+pub struct HttpMetrics<S> {
+    inner: S,
+}
+
+impl<S, ReqBody, ResBody> Service<Request<ReqBody>> for HttpMetrics<S>
+where
+    S: Service<Request<ReqBody>, Response = Response<ResBody>> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+    ReqBody: Send + 'static,
+{
+    type Response = S::Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<ReqBody>) -> Self::Future {
+        let method = request.method().to_string();
+        let route = request
+            .extensions()
+            .get::<MatchedPath>()
+            .map_or("unmatched", MatchedPath::as_str)
+            .to_string();
+
+        // The clone may not be ready; call the one that was polled
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+
+        Box::pin(async move {
+            let in_flight = InFlight::enter();
+            let started = Instant::now();
+            let result = inner.call(request).await;
+            let elapsed = started.elapsed().as_secs_f64();
+            drop(in_flight);
+
+            // Errors are turned into responses by axum before they get here
+            if let Ok(response) = &result {
+                let key = HttpKey {
+                    method,
+                    route,
+                    status: response.status().as_u16(),
+                };
+                lock(&REGISTRY.http).entry(key).or_default().observe(elapsed);
+            }
+            result
+        })
+    }
+}
+
+/// Counts a request as in flight until dropped, even if the client goes away
+struct InFlight;
+
+impl InFlight {
+    fn enter() -> Self {
+        REGISTRY.http_in_flight.fetch_add(1, Ordering::Relaxed);
+        Self
+    }
+}
+
+impl Drop for InFlight {
+    fn drop(&mut self) {
+        REGISTRY.http_in_flight.fetch_sub(1, Ordering::Relaxed);
+    }
+}
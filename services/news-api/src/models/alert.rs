@@ -0,0 +1,218 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use std::str::FromStr;
+use validator::{Validate, ValidationError};
+
+/// Value an alert rule watches
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AlertMetric {
+    /// Failed share of the articles crawled in the window, 0-100
+    CrawlFailureRate,
+    /// Articles crawled in the window
+    ArticlesIngested,
+    /// 1 if the latest probe of the target service found it down, else 0
+    ServiceDown,
+    /// Used share of the filesystem holding the target path, 0-100
+    DiskUsage,
+}
+
+impl AlertMetric {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::CrawlFailureRate => "crawl_failure_rate",
+            Self::ArticlesIngested => "articles_ingested",
+            Self::ServiceDown => "service_down",
+            Self::DiskUsage => "disk_usage",
+        }
+    }
+}
+
+impl FromStr for AlertMetric {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "crawl_failure_rate" => Ok(Self::CrawlFailureRate),
+            "articles_ingested" => Ok(Self::ArticlesIngested),
+            "service_down" => Ok(Self::ServiceDown),
+            "disk_usage" => Ok(Self::DiskUsage),
+            other => Err(format!("unknown alert metric {other:?}")),
+        }
+    }
+}
+
+impl TryFrom<String> for AlertMetric {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, String> {
+        value.parse()
+    }
+}
+
+/// How a metric value is compared against the threshold
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Comparison {
+    #[serde(rename = ">")]
+    Above,
+    #[serde(rename = ">=")]
+    AtLeast,
+    #[serde(rename = "<")]
+    Below,
+    #[serde(rename = "<=")]
+    AtMost,
+}
+
+impl Comparison {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Above => ">",
+            Self::AtLeast => ">=",
+            Self::Below => "<",
+            Self::AtMost => "<=",
+        }
+    }
+
+    pub fn holds(self, value: f64, threshold: f64) -> bool {
+        match self {
+            Self::Above => value > threshold,
+            Self::AtLeast => value >= threshold,
+            Self::Below => value < threshold,
+            Self::AtMost => value <= threshold,
+        }
+    }
+}
+
+impl FromStr for Comparison {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            ">" => Ok(Self::Above),
+            ">=" => Ok(Self::AtLeast),
+            "<" => Ok(Self::Below),
+            "<=" => Ok(Self::AtMost),
+            other => Err(format!("unknown comparison operator {other:?}")),
+        }
+    }
+}
+
+impl TryFrom<String> for Comparison {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, String> {
+        value.parse()
+    }
+}
+
+/// Alert rule with the outcome of its latest evaluation
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct AlertRule {
+    pub id: i32,
+    pub name: String,
+    #[sqlx(try_from = "String")]
+    pub metric: AlertMetric,
+    /// Service ID for `service_down`, path for `disk_usage` (default `/`)
+    pub target: Option<String>,
+    #[sqlx(try_from = "String")]
+    pub operator: Comparison,
+    pub threshold: f64,
+    /// Look-back window of `crawl_failure_rate` and `articles_ingested`
+    pub window_minutes: i32,
+    /// `info`, `warning` or `critical`
+    pub severity: String,
+    pub enabled: bool,
+    /// `ok` or `firing`
+    pub state: String,
+    pub last_value: Option<f64>,
+    /// Why the latest evaluation produced no value; the state is kept
+    pub last_error: Option<String>,
+    pub last_evaluated_at: Option<DateTime<Utc>>,
+    /// Start of the current or most recent firing period
+    pub fired_at: Option<DateTime<Utc>>,
+    pub resolved_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Request body for creating or replacing an alert rule
+#[derive(Debug, Deserialize, Validate)]
+pub struct AlertRuleRequest {
+    #[validate(length(min = 1, max = 200))]
+    pub name: String,
+
+    pub metric: AlertMetric,
+
+    #[validate(length(min = 1, max = 255))]
+    pub target: Option<String>,
+
+    pub operator: Comparison,
+
+    pub threshold: f64,
+
+    #[serde(default = "default_window_minutes")]
+    #[validate(range(min = 1, max = 10080))]
+    pub window_minutes: i32,
+
+    #[serde(default = "default_severity")]
+    #[validate(custom = "validate_severity")]
+    pub severity: String,
+
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_window_minutes() -> i32 {
+    60
+}
+
+fn default_severity() -> String {
+    "warning".to_string()
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn validate_severity(severity: &str) -> Result<(), ValidationError> {
+    match severity {
+        "info" | "warning" | "critical" => Ok(()),
+        _ => Err(ValidationError::new("severity")),
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AlertRuleListResponse {
+    pub rules: Vec<AlertRule>,
+    pub total: i64,
+}
+
+/// Transition of a rule between `ok` and `firing`
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct AlertEvent {
+    pub id: i64,
+    pub rule_id: i32,
+    pub rule_name: String,
+    pub severity: String,
+    /// `firing` or `resolved`
+    pub state: String,
+    pub value: Option<f64>,
+    pub message: String,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Response for /api/admin/alerts
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AlertListResponse {
+    /// Enabled rules, firing ones first
+    pub alerts: Vec<AlertRule>,
+    pub firing: i64,
+    pub last_evaluated_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AlertEventListResponse {
+    pub events: Vec<AlertEvent>,
+    pub total: i64,
+}
@@ -0,0 +1,70 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use validator::{Validate, ValidationError};
+
+/// Read articles, statistics and categories
+pub const SCOPE_NEWS_READ: &str = "news:read";
+/// Report crawler runs to /api/ingest
+pub const SCOPE_INGEST_WRITE: &str = "ingest:write";
+/// Read-only access to /api/admin
+pub const SCOPE_ADMIN_READ: &str = "admin:read";
+
+/// Scopes an API key can be granted
+pub const API_KEY_SCOPES: &[&str] = &[SCOPE_NEWS_READ, SCOPE_INGEST_WRITE, SCOPE_ADMIN_READ];
+
+/// Machine credential; only a hash of the secret is stored
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ApiKey {
+    pub id: i32,
+    pub name: String,
+    /// Start of the secret, enough to recognise it in logs and configs
+    pub prefix: String,
+    pub scopes: Vec<String>,
+    /// Requests per minute allowed for this key; none means only the route limits apply
+    pub rate_limit_per_minute: Option<i32>,
+    pub expires_at: Option<DateTime<Utc>>,
+    pub last_used_at: Option<DateTime<Utc>>,
+    /// Username of the admin who created the key
+    pub created_by: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub revoked_at: Option<DateTime<Utc>>,
+}
+
+/// Request body for creating an API key
+#[derive(Debug, Deserialize, Validate)]
+pub struct ApiKeyRequest {
+    #[validate(length(min = 1, max = 200))]
+    pub name: String,
+
+    #[validate(length(min = 1), custom = "validate_scopes")]
+    pub scopes: Vec<String>,
+
+    #[validate(range(min = 1, max = 1_000_000))]
+    pub rate_limit_per_minute: Option<i32>,
+
+    /// Never expires when omitted
+    pub expires_at: Option<DateTime<Utc>>,
+}
+
+fn validate_scopes(scopes: &[String]) -> Result<(), ValidationError> {
+    if scopes.iter().all(|s| API_KEY_SCOPES.contains(&s.as_str())) {
+        Ok(())
+    } else {
+        Err(ValidationError::new("scope"))
+    }
+}
+
+/// A newly created key with its secret, returned only once
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CreatedApiKey {
+    #[serde(flatten)]
+    pub api_key: ApiKey,
+    pub key: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ApiKeyListResponse {
+    pub api_keys: Vec<ApiKey>,
+    pub total: i64,
+}
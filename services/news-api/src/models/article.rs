@@ -20,6 +20,10 @@ pub struct Article {
     #[serde(skip_serializing_if = "Option::is_none")]
     pub source: Option<String>,
 
+    /// ID of the registered source in the `sources` table
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub source_id: Option<i32>,
+
     #[serde(skip_serializing_if = "Option::is_none")]
     pub category: Option<String>,
 
@@ -36,7 +40,7 @@ pub struct Article {
     pub crawled_at: Option<DateTime<Utc>>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ArticleListResponse {
     pub articles: Vec<Article>,
     pub total: i64,
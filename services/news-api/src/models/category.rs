@@ -1,15 +1,78 @@
+use chrono::{DateTime, Utc};
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
+use validator::{Validate, ValidationError};
 
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
 pub struct Category {
     pub id: i32,
     pub name: String,
+    pub parent_id: Option<i32>,
     pub article_count: i32,
+    pub last_synced_at: Option<DateTime<Utc>>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+/// A category with its aliases and child categories
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CategoryNode {
+    pub id: i32,
+    pub name: String,
+    pub parent_id: Option<i32>,
+    pub aliases: Vec<String>,
+    /// Articles labeled with this category or one of its aliases
+    pub article_count: i32,
+    /// `article_count` plus the counts of all descendants
+    pub total_count: i64,
+    pub last_synced_at: Option<DateTime<Utc>>,
+    pub children: Vec<CategoryNode>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct CategoryListResponse {
-    pub categories: Vec<Category>,
+    /// Root categories; children are nested
+    pub categories: Vec<CategoryNode>,
     pub total: i64,
 }
+
+/// Request body for placing a category in the hierarchy and setting its aliases
+#[derive(Debug, Deserialize, Validate)]
+pub struct CategoryUpdateRequest {
+    pub parent_id: Option<i32>,
+    #[serde(default)]
+    #[validate(custom = "validate_aliases")]
+    pub aliases: Vec<String>,
+}
+
+fn validate_aliases(aliases: &[String]) -> Result<(), ValidationError> {
+    if aliases.iter().all(|a| !a.trim().is_empty() && a.chars().count() <= 100) {
+        Ok(())
+    } else {
+        Err(ValidationError::new("alias_length"))
+    }
+}
+
+/// Result of recomputing category counts from the search index
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CategorySyncReport {
+    pub synced_at: DateTime<Utc>,
+    pub inserted: i64,
+    pub updated: i64,
+    /// Categories present in Postgres but no longer in the index
+    pub zeroed: i64,
+}
+
+/// Count difference for one category between Postgres and the search index
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CategoryDrift {
+    pub name: String,
+    pub db_count: Option<i32>,
+    pub index_count: Option<i64>,
+    pub difference: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CategoryDriftResponse {
+    pub in_sync: bool,
+    pub last_synced_at: Option<DateTime<Utc>>,
+    pub drift: Vec<CategoryDrift>,
+}
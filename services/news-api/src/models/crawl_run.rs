@@ -0,0 +1,66 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use validator::{Validate, ValidationError};
+
+/// Maximum number of error samples stored per run
+pub const MAX_ERROR_SAMPLES: usize = 20;
+
+/// Bytes kept of each error sample
+pub const MAX_ERROR_SAMPLE_BYTES: usize = 1024;
+
+/// One crawler run against a single source
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct CrawlRun {
+    pub id: i64,
+    pub source_id: Option<i32>,
+    pub source_name: Option<String>,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+    pub pages_fetched: i32,
+    pub articles_new: i32,
+    pub articles_updated: i32,
+    pub articles_failed: i32,
+    pub error_samples: Vec<String>,
+    pub created_at: DateTime<Utc>,
+}
+
+/// Run report posted by a crawler
+#[derive(Debug, Deserialize, Validate)]
+#[validate(schema(function = "validate_run_window"))]
+pub struct CrawlRunRequest {
+    pub source_id: Option<i32>,
+    pub started_at: DateTime<Utc>,
+    pub finished_at: Option<DateTime<Utc>>,
+    #[serde(default)]
+    #[validate(range(min = 0))]
+    pub pages_fetched: i32,
+    #[serde(default)]
+    #[validate(range(min = 0))]
+    pub articles_new: i32,
+    #[serde(default)]
+    #[validate(range(min = 0))]
+    pub articles_updated: i32,
+    #[serde(default)]
+    #[validate(range(min = 0))]
+    pub articles_failed: i32,
+    #[serde(default)]
+    pub error_samples: Vec<String>,
+}
+
+fn validate_run_window(req: &CrawlRunRequest) -> Result<(), ValidationError> {
+    match req.finished_at {
+        Some(finished_at) if finished_at < req.started_at => {
+            Err(ValidationError::new("finished_at_before_started_at"))
+        }
+        _ => Ok(()),
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct CrawlRunListResponse {
+    pub runs: Vec<CrawlRun>,
+    pub total: i64,
+    pub page: i64,
+    pub limit: i64,
+}
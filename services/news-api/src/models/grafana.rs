@@ -0,0 +1,31 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use super::HealthStatus;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct GrafanaDashboard {
+    pub uid: String,
+    pub title: String,
+    /// Kiosk-mode link for an iframe, with the requested time range
+    pub url: String,
+    pub tags: Vec<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub folder: Option<String>,
+}
+
+/// Response for /api/admin/grafana/dashboards
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct GrafanaCatalogResponse {
+    /// `healthy` when Grafana answered, `degraded` when serving a stale catalog,
+    /// `down` when there is nothing to serve
+    pub status: HealthStatus,
+    pub dashboards: Vec<GrafanaDashboard>,
+    /// When the dashboards were fetched from Grafana
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub fetched_at: Option<DateTime<Utc>>,
+    /// Why Grafana could not be queried
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
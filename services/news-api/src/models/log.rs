@@ -0,0 +1,92 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::{Map, Value};
+use sqlx::FromRow;
+use std::str::FromStr;
+
+/// Severity of a log entry, ordered from least to most severe
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogLevel {
+    Trace,
+    Debug,
+    Info,
+    #[serde(alias = "warning")]
+    Warn,
+    Error,
+}
+
+impl LogLevel {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Trace => "trace",
+            Self::Debug => "debug",
+            Self::Info => "info",
+            Self::Warn => "warn",
+            Self::Error => "error",
+        }
+    }
+
+    /// This level and every more severe one
+    pub fn and_above(self) -> Vec<&'static str> {
+        [Self::Trace, Self::Debug, Self::Info, Self::Warn, Self::Error]
+            .into_iter()
+            .filter(|l| *l >= self)
+            .map(Self::as_str)
+            .collect()
+    }
+}
+
+impl From<tracing::Level> for LogLevel {
+    fn from(level: tracing::Level) -> Self {
+        match level {
+            tracing::Level::TRACE => Self::Trace,
+            tracing::Level::DEBUG => Self::Debug,
+            tracing::Level::INFO => Self::Info,
+            tracing::Level::WARN => Self::Warn,
+            _ => Self::Error,
+        }
+    }
+}
+
+impl FromStr for LogLevel {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.to_ascii_lowercase().as_str() {
+            "trace" => Ok(Self::Trace),
+            "debug" => Ok(Self::Debug),
+            "info" => Ok(Self::Info),
+            "warn" | "warning" => Ok(Self::Warn),
+            "error" => Ok(Self::Error),
+            other => Err(format!("unknown log level {other:?}")),
+        }
+    }
+}
+
+impl TryFrom<String> for LogLevel {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, String> {
+        value.parse()
+    }
+}
+
+/// Captured tracing event, as returned by /api/admin/logs
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct LogEntry {
+    /// Sequence number within the store the entry was read from
+    pub id: i64,
+    #[sqlx(rename = "logged_at")]
+    pub timestamp: DateTime<Utc>,
+    #[sqlx(try_from = "String")]
+    pub level: LogLevel,
+    pub service: String,
+    /// Module path of the code that logged the event
+    pub target: String,
+    pub message: String,
+    /// Event fields and the fields of the spans it was logged in
+    #[serde(default, skip_serializing_if = "Map::is_empty")]
+    #[sqlx(json)]
+    pub metadata: Map<String, Value>,
+}
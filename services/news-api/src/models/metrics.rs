@@ -0,0 +1,26 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+
+/// One series of /api/admin/metrics: the latest sample plus its history
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetricData {
+    /// Stable identifier of the curated query, e.g. `api_latency_p95`
+    pub id: String,
+    pub name: String,
+    /// Latest sample
+    pub value: f64,
+    pub unit: String,
+    /// Time of the latest sample
+    pub timestamp: DateTime<Utc>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub labels: BTreeMap<String, String>,
+    /// Oldest first
+    pub points: Vec<MetricPoint>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MetricPoint {
+    pub timestamp: DateTime<Utc>,
+    pub value: f64,
+}
@@ -1,10 +1,51 @@
+pub mod alert;
+pub mod api_key;
 pub mod article;
 pub mod category;
+pub mod crawl_run;
+pub mod grafana;
+pub mod log;
+pub mod metrics;
+pub mod service_action;
+pub mod source;
 pub mod stats;
+pub mod system;
+pub mod user;
+pub mod webhook;
 
+pub use alert::{
+    AlertEvent, AlertEventListResponse, AlertListResponse, AlertMetric, AlertRule,
+    AlertRuleListResponse, AlertRuleRequest,
+};
+pub use api_key::{
+    ApiKey, ApiKeyListResponse, ApiKeyRequest, CreatedApiKey, SCOPE_ADMIN_READ,
+    SCOPE_INGEST_WRITE, SCOPE_NEWS_READ,
+};
 pub use article::{Article, ArticleListResponse};
-pub use category::{Category, CategoryListResponse};
+pub use category::{
+    Category, CategoryDrift, CategoryDriftResponse, CategoryListResponse, CategoryNode,
+    CategorySyncReport, CategoryUpdateRequest,
+};
+pub use crawl_run::{
+    CrawlRun, CrawlRunListResponse, CrawlRunRequest, MAX_ERROR_SAMPLES, MAX_ERROR_SAMPLE_BYTES,
+};
+pub use grafana::{GrafanaCatalogResponse, GrafanaDashboard};
+pub use log::{LogEntry, LogLevel};
+pub use metrics::{MetricData, MetricPoint};
+pub use service_action::{ServiceAction, ServiceActionListResponse};
+pub use source::{Source, SourceListResponse, SourceRequest};
 pub use stats::{
-    CrawlStats, DailyCrawlStats, DailyCount, DailyStatsResponse, HourlyCount, StatsResponse,
-    SystemStatusResponse,
+    BreakdownResponse, BreakdownSeries, DailyCrawlStats, DailyCount, DailyStatsResponse,
+    HourlyCount, StatsResponse, SystemStatusResponse, TimeSeriesPoint,
+};
+pub use system::{
+    AdminStatusResponse, CpuMetrics, FilesystemMetrics, HealthStatus, ProbeSample, ResourceUsage,
+    ServiceDetail, ServiceHealth, ServiceInfo, ServiceKind, ServiceState, SystemMetrics,
+};
+pub use user::{
+    LoginRequest, RefreshRequest, StreamTicketResponse, TokenResponse, User, UserRole,
+};
+pub use webhook::{
+    Webhook, WebhookDelivery, WebhookDeliveryListResponse, WebhookFormat, WebhookListResponse,
+    WebhookRequest,
 };
@@ -0,0 +1,27 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+
+/// Audit record of a container start, stop or restart request
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct ServiceAction {
+    pub id: i64,
+    pub service_id: String,
+    pub container: Option<String>,
+    /// `start`, `stop` or `restart`
+    pub action: String,
+    /// `succeeded`, `unchanged` (already in that state), `failed` or `denied`
+    pub outcome: String,
+    pub message: Option<String>,
+    pub client_addr: Option<String>,
+    /// Username of the caller; empty for actions recorded before sign-in existed
+    pub actor: Option<String>,
+    pub duration_ms: i32,
+    pub created_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct ServiceActionListResponse {
+    pub actions: Vec<ServiceAction>,
+    pub total: i64,
+}
@@ -0,0 +1,73 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use validator::{Validate, ValidationError};
+
+/// A news source (publisher) registered for crawling
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Source {
+    pub id: i32,
+    pub name: String,
+    pub homepage: Option<String>,
+    pub feed_urls: Vec<String>,
+    pub language: String,
+    pub default_category: Option<String>,
+    pub crawl_interval_minutes: i32,
+    pub enabled: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Request body for creating or replacing a source
+#[derive(Debug, Deserialize, Validate)]
+pub struct SourceRequest {
+    #[validate(length(min = 1, max = 200))]
+    pub name: String,
+
+    #[validate(url)]
+    pub homepage: Option<String>,
+
+    #[serde(default)]
+    #[validate(custom = "validate_feed_urls")]
+    pub feed_urls: Vec<String>,
+
+    #[serde(default = "default_language")]
+    #[validate(length(min = 2, max = 10))]
+    pub language: String,
+
+    #[validate(length(min = 1, max = 100))]
+    pub default_category: Option<String>,
+
+    #[serde(default = "default_crawl_interval")]
+    #[validate(range(min = 1, max = 10080))]
+    pub crawl_interval_minutes: i32,
+
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_language() -> String {
+    "ko".to_string()
+}
+
+fn default_crawl_interval() -> i32 {
+    60
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn validate_feed_urls(urls: &[String]) -> Result<(), ValidationError> {
+    if urls.iter().all(validator::validate_url) {
+        Ok(())
+    } else {
+        Err(ValidationError::new("url"))
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct SourceListResponse {
+    pub sources: Vec<Source>,
+    pub total: i64,
+}
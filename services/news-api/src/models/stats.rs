@@ -1,19 +1,9 @@
-use chrono::{DateTime, NaiveDate, Utc};
+use chrono::NaiveDate;
 use serde::{Deserialize, Serialize};
 use sqlx::FromRow;
 use std::collections::HashMap;
 
 #[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
-pub struct CrawlStats {
-    pub id: i32,
-    pub date: NaiveDate,
-    pub total_crawled: i32,
-    pub success_count: i32,
-    pub failed_count: i32,
-    pub created_at: DateTime<Utc>,
-}
-
-#[derive(Debug, Serialize, Deserialize)]
 pub struct DailyCrawlStats {
     pub date: NaiveDate,
     pub total_crawled: i32,
@@ -22,32 +12,71 @@ pub struct DailyCrawlStats {
 }
 
 /// Dashboard-compatible stats response
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct StatsResponse {
     pub total_articles: i64,
     pub today_articles: i64,
+    /// Articles crawled today according to `crawl_stats`, failures included
+    pub total_crawled_today: i64,
+    /// Percentage of successfully crawled articles over the last 30 days
+    pub success_rate: f64,
     pub categories: HashMap<String, i64>,
     pub publishers: HashMap<String, i64>,
     pub hourly_counts: Vec<HourlyCount>,
     pub daily_counts: Vec<DailyCount>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct HourlyCount {
     pub hour: String,
     pub count: i64,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DailyCount {
     pub date: String,
     pub count: i64,
 }
 
+/// Per-key time series response for /api/stats/breakdown
 #[derive(Debug, Serialize, Deserialize)]
+pub struct BreakdownResponse {
+    pub by: String,
+    pub interval: String,
+    pub days: i64,
+    pub series: Vec<BreakdownSeries>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct BreakdownSeries {
+    /// Category name, or the source ID for `by=source`
+    pub key: String,
+    /// Registered name of the source for `by=source`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub name: Option<String>,
+    pub total: i64,
+    pub points: Vec<TimeSeriesPoint>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TimeSeriesPoint {
+    pub timestamp: String,
+    pub count: i64,
+}
+
+/// Daily crawl stats for a date window, newest first, with window totals
+#[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct DailyStatsResponse {
     pub stats: Vec<DailyCrawlStats>,
     pub total_days: i64,
+    pub from: NaiveDate,
+    pub to: NaiveDate,
+    pub total_crawled: i64,
+    pub success_count: i64,
+    pub failed_count: i64,
+    /// Percentage of successfully crawled articles in the window
+    pub success_rate: f64,
+    pub total_crawled_today: i64,
 }
 
 /// System status response for /api/status
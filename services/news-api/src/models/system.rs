@@ -0,0 +1,137 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+
+use crate::config::ProbeConfig;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HealthStatus {
+    Healthy,
+    Degraded,
+    Down,
+}
+
+/// Result of probing one service
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServiceHealth {
+    pub name: String,
+    pub status: HealthStatus,
+    /// Seconds the service has been up, 0 if it does not report it
+    pub uptime: i64,
+    pub last_check: DateTime<Utc>,
+    pub latency_ms: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CpuMetrics {
+    /// Busy percentage across all cores
+    pub usage: f64,
+    pub cores: usize,
+}
+
+/// Used and total in GB, as the dashboard displays them
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ResourceUsage {
+    pub used: f64,
+    pub total: f64,
+    pub percentage: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FilesystemMetrics {
+    pub mount_point: String,
+    pub device: String,
+    pub fs_type: String,
+    pub used: f64,
+    pub total: f64,
+    pub percentage: f64,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SystemMetrics {
+    pub cpu: CpuMetrics,
+    pub memory: ResourceUsage,
+    /// Filesystem holding `/`
+    pub disk: ResourceUsage,
+    /// Every mounted filesystem that stores data
+    pub filesystems: Vec<FilesystemMetrics>,
+}
+
+/// System status response for /api/admin/status
+#[derive(Debug, Serialize, Deserialize)]
+pub struct AdminStatusResponse {
+    pub overall: HealthStatus,
+    pub services: Vec<ServiceHealth>,
+    pub metrics: SystemMetrics,
+    pub timestamp: DateTime<Utc>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ServiceKind {
+    Api,
+    Worker,
+    Database,
+    Cache,
+    Monitoring,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ServiceState {
+    Running,
+    Stopped,
+    Restarting,
+}
+
+/// Registered service as listed by /api/admin/services
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServiceInfo {
+    pub id: String,
+    pub name: String,
+    pub status: ServiceState,
+    #[serde(rename = "type")]
+    pub kind: ServiceKind,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub port: Option<u16>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub replicas: Option<u32>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub version: Option<String>,
+    /// Derived from the reported uptime, or the first successful probe after an outage
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub started_at: Option<DateTime<Utc>>,
+    /// Latest probe result; absent until the service has been probed
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub health: Option<ServiceHealth>,
+}
+
+/// One probe result in a service's history
+#[derive(Debug, Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ProbeSample {
+    pub timestamp: DateTime<Utc>,
+    pub status: HealthStatus,
+    pub latency_ms: u64,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub message: Option<String>,
+}
+
+/// Service detail response for /api/admin/services/:id
+#[derive(Debug, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ServiceDetail {
+    #[serde(flatten)]
+    pub service: ServiceInfo,
+    /// Probe configuration with secrets redacted
+    pub probe: ProbeConfig,
+    /// Share of healthy or degraded probes in `history`
+    pub availability: f64,
+    /// Oldest first
+    pub history: Vec<ProbeSample>,
+}
@@ -0,0 +1,96 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::FromRow;
+use std::str::FromStr;
+use validator::Validate;
+
+/// What a signed-in user may do in the admin API
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum UserRole {
+    /// Full access
+    Admin,
+    /// Read-only access
+    Viewer,
+}
+
+impl UserRole {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Admin => "admin",
+            Self::Viewer => "viewer",
+        }
+    }
+}
+
+impl FromStr for UserRole {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "admin" => Ok(Self::Admin),
+            "viewer" => Ok(Self::Viewer),
+            other => Err(format!("unknown role {other:?}")),
+        }
+    }
+}
+
+impl TryFrom<String> for UserRole {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, String> {
+        value.parse()
+    }
+}
+
+/// Admin API account; the password hash never leaves the database layer
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct User {
+    pub id: i32,
+    pub username: String,
+    #[sqlx(try_from = "String")]
+    pub role: UserRole,
+    pub enabled: bool,
+    pub last_login_at: Option<DateTime<Utc>>,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+#[derive(Debug, Deserialize, Validate)]
+pub struct LoginRequest {
+    #[validate(length(min = 1, max = 100))]
+    pub username: String,
+
+    #[validate(length(min = 1, max = 1024))]
+    pub password: String,
+}
+
+/// Body of refresh and logout requests
+#[derive(Debug, Deserialize, Validate)]
+pub struct RefreshRequest {
+    #[validate(length(min = 1, max = 256))]
+    pub refresh_token: String,
+}
+
+/// Access and refresh token pair issued on login and refresh
+#[derive(Debug, Serialize, Deserialize)]
+pub struct TokenResponse {
+    pub access_token: String,
+    /// Always `Bearer`
+    pub token_type: String,
+    /// Seconds until the access token expires
+    pub expires_in: u64,
+    /// Single use; exchanging it at /api/auth/refresh revokes it
+    pub refresh_token: String,
+    pub refresh_expires_in: u64,
+    pub user: User,
+}
+
+/// Short-lived token that only opens /api/admin/logs/stream
+#[derive(Debug, Serialize, Deserialize)]
+pub struct StreamTicketResponse {
+    /// Passed as `?ticket=`, since `EventSource` cannot send headers
+    pub ticket: String,
+    /// Seconds the ticket can be used to connect
+    pub expires_in: u64,
+}
@@ -0,0 +1,138 @@
+use chrono::{DateTime, Utc};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use sqlx::FromRow;
+use std::str::FromStr;
+use validator::{Validate, ValidationError};
+
+/// Events a webhook can subscribe to
+pub const WEBHOOK_EVENTS: &[&str] = &["alert.firing", "alert.resolved"];
+
+/// Payload layout expected by the receiver
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum WebhookFormat {
+    Slack,
+    Discord,
+    #[default]
+    Json,
+}
+
+impl WebhookFormat {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::Slack => "slack",
+            Self::Discord => "discord",
+            Self::Json => "json",
+        }
+    }
+}
+
+impl FromStr for WebhookFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "slack" => Ok(Self::Slack),
+            "discord" => Ok(Self::Discord),
+            "json" => Ok(Self::Json),
+            other => Err(format!("unknown webhook format {other:?}")),
+        }
+    }
+}
+
+impl TryFrom<String> for WebhookFormat {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, String> {
+        value.parse()
+    }
+}
+
+/// Webhook receiver; the secret is write-only
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct Webhook {
+    pub id: i32,
+    pub name: String,
+    pub url: String,
+    #[sqlx(try_from = "String")]
+    pub format: WebhookFormat,
+    /// Whether deliveries carry an HMAC signature
+    pub signed: bool,
+    pub events: Vec<String>,
+    pub enabled: bool,
+    pub created_at: DateTime<Utc>,
+    pub updated_at: DateTime<Utc>,
+}
+
+/// Request body for creating or replacing a webhook
+#[derive(Debug, Deserialize, Validate)]
+pub struct WebhookRequest {
+    #[validate(length(min = 1, max = 200))]
+    pub name: String,
+
+    #[validate(url)]
+    pub url: String,
+
+    #[serde(default)]
+    pub format: WebhookFormat,
+
+    /// HMAC key; on update, omitted keeps the current one and `""` removes it
+    #[validate(length(max = 512))]
+    pub secret: Option<String>,
+
+    #[serde(default = "default_events")]
+    #[validate(custom = "validate_events")]
+    pub events: Vec<String>,
+
+    #[serde(default = "default_enabled")]
+    pub enabled: bool,
+}
+
+fn default_events() -> Vec<String> {
+    WEBHOOK_EVENTS.iter().map(|e| e.to_string()).collect()
+}
+
+fn default_enabled() -> bool {
+    true
+}
+
+fn validate_events(events: &[String]) -> Result<(), ValidationError> {
+    if events.iter().all(|e| WEBHOOK_EVENTS.contains(&e.as_str())) {
+        Ok(())
+    } else {
+        Err(ValidationError::new("event"))
+    }
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WebhookListResponse {
+    pub webhooks: Vec<Webhook>,
+    pub total: i64,
+}
+
+/// One notification sent, or being retried, to one webhook
+#[derive(Debug, Clone, Serialize, Deserialize, FromRow)]
+pub struct WebhookDelivery {
+    pub id: i64,
+    pub webhook_id: i32,
+    pub event: String,
+    /// Body as posted to the receiver
+    pub payload: Value,
+    /// `pending`, `succeeded` or `failed`
+    pub status: String,
+    pub attempts: i32,
+    /// When a pending delivery is tried next
+    pub next_attempt_at: Option<DateTime<Utc>>,
+    /// HTTP status of the latest attempt, if the receiver answered
+    pub response_status: Option<i32>,
+    pub last_error: Option<String>,
+    pub created_at: DateTime<Utc>,
+    pub delivered_at: Option<DateTime<Utc>>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+pub struct WebhookDeliveryListResponse {
+    pub deliveries: Vec<WebhookDelivery>,
+    pub total: i64,
+}
@@ -0,0 +1,526 @@
+//! Client for the Prometheus HTTP API behind /api/admin/metrics.
+//!
+//! The admin dashboard gets a curated set of range queries over the API's own
+//! metrics. Raw range queries are passed through only when every metric they
+//! select matches `prometheus.allowed_metrics`, so the dashboard cannot read
+//! series of unrelated jobs scraped by the same server.
+
+use chrono::{DateTime, TimeZone, Utc};
+use reqwest::Client;
+use serde::Deserialize;
+use serde_json::Value;
+use std::collections::BTreeMap;
+
+use crate::config::PrometheusConfig;
+use crate::error::{ApiError, ApiResult};
+
+/// Most points Prometheus returns for one series
+pub const MAX_POINTS: i64 = 11_000;
+
+/// A query shown on the admin Metrics page
+pub struct CuratedQuery {
+    pub id: &'static str,
+    pub name: &'static str,
+    pub unit: &'static str,
+    pub query: &'static str,
+}
+
+pub const CURATED_QUERIES: &[CuratedQuery] = &[
+    CuratedQuery {
+        id: "api_request_rate",
+        name: "API request rate",
+        unit: "req/s",
+        query: "sum(rate(http_requests_total[5m]))",
+    },
+    CuratedQuery {
+        id: "api_latency_p95",
+        name: "API p95 latency",
+        unit: "ms",
+        query: "histogram_quantile(0.95, \
+                sum by (le) (rate(http_request_duration_seconds_bucket[5m]))) * 1000",
+    },
+    CuratedQuery {
+        id: "api_error_rate",
+        name: "API error rate",
+        unit: "%",
+        query: "sum(rate(http_requests_total{status=~\"5..\"}[5m])) \
+                / sum(rate(http_requests_total[5m])) * 100",
+    },
+    CuratedQuery {
+        id: "crawl_rate",
+        name: "Crawl rate",
+        unit: "articles/h",
+        query: "clamp_min(deriv(news_articles_total[15m]) * 3600, 0)",
+    },
+    CuratedQuery {
+        id: "opensearch_latency_p95",
+        name: "OpenSearch p95 latency",
+        unit: "ms",
+        query: "histogram_quantile(0.95, \
+                sum by (le) (rate(opensearch_request_duration_seconds_bucket[5m]))) * 1000",
+    },
+    CuratedQuery {
+        id: "opensearch_error_rate",
+        name: "OpenSearch errors",
+        unit: "errors/s",
+        query: "sum(rate(opensearch_request_errors_total[5m]))",
+    },
+    CuratedQuery {
+        id: "db_connections_in_use",
+        name: "DB connections in use",
+        unit: "connections",
+        query: "sum(db_pool_connections{state=\"in_use\"})",
+    },
+];
+
+/// Time range and resolution of a range query
+#[derive(Debug, Clone, Copy)]
+pub struct Range {
+    pub start: DateTime<Utc>,
+    pub end: DateTime<Utc>,
+    pub step_secs: i64,
+}
+
+/// One series of a range query result
+#[derive(Debug)]
+pub struct RangeSeries {
+    pub labels: BTreeMap<String, String>,
+    /// Finite samples, oldest first
+    pub points: Vec<(DateTime<Utc>, f64)>,
+}
+
+#[derive(Clone)]
+pub struct PrometheusClient {
+    client: Client,
+    base_url: String,
+}
+
+#[derive(Deserialize)]
+struct ApiResponse {
+    status: String,
+    #[serde(default)]
+    data: Value,
+    error: Option<String>,
+}
+
+#[derive(Deserialize)]
+struct MatrixData {
+    result: Vec<MatrixSeries>,
+}
+
+#[derive(Deserialize)]
+struct MatrixSeries {
+    metric: BTreeMap<String, String>,
+    values: Vec<(f64, String)>,
+}
+
+impl PrometheusClient {
+    pub fn new(config: &PrometheusConfig) -> Self {
+        let client = Client::builder()
+            .timeout(config.timeout())
+            .build()
+            .unwrap_or_default();
+
+        Self {
+            client,
+            base_url: config.url.trim_end_matches('/').to_string(),
+        }
+    }
+
+    /// Run a range query and parse the matrix it returns
+    pub async fn query_range(&self, query: &str, range: Range) -> ApiResult<Vec<RangeSeries>> {
+        let data = self.query_range_raw(query, range).await?;
+        let matrix: MatrixData = serde_json::from_value(data).map_err(|e| {
+            ApiError::Prometheus(format!("Unexpected query_range response: {e}"))
+        })?;
+
+        Ok(matrix
+            .result
+            .into_iter()
+            .map(|series| RangeSeries {
+                labels: series.metric,
+                points: series
+                    .values
+                    .into_iter()
+                    .filter_map(|(time, value)| {
+                        let value: f64 = value.parse().ok().filter(|v: &f64| v.is_finite())?;
+                        let millis = (time * 1000.0) as i64;
+                        Some((Utc.timestamp_millis_opt(millis).single()?, value))
+                    })
+                    .collect(),
+            })
+            .collect())
+    }
+
+    /// Run a range query and return the `data` object unchanged
+    pub async fn query_range_raw(&self, query: &str, range: Range) -> ApiResult<Value> {
+        let url = format!("{}/api/v1/query_range", self.base_url);
+
+        let response = self
+            .client
+            .get(&url)
+            .query(&[
+                ("query", query.to_string()),
+                ("start", range.start.timestamp().to_string()),
+                ("end", range.end.timestamp().to_string()),
+                ("step", range.step_secs.to_string()),
+            ])
+            .send()
+            .await
+            .map_err(|e| ApiError::Prometheus(format!("Failed to reach Prometheus: {e}")))?;
+
+        let status = response.status();
+        let body: ApiResponse = response.json().await.map_err(|e| {
+            ApiError::Prometheus(format!("Failed to parse Prometheus response ({status}): {e}"))
+        })?;
+
+        if body.status != "success" {
+            let message = body.error.unwrap_or_else(|| format!("status {status}"));
+            // Prometheus rejects invalid expressions with 400 or 422
+            return Err(if status.is_client_error() {
+                ApiError::BadRequest(format!("Prometheus rejected the query: {message}"))
+            } else {
+                ApiError::Prometheus(format!("Query failed: {message}"))
+            });
+        }
+
+        Ok(body.data)
+    }
+}
+
+/// Check that every metric a PromQL expression selects is allowlisted
+pub fn check_query(query: &str, allowed_prefixes: &[String]) -> Result<(), String> {
+    if query.contains("__name__") {
+        return Err("Selecting metrics by __name__ is not allowed".to_string());
+    }
+
+    for name in selected_metrics(query)? {
+        if !allowed_prefixes.iter().any(|p| name.starts_with(p.as_str())) {
+            return Err(format!("Metric {name} is not in prometheus.allowed_metrics"));
+        }
+    }
+    Ok(())
+}
+
+/// Words that look like metric names but are always PromQL syntax
+const KEYWORDS: &[&str] = &[
+    "and", "or", "unless", "bool", "offset", "group_left", "group_right", "inf", "nan",
+];
+
+/// Aggregation operators, which are only syntax when an argument list or a
+/// `by`/`without` clause follows; otherwise they are read as metric names
+const AGGREGATIONS: &[&str] = &[
+    "sum", "min", "max", "avg", "group", "stddev", "stdvar", "count", "count_values", "bottomk",
+    "topk", "quantile", "limitk", "limit_ratio",
+];
+
+/// Keywords followed by a parenthesised list of label names
+const LABEL_LIST_KEYWORDS: &[&str] =
+    &["by", "without", "on", "ignoring", "group_left", "group_right"];
+
+/// Metric names in a PromQL expression
+///
+/// A lexical scan, not a parser: identifiers followed by `(` are functions,
+/// label matchers, strings and durations are skipped, and a bare `{...}`
+/// selector without a metric name is rejected. Keywords that can also be
+/// metric names (`count`, `by`, `on`, ...) only count as syntax in the position
+/// PromQL expects them, so they cannot smuggle a series past the allowlist.
+fn selected_metrics(query: &str) -> Result<Vec<&str>, String> {
+    let bytes = query.as_bytes();
+    let mut names = Vec::new();
+    let mut after_metric = false;
+    let mut i = 0;
+
+    while i < bytes.len() {
+        let c = bytes[i];
+        match c {
+            b'"' | b'\'' | b'`' => {
+                i = skip_string(bytes, i);
+                after_metric = false;
+            }
+            b'{' => {
+                if !after_metric {
+                    return Err("Selectors must name a metric".to_string());
+                }
+                i = skip_until(bytes, i, b'}');
+                after_metric = false;
+            }
+            b'[' => {
+                i = skip_until(bytes, i, b']');
+            }
+            b'0'..=b'9' | b'.' => {
+                while i < bytes.len() && (bytes[i].is_ascii_alphanumeric() || bytes[i] == b'.') {
+                    i += 1;
+                }
+                after_metric = false;
+            }
+            c if c.is_ascii_alphabetic() || c == b'_' || c == b':' => {
+                let start = i;
+                while i < bytes.len()
+                    && (bytes[i].is_ascii_alphanumeric() || bytes[i] == b'_' || bytes[i] == b':')
+                {
+                    i += 1;
+                }
+                let word = &query[start..i];
+                let rest = query[i..].trim_start();
+                let next = rest.as_bytes().first().copied();
+
+                if LABEL_LIST_KEYWORDS.contains(&word) && next == Some(b'(') {
+                    // `rest` starts at the opening parenthesis
+                    i = skip_until(bytes, query.len() - rest.len(), b')');
+                    after_metric = false;
+                } else if next == Some(b'(')
+                    || KEYWORDS.contains(&word)
+                    || (AGGREGATIONS.contains(&word)
+                        && matches!(leading_word(rest), "by" | "without"))
+                {
+                    after_metric = false;
+                } else {
+                    names.push(word);
+                    after_metric = true;
+                }
+            }
+            c if c.is_ascii_whitespace() => i += 1,
+            _ => {
+                i += 1;
+                after_metric = false;
+            }
+        }
+    }
+
+    Ok(names)
+}
+
+/// The identifier `s` starts with, if any
+fn leading_word(s: &str) -> &str {
+    let end = s
+        .find(|c: char| !(c.is_ascii_alphanumeric() || c == '_' || c == ':'))
+        .unwrap_or(s.len());
+    &s[..end]
+}
+
+/// Index just past the closing quote of the string starting at `start`
+fn skip_string(bytes: &[u8], start: usize) -> usize {
+    let quote = bytes[start];
+    let mut i = start + 1;
+    while i < bytes.len() && bytes[i] != quote {
+        if bytes[i] == b'\\' && quote != b'`' {
+            i += 1;
+        }
+        i += 1;
+    }
+    i + 1
+}
+
+/// Index just past the first `close` after `start`, skipping strings
+fn skip_until(bytes: &[u8], start: usize, close: u8) -> usize {
+    let mut i = start + 1;
+    while i < bytes.len() && bytes[i] != close {
+        if matches!(bytes[i], b'"' | b'\'' | b'`') {
+            i = skip_string(bytes, i);
+        } else {
+            i += 1;
+        }
+    }
+    i + 1
+}
+
+impl Range {
+    /// The last `minutes` up to now, at most `target_points` samples per series
+    pub fn last_minutes(minutes: i64, step_secs: Option<i64>, target_points: i64) -> Self {
+        let end = Utc::now();
+        let start = end - chrono::Duration::minutes(minutes);
+        let step_secs = step_secs.unwrap_or((minutes * 60 / target_points).max(15));
+        Self {
+            start,
+            end,
+            step_secs,
+        }
+    }
+
+    pub fn points(&self) -> i64 {
+        (self.end - self.start).num_seconds() / self.step_secs.max(1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::PrometheusConfig;
+    use axum::{extract::Query, routing::get, Json, Router};
+    use serde_json::json;
+    use std::collections::HashMap;
+    use std::sync::{Arc, Mutex};
+
+    fn check(query: &str) -> Result<(), String> {
+        check_query(query, &PrometheusConfig::default().allowed_metrics)
+    }
+
+    #[test]
+    fn curated_queries_pass_the_allowlist() {
+        for curated in CURATED_QUERIES {
+            assert_eq!(check(curated.query), Ok(()), "{}", curated.id);
+        }
+    }
+
+    #[test]
+    fn allowed_queries() {
+        let cases = [
+            "up",
+            "rate(http_requests_total{status=~\"5..\"}[5m])",
+            "sum without (instance) (rate(http_requests_total[1m]))",
+            "sum by(le)(rate(http_request_duration_seconds_bucket[5m]))",
+            "sum(rate(http_requests_total[5m])) by (route)",
+            "count by (job) (up)",
+            "topk(5, news_articles_total)",
+            "count_values(\"version\", up)",
+            "http_requests_total offset 1h",
+            "up == bool 1",
+            "http_requests_total and on (job) up",
+            "http_requests_total * on (job) group_left (instance) up",
+            "http_requests_total / ignoring(status) group_right up",
+            "label_replace(up, \"dst\", \"$1\", \"src\", \"(node_.*)\")",
+            "process_resident_memory_bytes > 1e9 or up",
+        ];
+        for query in cases {
+            assert_eq!(check(query), Ok(()), "{query}");
+        }
+    }
+
+    #[test]
+    fn rejected_queries() {
+        let cases = [
+            ("node_cpu_seconds_total", "node_cpu_seconds_total"),
+            ("rate(node_cpu_seconds_total[5m])", "node_cpu_seconds_total"),
+            ("http_requests_total or secret_total", "secret_total"),
+            ("sum by (le) (secret_bucket)", "secret_bucket"),
+            ("label_replace(secret, \"a\", \"b\", \"c\", \"d\")", "Metric secret "),
+            ("{job=\"node\"}", "must name a metric"),
+            ("{__name__=~\".+\"}", "__name__"),
+            // Keywords used as bare metric names
+            ("count", "Metric count "),
+            ("sum", "Metric sum "),
+            ("group", "Metric group "),
+            ("max{job=\"node\"}", "Metric max "),
+            ("rate(count[5m])", "Metric count "),
+            ("up + on", "Metric on "),
+            ("sum(up) by", "Metric by "),
+            ("http_requests_total / without", "Metric without "),
+        ];
+        for (query, error) in cases {
+            match check(query) {
+                Err(message) => assert!(message.contains(error), "{query}: {message}"),
+                Ok(()) => panic!("{query} was allowed"),
+            }
+        }
+    }
+
+    #[test]
+    fn label_lists_are_skipped_after_whitespace() {
+        // Label names inside the list are not metrics, however it is spaced
+        assert_eq!(selected_metrics("sum by   ( le, node_x ) (up)"), Ok(vec!["up"]));
+        assert_eq!(selected_metrics("up * on\t(node_x) group_left() up"), Ok(vec!["up", "up"]));
+    }
+
+    /// Prometheus stand-in answering every range query with one series and
+    /// recording the query parameters it received
+    async fn stub_prometheus(
+        body: serde_json::Value,
+        status: axum::http::StatusCode,
+    ) -> (PrometheusClient, Arc<Mutex<Vec<HashMap<String, String>>>>) {
+        let seen = Arc::new(Mutex::new(Vec::new()));
+        let recorder = seen.clone();
+        let app = Router::new().route(
+            "/api/v1/query_range",
+            get(move |Query(params): Query<HashMap<String, String>>| {
+                recorder.lock().unwrap().push(params);
+                let body = body.clone();
+                async move { (status, Json(body)) }
+            }),
+        );
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        tokio::spawn(async move { axum::serve(listener, app).await });
+
+        let client = PrometheusClient::new(&PrometheusConfig {
+            url: format!("http://{addr}/"),
+            ..PrometheusConfig::default()
+        });
+        (client, seen)
+    }
+
+    fn range() -> Range {
+        Range {
+            start: Utc.timestamp_opt(1_700_000_000, 0).unwrap(),
+            end: Utc.timestamp_opt(1_700_003_600, 0).unwrap(),
+            step_secs: 60,
+        }
+    }
+
+    #[tokio::test]
+    async fn curated_range_queries_against_a_stub_server() {
+        let matrix = json!({
+            "status": "success",
+            "data": {
+                "resultType": "matrix",
+                "result": [{
+                    "metric": {"route": "/api/news"},
+                    "values": [
+                        [1_700_000_000.0, "1.5"],
+                        [1_700_000_060.5, "NaN"],
+                        [1_700_000_120.0, "+Inf"],
+                        [1_700_000_180.0, "2"]
+                    ]
+                }]
+            }
+        });
+        let (client, seen) = stub_prometheus(matrix, axum::http::StatusCode::OK).await;
+
+        for curated in CURATED_QUERIES {
+            let series = client.query_range(curated.query, range()).await.unwrap();
+
+            assert_eq!(series.len(), 1, "{}", curated.id);
+            assert_eq!(series[0].labels["route"], "/api/news");
+            let points: Vec<(i64, f64)> =
+                series[0].points.iter().map(|(t, v)| (t.timestamp(), *v)).collect();
+            assert_eq!(points, [(1_700_000_000, 1.5), (1_700_000_180, 2.0)]);
+        }
+
+        let seen = seen.lock().unwrap();
+        assert_eq!(seen.len(), CURATED_QUERIES.len());
+        for (params, curated) in seen.iter().zip(CURATED_QUERIES) {
+            assert_eq!(params["query"], curated.query);
+            assert_eq!(params["start"], "1700000000");
+            assert_eq!(params["end"], "1700003600");
+            assert_eq!(params["step"], "60");
+        }
+    }
+
+    #[tokio::test]
+    async fn rejected_expressions_are_bad_requests() {
+        let error = json!({
+            "status": "error",
+            "errorType": "bad_data",
+            "error": "parse error: unexpected end of input"
+        });
+        let (client, _) = stub_prometheus(error, axum::http::StatusCode::BAD_REQUEST).await;
+
+        match client.query_range("sum(", range()).await {
+            Err(ApiError::BadRequest(message)) => {
+                assert!(message.contains("unexpected end of input"), "{message}")
+            }
+            other => panic!("expected BadRequest, got {other:?}"),
+        }
+    }
+
+    #[tokio::test]
+    async fn server_errors_are_prometheus_errors() {
+        let error = json!({"status": "error", "error": "query timed out"});
+        let status = axum::http::StatusCode::SERVICE_UNAVAILABLE;
+        let (client, _) = stub_prometheus(error, status).await;
+
+        let result = client.query_range("up", range()).await;
+        assert!(matches!(result, Err(ApiError::Prometheus(m)) if m.contains("query timed out")));
+    }
+}
@@ -0,0 +1,721 @@
+//! Request rate limiting with in-memory token buckets.
+//!
+//! Each bucket holds up to `capacity` tokens and refills at `capacity` per
+//! `period`; a request takes one token or is refused with the time until the
+//! next one is available. [`RateLimitLayer`] gives every client IP, or API key
+//! when one was presented, a bucket per route class, plus one across all
+//! routes for keys with their own `rate_limit_per_minute`. Failed
+//! authentications on any route are charged to the client IP's `auth` bucket
+//! by [`AuthFailureLayer`]. Buckets are per process, so with several replicas
+//! each enforces its own share.
+
+use std::collections::HashMap;
+use std::future::Future;
+use std::hash::Hash;
+use std::net::{IpAddr, SocketAddr};
+use std::pin::Pin;
+use std::sync::{Arc, Mutex};
+use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
+
+use axum::{
+    extract::{ConnectInfo, MatchedPath},
+    http::{header, HeaderMap, HeaderValue, Request, StatusCode},
+    response::{IntoResponse, Response},
+};
+use tower::{Layer, Service};
+
+use crate::{api_keys::ApiKeyAuth, config::RateLimitConfig, error::ApiError};
+
+/// Calls to `check` between sweeps of buckets that have refilled completely
+const SWEEP_EVERY: u64 = 1024;
+
+#[derive(Debug, Clone, Copy)]
+struct Bucket {
+    tokens: f64,
+    updated: Instant,
+    capacity: f64,
+    per_sec: f64,
+}
+
+impl Bucket {
+    fn refill(&mut self, now: Instant) {
+        let elapsed = now.duration_since(self.updated).as_secs_f64();
+        self.tokens = (self.tokens + elapsed * self.per_sec).min(self.capacity);
+        self.updated = now;
+    }
+
+    /// Time until a token is available, if none is now
+    fn wait(&self) -> Option<Duration> {
+        (self.tokens < 1.0).then(|| Duration::from_secs_f64((1.0 - self.tokens) / self.per_sec))
+    }
+}
+
+/// One bucket a request has to take a token from
+#[derive(Debug, Clone)]
+pub struct Limit<K> {
+    pub key: K,
+    pub capacity: u32,
+    pub period: Duration,
+}
+
+/// Outcome of taking a token, for the most restrictive bucket
+#[derive(Debug, Clone, Copy)]
+pub enum Decision {
+    Allowed {
+        limit: u32,
+        /// Tokens left after this request
+        remaining: u32,
+        /// Until the bucket is full again
+        reset: Duration,
+    },
+    Limited {
+        limit: u32,
+        /// Until one token is available again
+        retry_after: Duration,
+    },
+}
+
+#[derive(Clone)]
+pub struct RateLimiter<K> {
+    inner: Arc<Mutex<State<K>>>,
+}
+
+struct State<K> {
+    buckets: HashMap<K, Bucket>,
+    checks: u64,
+}
+
+impl<K: Hash + Eq + Clone> RateLimiter<K> {
+    pub fn new() -> Self {
+        Self {
+            inner: Arc::new(Mutex::new(State {
+                buckets: HashMap::new(),
+                checks: 0,
+            })),
+        }
+    }
+
+    /// Take a token from every bucket, or from none if any of them is empty
+    pub fn check(&self, limits: &[Limit<K>]) -> Decision {
+        let now = Instant::now();
+        let mut state = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+
+        state.checks += 1;
+        if state.checks.is_multiple_of(SWEEP_EVERY) {
+            // A full bucket is the same as no bucket
+            state.buckets.retain(|_, bucket| {
+                bucket.refill(now);
+                bucket.tokens < bucket.capacity
+            });
+        }
+
+        let mut limited: Option<(u32, Duration)> = None;
+        for limit in limits {
+            if let Some(wait) = state.bucket(limit, now).wait() {
+                if limited.is_none_or(|(_, longest)| wait > longest) {
+                    limited = Some((limit.capacity, wait));
+                }
+            }
+        }
+        if let Some((limit, retry_after)) = limited {
+            return Decision::Limited { limit, retry_after };
+        }
+
+        let mut tightest = Decision::Allowed {
+            limit: 0,
+            remaining: u32::MAX,
+            reset: Duration::ZERO,
+        };
+        for limit in limits {
+            let Some(bucket) = state.buckets.get_mut(&limit.key) else {
+                continue;
+            };
+            bucket.tokens -= 1.0;
+            let remaining = bucket.tokens as u32;
+            if matches!(tightest, Decision::Allowed { remaining: r, .. } if remaining < r) {
+                tightest = Decision::Allowed {
+                    limit: limit.capacity,
+                    remaining,
+                    reset: Duration::from_secs_f64(
+                        (bucket.capacity - bucket.tokens) / bucket.per_sec,
+                    ),
+                };
+            }
+        }
+
+        tightest
+    }
+
+    /// Time until `limit` has a token again, without taking one
+    pub fn wait(&self, limit: &Limit<K>) -> Option<Duration> {
+        let mut state = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        state.bucket(limit, Instant::now()).wait()
+    }
+
+    /// Take a token from `limit` if one is left, without refusing anything
+    pub fn charge(&self, limit: &Limit<K>) {
+        let mut state = self.inner.lock().unwrap_or_else(|e| e.into_inner());
+        let bucket = state.bucket(limit, Instant::now());
+        bucket.tokens = (bucket.tokens - 1.0).max(0.0);
+    }
+}
+
+impl<K: Hash + Eq + Clone> State<K> {
+    /// Bucket of `limit`, refilled up to `now`
+    fn bucket(&mut self, limit: &Limit<K>, now: Instant) -> &mut Bucket {
+        let capacity = f64::from(limit.capacity.max(1));
+        let per_sec = capacity / limit.period.as_secs_f64().max(f64::EPSILON);
+        let bucket = self.buckets.entry(limit.key.clone()).or_insert(Bucket {
+            tokens: capacity,
+            updated: now,
+            capacity,
+            per_sec,
+        });
+        bucket.refill(now);
+        // The limit may have changed since the bucket was created
+        bucket.capacity = capacity;
+        bucket.per_sec = per_sec;
+        bucket.tokens = bucket.tokens.min(capacity);
+        bucket
+    }
+}
+
+/// Routes sharing a budget
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum RouteClass {
+    /// `/api/news/search`
+    Search,
+    /// Article, statistics and category reads
+    List,
+    /// `/api/export`
+    Export,
+    /// `/api/admin` and the other `/api/auth` routes
+    Admin,
+    /// Sign-in and token refresh, where credentials can be guessed
+    Auth,
+}
+
+impl RouteClass {
+    pub const ALL: [Self; 5] = [
+        Self::Search,
+        Self::List,
+        Self::Export,
+        Self::Admin,
+        Self::Auth,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Search => "search",
+            Self::List => "list",
+            Self::Export => "export",
+            Self::Admin => "admin",
+            Self::Auth => "auth",
+        }
+    }
+
+    /// Class of a matched route template; health, metrics and ingest are not limited
+    pub fn of(route: &str) -> Option<Self> {
+        let under = |prefix: &str| {
+            route
+                .strip_prefix(prefix)
+                .is_some_and(|rest| rest.is_empty() || rest.starts_with('/'))
+        };
+
+        if route == "/api/news/search" {
+            Some(Self::Search)
+        } else if under("/api/export") {
+            Some(Self::Export)
+        } else if route == "/api/auth/login" || route == "/api/auth/refresh" {
+            Some(Self::Auth)
+        } else if under("/api/admin") || under("/api/auth") {
+            Some(Self::Admin)
+        } else if ["/api/news", "/api/stats", "/api/status", "/api/categories"]
+            .into_iter()
+            .any(under)
+        {
+            Some(Self::List)
+        } else {
+            None
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum BucketKey {
+    Client(RouteClass, IpAddr),
+    ApiKey(RouteClass, i32),
+    /// All requests of a key with its own `rate_limit_per_minute`
+    ApiKeyTotal(i32),
+}
+
+/// Tower layer applying the configured budgets
+///
+/// Add it with `Router::route_layer` inside the API key middleware, so the
+/// matched route and the request's key are known.
+#[derive(Clone)]
+pub struct RateLimitLayer {
+    limiter: RateLimiter<BucketKey>,
+    config: Arc<RateLimitConfig>,
+}
+
+impl RateLimitLayer {
+    pub fn new(config: &RateLimitConfig) -> Self {
+        Self {
+            limiter: RateLimiter::new(),
+            config: Arc::new(config.clone()),
+        }
+    }
+}
+
+impl RateLimitLayer {
+    /// Layer charging failed authentications to the client's `auth` bucket
+    ///
+    /// Add it with `Router::route_layer` outside the API key middleware, so
+    /// requests that middleware or a route extractor answers with `401` are
+    /// counted too. Unknown API keys, invalid tokens and wrong passwords then
+    /// share one budget per client IP, and a client that has used it up gets
+    /// `429` for any request carrying credentials before they are looked up.
+    pub fn auth_failures(&self) -> AuthFailureLayer {
+        AuthFailureLayer {
+            limiter: self.limiter.clone(),
+            config: self.config.clone(),
+        }
+    }
+}
+
+impl<S> Layer<S> for RateLimitLayer {
+    type Service = RateLimit<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        RateLimit {
+            inner,
+            limiter: self.limiter.clone(),
+            config: self.config.clone(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct RateLimit<S> {
+    inner: S,
+    limiter: RateLimiter<BucketKey>,
+    config: Arc<RateLimitConfig>,
+}
+
+impl<S> RateLimit<S> {
+    /// Buckets the request draws from
+    fn limits<B>(&self, request: &Request<B>) -> Vec<Limit<BucketKey>> {
+        let mut limits = Vec::new();
+        if !self.config.enabled {
+            return limits;
+        }
+        let Some(route) = request.extensions().get::<MatchedPath>() else {
+            return limits;
+        };
+        let Some(class) = RouteClass::of(route.as_str()) else {
+            return limits;
+        };
+
+        let budget = self.config.budget(class);
+        let key = request.extensions().get::<ApiKeyAuth>();
+        let bucket = match key {
+            Some(key) => Some(BucketKey::ApiKey(class, key.id)),
+            None => client_ip(request, self.config.trusted_proxies)
+                .map(|ip| BucketKey::Client(class, ip)),
+        };
+        if let Some(bucket) = bucket.filter(|_| budget.requests > 0) {
+            limits.push(Limit {
+                key: bucket,
+                capacity: budget.requests,
+                period: budget.period(),
+            });
+        }
+        if let Some(key) = key {
+            if let Some(per_minute) = key.rate_limit_per_minute {
+                limits.push(Limit {
+                    key: BucketKey::ApiKeyTotal(key.id),
+                    capacity: per_minute,
+                    period: Duration::from_secs(60),
+                });
+            }
+        }
+
+        limits
+    }
+}
+
+impl<S, B> Service<Request<B>> for RateLimit<S>
+where
+    S: Service<Request<B>, Response = Response> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+    B: Send + 'static,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Response, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<B>) -> Self::Future {
+        let limits = self.limits(&request);
+        let decision = (!limits.is_empty()).then(|| self.limiter.check(&limits));
+
+        if let Some(Decision::Limited { limit, retry_after }) = decision {
+            let mut response = ApiError::TooManyRequests { retry_after }.into_response();
+            write_headers(response.headers_mut(), &limits, limit, 0, retry_after);
+            return Box::pin(async move { Ok(response) });
+        }
+
+        // The clone may not be ready; call the one that was polled
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+
+        Box::pin(async move {
+            let mut response = inner.call(request).await?;
+            if let Some(Decision::Allowed {
+                limit,
+                remaining,
+                reset,
+            }) = decision
+            {
+                write_headers(response.headers_mut(), &limits, limit, remaining, reset);
+            }
+            Ok(response)
+        })
+    }
+}
+
+#[derive(Clone)]
+pub struct AuthFailureLayer {
+    limiter: RateLimiter<BucketKey>,
+    config: Arc<RateLimitConfig>,
+}
+
+impl<S> Layer<S> for AuthFailureLayer {
+    type Service = AuthFailures<S>;
+
+    fn layer(&self, inner: S) -> Self::Service {
+        AuthFailures {
+            inner,
+            limiter: self.limiter.clone(),
+            config: self.config.clone(),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct AuthFailures<S> {
+    inner: S,
+    limiter: RateLimiter<BucketKey>,
+    config: Arc<RateLimitConfig>,
+}
+
+impl<S> AuthFailures<S> {
+    /// The client's `auth` bucket, unless the request is not limited
+    fn limit<B>(&self, request: &Request<B>) -> Option<Limit<BucketKey>> {
+        let budget = self.config.budget(RouteClass::Auth);
+        if !self.config.enabled || budget.requests == 0 {
+            return None;
+        }
+        // Sign-in and refresh draw from this bucket for every request already
+        let route = request.extensions().get::<MatchedPath>()?;
+        if RouteClass::of(route.as_str()) == Some(RouteClass::Auth) {
+            return None;
+        }
+
+        let ip = client_ip(request, self.config.trusted_proxies)?;
+        Some(Limit {
+            key: BucketKey::Client(RouteClass::Auth, ip),
+            capacity: budget.requests,
+            period: budget.period(),
+        })
+    }
+}
+
+impl<S, B> Service<Request<B>> for AuthFailures<S>
+where
+    S: Service<Request<B>, Response = Response> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+    B: Send + 'static,
+{
+    type Response = Response;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Response, S::Error>> + Send>>;
+
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+
+    fn call(&mut self, request: Request<B>) -> Self::Future {
+        let limit = self.limit(&request);
+
+        if let Some(limit) = limit.as_ref().filter(|_| carries_credentials(&request)) {
+            if let Some(retry_after) = self.limiter.wait(limit) {
+                let mut response = ApiError::TooManyRequests { retry_after }.into_response();
+                let limits = std::slice::from_ref(limit);
+                write_headers(response.headers_mut(), limits, limit.capacity, 0, retry_after);
+                return Box::pin(async move { Ok(response) });
+            }
+        }
+
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        let limiter = self.limiter.clone();
+
+        Box::pin(async move {
+            let response = inner.call(request).await?;
+            if let Some(limit) = limit.filter(|_| response.status() == StatusCode::UNAUTHORIZED) {
+                limiter.charge(&limit);
+            }
+            Ok(response)
+        })
+    }
+}
+
+/// Whether a request presents an API key, access token or stream ticket
+fn carries_credentials<B>(request: &Request<B>) -> bool {
+    let headers = request.headers();
+    headers.contains_key(header::AUTHORIZATION)
+        || headers.contains_key("x-api-key")
+        || request
+            .uri()
+            .query()
+            .is_some_and(|query| query.split('&').any(|pair| pair.starts_with("ticket=")))
+}
+
+/// `RateLimit-*` headers of draft-ietf-httpapi-ratelimit-headers
+fn write_headers(
+    headers: &mut HeaderMap,
+    limits: &[Limit<BucketKey>],
+    limit: u32,
+    remaining: u32,
+    reset: Duration,
+) {
+    headers.insert("ratelimit-limit", HeaderValue::from(limit));
+    headers.insert("ratelimit-remaining", HeaderValue::from(remaining));
+    headers.insert("ratelimit-reset", HeaderValue::from(ceil_secs(reset)));
+
+    let policy = limits
+        .iter()
+        .map(|l| format!("{};w={}", l.capacity, l.period.as_secs()))
+        .collect::<Vec<_>>()
+        .join(", ");
+    if let Ok(value) = HeaderValue::from_str(&policy) {
+        headers.insert("ratelimit-policy", value);
+    }
+}
+
+fn ceil_secs(duration: Duration) -> u64 {
+    duration.as_secs() + u64::from(duration.subsec_nanos() > 0)
+}
+
+/// Address of the client, looking through `trusted_proxies` reverse proxies
+///
+/// Each trusted proxy appends the address it received the request from to
+/// `X-Forwarded-For`, so the client is that many entries from the end; entries
+/// further left were sent by the client and cannot be trusted. Without enough
+/// entries, or if that entry is not an address, the peer address is used.
+fn client_ip<B>(request: &Request<B>, trusted_proxies: usize) -> Option<IpAddr> {
+    let peer = request
+        .extensions()
+        .get::<ConnectInfo<SocketAddr>>()
+        .map(|ConnectInfo(addr)| addr.ip());
+    if trusted_proxies == 0 {
+        return peer;
+    }
+
+    let forwarded: Vec<&str> = request
+        .headers()
+        .get_all("x-forwarded-for")
+        .iter()
+        .filter_map(|value| value.to_str().ok())
+        .flat_map(|value| value.split(','))
+        .collect();
+
+    forwarded
+        .len()
+        .checked_sub(trusted_proxies)
+        .and_then(|index| forwarded[index].trim().parse().ok())
+        .or(peer)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn route_classes() {
+        let cases = [
+            ("/api/news/search", Some(RouteClass::Search)),
+            ("/api/news", Some(RouteClass::List)),
+            ("/api/news/:id", Some(RouteClass::List)),
+            ("/api/stats/daily", Some(RouteClass::List)),
+            ("/api/categories", Some(RouteClass::List)),
+            ("/api/export", Some(RouteClass::Export)),
+            ("/api/export/:format", Some(RouteClass::Export)),
+            ("/api/exports", None),
+            ("/api/auth/login", Some(RouteClass::Auth)),
+            ("/api/auth/refresh", Some(RouteClass::Auth)),
+            ("/api/auth/me", Some(RouteClass::Admin)),
+            ("/api/auth/stream-ticket", Some(RouteClass::Admin)),
+            ("/api/admin/logs/stream", Some(RouteClass::Admin)),
+            ("/api/newsletter", None),
+            ("/api/ingest/crawl-runs", None),
+            ("/api/health", None),
+            ("/metrics", None),
+        ];
+        for (route, class) in cases {
+            assert_eq!(RouteClass::of(route), class, "{route}");
+        }
+    }
+
+    fn limit(capacity: u32) -> Limit<&'static str> {
+        Limit {
+            key: "client",
+            capacity,
+            period: Duration::from_secs(60),
+        }
+    }
+
+    #[test]
+    fn check_refuses_once_the_bucket_is_empty() {
+        let limiter = RateLimiter::new();
+        let limits = [limit(2)];
+
+        assert!(matches!(
+            limiter.check(&limits),
+            Decision::Allowed { limit: 2, remaining: 1, .. }
+        ));
+        assert!(matches!(limiter.check(&limits), Decision::Allowed { remaining: 0, .. }));
+        match limiter.check(&limits) {
+            Decision::Limited { limit, retry_after } => {
+                assert_eq!(limit, 2);
+                // One token refills in 30 seconds
+                assert!(retry_after > Duration::from_secs(29), "{retry_after:?}");
+                assert!(retry_after <= Duration::from_secs(30), "{retry_after:?}");
+            }
+            other => panic!("expected Limited, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn charge_takes_tokens_without_refusing() {
+        let limiter = RateLimiter::new();
+        let limit = limit(2);
+
+        assert_eq!(limiter.wait(&limit), None);
+        limiter.charge(&limit);
+        assert_eq!(limiter.wait(&limit), None);
+        limiter.charge(&limit);
+        limiter.charge(&limit);
+        assert!(limiter.wait(&limit).is_some());
+        assert!(matches!(limiter.check(&[limit]), Decision::Limited { .. }));
+    }
+
+    /// Router limited by `config` where every request carries API key 3
+    fn keyed_router(config: &RateLimitConfig, per_minute: Option<u32>) -> axum::Router {
+        use axum::{middleware, routing::get};
+
+        let key = ApiKeyAuth {
+            id: 3,
+            name: "partner".to_string(),
+            scopes: Vec::new(),
+            rate_limit_per_minute: per_minute,
+        };
+        axum::Router::new()
+            .route("/api/news", get(|| async { "list" }))
+            .route("/api/news/search", get(|| async { "search" }))
+            .route_layer(RateLimitLayer::new(config))
+            .route_layer(middleware::from_fn(
+                move |mut request: axum::extract::Request, next: middleware::Next| {
+                    request.extensions_mut().insert(key.clone());
+                    next.run(request)
+                },
+            ))
+    }
+
+    async fn get_status(router: &axum::Router, uri: &str) -> StatusCode {
+        // A router is always ready, so it can be called without `poll_ready`
+        let request = Request::get(uri).body(axum::body::Body::empty()).unwrap();
+        router.clone().call(request).await.unwrap().status()
+    }
+
+    #[tokio::test]
+    async fn key_limit_spans_route_classes() {
+        let config = RateLimitConfig {
+            enabled: true,
+            ..RateLimitConfig::default()
+        };
+
+        let router = keyed_router(&config, Some(2));
+        assert_eq!(get_status(&router, "/api/news").await, StatusCode::OK);
+        assert_eq!(get_status(&router, "/api/news/search").await, StatusCode::OK);
+        assert_eq!(get_status(&router, "/api/news").await, StatusCode::TOO_MANY_REQUESTS);
+        assert_eq!(
+            get_status(&router, "/api/news/search").await,
+            StatusCode::TOO_MANY_REQUESTS
+        );
+
+        // Without its own limit a key only has the class budgets
+        let router = keyed_router(&config, None);
+        for _ in 0..5 {
+            assert_eq!(get_status(&router, "/api/news").await, StatusCode::OK);
+        }
+    }
+
+    fn forwarded_request(forwarded: &[&str]) -> Request<()> {
+        let mut request = Request::new(());
+        for value in forwarded {
+            request
+                .headers_mut()
+                .append("x-forwarded-for", HeaderValue::from_str(value).unwrap());
+        }
+        request
+            .extensions_mut()
+            .insert(ConnectInfo(SocketAddr::from(([10, 0, 0, 2], 41000))));
+        request
+    }
+
+    fn ip(value: &str) -> Option<IpAddr> {
+        Some(value.parse().unwrap())
+    }
+
+    #[test]
+    fn client_ip_without_forwarded_header_is_the_peer() {
+        let request = forwarded_request(&[]);
+
+        assert_eq!(client_ip(&request, 0), ip("10.0.0.2"));
+        assert_eq!(client_ip(&request, 1), ip("10.0.0.2"));
+    }
+
+    #[test]
+    fn client_ip_ignores_entries_sent_by_the_client() {
+        let request = forwarded_request(&["6.6.6.6, 7.7.7.7", "203.0.113.9, 10.0.0.1"]);
+
+        assert_eq!(client_ip(&request, 0), ip("10.0.0.2"));
+        assert_eq!(client_ip(&request, 1), ip("10.0.0.1"));
+        assert_eq!(client_ip(&request, 2), ip("203.0.113.9"));
+    }
+
+    #[test]
+    fn client_ip_with_fewer_hops_than_trusted_is_the_peer() {
+        let request = forwarded_request(&["203.0.113.9"]);
+
+        assert_eq!(client_ip(&request, 1), ip("203.0.113.9"));
+        assert_eq!(client_ip(&request, 2), ip("10.0.0.2"));
+    }
+
+    #[test]
+    fn client_ip_counts_garbage_entries_as_hops() {
+        let request = forwarded_request(&["203.0.113.9, unknown, 2001:db8::1,"]);
+
+        assert_eq!(client_ip(&request, 1), ip("10.0.0.2"));
+        assert_eq!(client_ip(&request, 2), ip("2001:db8::1"));
+        assert_eq!(client_ip(&request, 3), ip("10.0.0.2"));
+        assert_eq!(client_ip(&request, 4), ip("203.0.113.9"));
+    }
+}
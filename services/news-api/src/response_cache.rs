@@ -0,0 +1,291 @@
+//! In-process cache for the expensive read endpoints.
+//!
+//! Responses of `/api/stats`, `/api/stats/daily`, `/api/categories` and the
+//! first pages of `/api/news` are kept for a TTL per route (`[cache]`).
+//! Concurrent misses for one key wait for a single load instead of each
+//! querying OpenSearch or PostgreSQL, and failed loads are not cached. Each
+//! route keeps at most `cache.max_entries` keys, evicting the least recently
+//! used, since keys come from query parameters. A reported crawl run drops
+//! every entry, since it means new articles and crawl stats; category changes
+//! drop the category-dependent ones.
+
+use std::any::Any;
+use std::collections::HashMap;
+use std::future::Future;
+use std::sync::{Arc, Mutex};
+use std::time::Instant;
+
+use crate::{config::CacheConfig, error::ApiResult, metrics};
+
+/// New keys between sweeps of expired entries
+const SWEEP_EVERY: usize = 256;
+
+/// Endpoint whose responses are cached
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum CacheRoute {
+    Stats,
+    DailyStats,
+    Categories,
+    News,
+}
+
+impl CacheRoute {
+    pub const ALL: [Self; 4] = [Self::Stats, Self::DailyStats, Self::Categories, Self::News];
+
+    /// Route template, used as the metrics label
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Stats => "/api/stats",
+            Self::DailyStats => "/api/stats/daily",
+            Self::Categories => "/api/categories",
+            Self::News => "/api/news",
+        }
+    }
+}
+
+struct Cached {
+    value: Box<dyn Any + Send + Sync>,
+    expires: Instant,
+}
+
+/// Locked while the value is loaded, so concurrent misses wait for one load
+type Slot = Arc<tokio::sync::Mutex<Option<Cached>>>;
+
+#[derive(Clone)]
+pub struct ResponseCache {
+    config: Arc<CacheConfig>,
+    entries: Arc<Mutex<Entries>>,
+}
+
+#[derive(Default)]
+struct Entries {
+    slots: HashMap<(CacheRoute, String), Entry>,
+    inserted: usize,
+    /// Incremented on every lookup, to order entries by last use
+    clock: u64,
+}
+
+struct Entry {
+    slot: Slot,
+    used: u64,
+}
+
+impl ResponseCache {
+    pub fn new(config: &CacheConfig) -> Self {
+        Self {
+            config: Arc::new(config.clone()),
+            entries: Arc::default(),
+        }
+    }
+
+    /// Cached value for `key`, or the result of `load`, which is cached if it succeeds
+    pub async fn get_or_load<T, F, Fut>(
+        &self,
+        route: CacheRoute,
+        key: String,
+        load: F,
+    ) -> ApiResult<T>
+    where
+        T: Clone + Send + Sync + 'static,
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = ApiResult<T>>,
+    {
+        let ttl = self.config.ttl(route);
+        if ttl.is_zero() {
+            return load().await;
+        }
+
+        let slot = self.slot(route, key);
+        let mut cached = slot.lock().await;
+
+        let fresh = cached
+            .as_ref()
+            .filter(|c| c.expires > Instant::now())
+            .and_then(|c| c.value.downcast_ref::<T>());
+        if let Some(value) = fresh {
+            metrics::record_cache(route.as_str(), true);
+            return Ok(value.clone());
+        }
+
+        metrics::record_cache(route.as_str(), false);
+        let value = load().await?;
+        *cached = Some(Cached {
+            value: Box::new(value.clone()),
+            expires: Instant::now() + ttl,
+        });
+
+        Ok(value)
+    }
+
+    /// Drop the entries of these routes; loads already running finish uncached
+    pub fn invalidate(&self, routes: &[CacheRoute]) {
+        let mut entries = self.lock();
+        entries.slots.retain(|(route, _), _| !routes.contains(route));
+    }
+
+    /// Whether `page` of `/api/news` is among the cached first pages
+    pub fn caches_news_page(&self, page: i64) -> bool {
+        page <= i64::from(self.config.news_max_page)
+    }
+
+    fn slot(&self, route: CacheRoute, key: String) -> Slot {
+        let mut entries = self.lock();
+        entries.clock += 1;
+        let used = entries.clock;
+
+        let id = (route, key);
+        if let Some(entry) = entries.slots.get_mut(&id) {
+            entry.used = used;
+            return entry.slot.clone();
+        }
+
+        entries.inserted += 1;
+        if entries.inserted.is_multiple_of(SWEEP_EVERY) {
+            let now = Instant::now();
+            // A slot that cannot be locked is being loaded or read
+            entries.slots.retain(|_, entry| {
+                entry
+                    .slot
+                    .try_lock()
+                    .map_or(true, |c| c.as_ref().is_some_and(|c| c.expires > now))
+            });
+        }
+
+        // Requests still holding an evicted slot finish with it unaffected
+        let in_route = entries.slots.keys().filter(|(r, _)| *r == route).count();
+        if in_route >= self.config.max_entries {
+            let oldest = entries
+                .slots
+                .iter()
+                .filter(|((r, _), _)| *r == route)
+                .min_by_key(|(_, entry)| entry.used)
+                .map(|(id, _)| id.clone());
+            if let Some(oldest) = oldest {
+                entries.slots.remove(&oldest);
+            }
+        }
+
+        let slot = Slot::default();
+        entries.slots.insert(
+            id,
+            Entry {
+                slot: slot.clone(),
+                used,
+            },
+        );
+        slot
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, Entries> {
+        self.entries.lock().unwrap_or_else(|e| e.into_inner())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ApiError;
+    use std::sync::atomic::{AtomicUsize, Ordering};
+
+    fn cache(max_entries: usize) -> ResponseCache {
+        ResponseCache::new(&CacheConfig {
+            max_entries,
+            ..CacheConfig::default()
+        })
+    }
+
+    /// Value for `key`, counting the loads
+    async fn get(cache: &ResponseCache, key: &str, loads: &AtomicUsize) -> String {
+        let load = || async {
+            loads.fetch_add(1, Ordering::SeqCst);
+            Ok(key.to_uppercase())
+        };
+        cache
+            .get_or_load(CacheRoute::News, key.to_string(), load)
+            .await
+            .unwrap()
+    }
+
+    #[tokio::test]
+    async fn hits_do_not_load_again() {
+        let (cache, loads) = (cache(10), AtomicUsize::new(0));
+
+        assert_eq!(get(&cache, "a", &loads).await, "A");
+        assert_eq!(get(&cache, "a", &loads).await, "A");
+        assert_eq!(loads.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn least_recently_used_entry_is_evicted_at_the_cap() {
+        let (cache, loads) = (cache(2), AtomicUsize::new(0));
+
+        get(&cache, "a", &loads).await;
+        get(&cache, "b", &loads).await;
+        // `a` is now more recently used than `b`
+        get(&cache, "a", &loads).await;
+        get(&cache, "c", &loads).await;
+        assert_eq!(loads.load(Ordering::SeqCst), 3);
+        assert_eq!(cache.lock().slots.len(), 2);
+
+        get(&cache, "a", &loads).await;
+        get(&cache, "c", &loads).await;
+        assert_eq!(loads.load(Ordering::SeqCst), 3);
+        get(&cache, "b", &loads).await;
+        assert_eq!(loads.load(Ordering::SeqCst), 4);
+    }
+
+    #[tokio::test]
+    async fn cap_is_per_route() {
+        let (cache, loads) = (cache(1), AtomicUsize::new(0));
+
+        get(&cache, "a", &loads).await;
+        let stats = cache
+            .get_or_load(CacheRoute::Stats, String::new(), || async { Ok(1) })
+            .await
+            .unwrap();
+        assert_eq!(stats, 1);
+
+        get(&cache, "a", &loads).await;
+        assert_eq!(loads.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn errors_are_not_cached() {
+        let (cache, loads) = (cache(10), AtomicUsize::new(0));
+
+        let failed: ApiResult<String> = cache
+            .get_or_load(CacheRoute::News, "a".to_string(), || async {
+                Err(ApiError::BadRequest("no".to_string()))
+            })
+            .await;
+        assert!(failed.is_err());
+
+        assert_eq!(get(&cache, "a", &loads).await, "A");
+        assert_eq!(loads.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn concurrent_misses_load_once() {
+        let (cache, loads) = (cache(10), AtomicUsize::new(0));
+
+        let values =
+            futures_util::future::join_all((0..10).map(|_| get(&cache, "a", &loads))).await;
+
+        assert!(values.iter().all(|v| v == "A"));
+        assert_eq!(loads.load(Ordering::SeqCst), 1);
+    }
+
+    #[tokio::test]
+    async fn invalidate_drops_only_the_given_routes() {
+        let (cache, loads) = (cache(10), AtomicUsize::new(0));
+
+        get(&cache, "a", &loads).await;
+        cache.invalidate(&[CacheRoute::Stats]);
+        get(&cache, "a", &loads).await;
+        assert_eq!(loads.load(Ordering::SeqCst), 1);
+
+        cache.invalidate(&[CacheRoute::News]);
+        get(&cache, "a", &loads).await;
+        assert_eq!(loads.load(Ordering::SeqCst), 2);
+    }
+}
@@ -0,0 +1,275 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+use serde::Deserialize;
+use validator::Validate;
+
+use crate::{
+    alerts::{self, ALERT_RULE_COLUMNS},
+    error::{ApiError, ApiResult},
+    models::{
+        AlertEvent, AlertEventListResponse, AlertListResponse, AlertMetric, AlertRule,
+        AlertRuleListResponse, AlertRuleRequest,
+    },
+    AppState,
+};
+
+#[derive(Debug, Deserialize)]
+pub struct AlertQuery {
+    /// `ok` or `firing`
+    pub state: Option<String>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct AlertEventParams {
+    pub rule_id: Option<i32>,
+    /// `firing` or `resolved`
+    pub state: Option<String>,
+    #[serde(default = "default_event_limit")]
+    pub limit: i64,
+}
+
+fn default_event_limit() -> i64 {
+    100
+}
+
+/// GET /api/admin/alerts - Enabled rules with their current state
+pub async fn get_alerts(
+    State(state): State<AppState>,
+    Query(params): Query<AlertQuery>,
+) -> ApiResult<Json<AlertListResponse>> {
+    if let Some(s) = params.state.as_deref() {
+        if !matches!(s, "ok" | "firing") {
+            return Err(ApiError::BadRequest("state must be ok or firing".to_string()));
+        }
+    }
+
+    alert_list(&state, params.state.as_deref()).await.map(Json)
+}
+
+/// POST /api/admin/alerts/evaluate - Evaluate every enabled rule now
+pub async fn evaluate_alerts(State(state): State<AppState>) -> ApiResult<Json<AlertListResponse>> {
+    alerts::evaluate_all(&state).await?;
+
+    alert_list(&state, None).await.map(Json)
+}
+
+async fn alert_list(state: &AppState, filter: Option<&str>) -> ApiResult<AlertListResponse> {
+    let alerts: Vec<AlertRule> = sqlx::query_as(&format!(
+        r#"
+        SELECT {ALERT_RULE_COLUMNS}
+        FROM alert_rules
+        WHERE enabled AND ($1::VARCHAR IS NULL OR state = $1)
+        ORDER BY state = 'firing' DESC,
+                 CASE severity WHEN 'critical' THEN 0 WHEN 'warning' THEN 1 ELSE 2 END,
+                 name
+        "#
+    ))
+    .bind(filter)
+    .fetch_all(&state.db.pool)
+    .await?;
+
+    let (firing, last_evaluated_at) = sqlx::query_as(
+        r#"
+        SELECT COUNT(*) FILTER (WHERE state = 'firing'), MAX(last_evaluated_at)
+        FROM alert_rules
+        WHERE enabled
+        "#,
+    )
+    .fetch_one(&state.db.pool)
+    .await?;
+
+    Ok(AlertListResponse {
+        alerts,
+        firing,
+        last_evaluated_at,
+    })
+}
+
+/// GET /api/admin/alerts/events - Firing and resolved transitions, newest first
+pub async fn get_alert_events(
+    State(state): State<AppState>,
+    Query(params): Query<AlertEventParams>,
+) -> ApiResult<Json<AlertEventListResponse>> {
+    let limit = params.limit.clamp(1, 1000);
+
+    let events: Vec<AlertEvent> = sqlx::query_as(
+        r#"
+        SELECT e.id, e.rule_id, r.name AS rule_name, r.severity, e.state, e.value, e.message,
+               e.created_at
+        FROM alert_events e
+        JOIN alert_rules r ON r.id = e.rule_id
+        WHERE ($1::INT IS NULL OR e.rule_id = $1)
+          AND ($2::VARCHAR IS NULL OR e.state = $2)
+        ORDER BY e.created_at DESC, e.id DESC
+        LIMIT $3
+        "#,
+    )
+    .bind(params.rule_id)
+    .bind(&params.state)
+    .bind(limit)
+    .fetch_all(&state.db.pool)
+    .await?;
+
+    let total: i64 = sqlx::query_scalar(
+        r#"
+        SELECT COUNT(*)
+        FROM alert_events
+        WHERE ($1::INT IS NULL OR rule_id = $1)
+          AND ($2::VARCHAR IS NULL OR state = $2)
+        "#,
+    )
+    .bind(params.rule_id)
+    .bind(&params.state)
+    .fetch_one(&state.db.pool)
+    .await?;
+
+    Ok(Json(AlertEventListResponse { events, total }))
+}
+
+/// GET /api/admin/alerts/rules - List alert rules
+pub async fn get_alert_rules(
+    State(state): State<AppState>,
+) -> ApiResult<Json<AlertRuleListResponse>> {
+    let rules: Vec<AlertRule> =
+        sqlx::query_as(&format!("SELECT {ALERT_RULE_COLUMNS} FROM alert_rules ORDER BY name"))
+            .fetch_all(&state.db.pool)
+            .await?;
+
+    let total = rules.len() as i64;
+
+    Ok(Json(AlertRuleListResponse { rules, total }))
+}
+
+/// GET /api/admin/alerts/rules/:id - Get an alert rule by ID
+pub async fn get_alert_rule(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+) -> ApiResult<Json<AlertRule>> {
+    let rule: Option<AlertRule> =
+        sqlx::query_as(&format!("SELECT {ALERT_RULE_COLUMNS} FROM alert_rules WHERE id = $1"))
+            .bind(id)
+            .fetch_optional(&state.db.pool)
+            .await?;
+
+    rule.map(Json)
+        .ok_or_else(|| ApiError::NotFound(format!("Alert rule with id {id} not found")))
+}
+
+/// POST /api/admin/alerts/rules - Create an alert rule
+pub async fn create_alert_rule(
+    State(state): State<AppState>,
+    Json(req): Json<AlertRuleRequest>,
+) -> ApiResult<(StatusCode, Json<AlertRule>)> {
+    check_rule(&state, &req)?;
+
+    let rule: AlertRule = sqlx::query_as(&format!(
+        r#"
+        INSERT INTO alert_rules
+            (name, metric, target, operator, threshold, window_minutes, severity, enabled)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        RETURNING {ALERT_RULE_COLUMNS}
+        "#
+    ))
+    .bind(&req.name)
+    .bind(req.metric.as_str())
+    .bind(&req.target)
+    .bind(req.operator.as_str())
+    .bind(req.threshold)
+    .bind(req.window_minutes)
+    .bind(&req.severity)
+    .bind(req.enabled)
+    .fetch_one(&state.db.pool)
+    .await
+    .map_err(|e| unique_violation(e, &req.name))?;
+
+    Ok((StatusCode::CREATED, Json(rule)))
+}
+
+/// PUT /api/admin/alerts/rules/:id - Replace an alert rule
+///
+/// The rule keeps its state until the next evaluation; disabling it clears
+/// the state without recording a resolved event.
+pub async fn update_alert_rule(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+    Json(req): Json<AlertRuleRequest>,
+) -> ApiResult<Json<AlertRule>> {
+    check_rule(&state, &req)?;
+
+    let rule: Option<AlertRule> = sqlx::query_as(&format!(
+        r#"
+        UPDATE alert_rules
+        SET name = $2, metric = $3, target = $4, operator = $5, threshold = $6,
+            window_minutes = $7, severity = $8, enabled = $9,
+            state = CASE WHEN $9 THEN state ELSE 'ok' END,
+            updated_at = NOW()
+        WHERE id = $1
+        RETURNING {ALERT_RULE_COLUMNS}
+        "#
+    ))
+    .bind(id)
+    .bind(&req.name)
+    .bind(req.metric.as_str())
+    .bind(&req.target)
+    .bind(req.operator.as_str())
+    .bind(req.threshold)
+    .bind(req.window_minutes)
+    .bind(&req.severity)
+    .bind(req.enabled)
+    .fetch_optional(&state.db.pool)
+    .await
+    .map_err(|e| unique_violation(e, &req.name))?;
+
+    rule.map(Json)
+        .ok_or_else(|| ApiError::NotFound(format!("Alert rule with id {id} not found")))
+}
+
+/// DELETE /api/admin/alerts/rules/:id - Remove an alert rule and its events
+pub async fn delete_alert_rule(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+) -> ApiResult<StatusCode> {
+    let result = sqlx::query("DELETE FROM alert_rules WHERE id = $1")
+        .bind(id)
+        .execute(&state.db.pool)
+        .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(ApiError::NotFound(format!("Alert rule with id {id} not found")));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+fn check_rule(state: &AppState, req: &AlertRuleRequest) -> ApiResult<()> {
+    req.validate()?;
+
+    if !req.threshold.is_finite() {
+        return Err(ApiError::BadRequest("threshold must be a finite number".to_string()));
+    }
+    if req.metric == AlertMetric::ServiceDown {
+        match req.target.as_deref() {
+            Some(id) if state.services.contains(id) => {}
+            Some(id) => return Err(ApiError::BadRequest(format!("Service {id} is not registered"))),
+            None => {
+                return Err(ApiError::BadRequest(
+                    "service_down rules need the service ID as target".to_string(),
+                ))
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn unique_violation(e: sqlx::Error, name: &str) -> ApiError {
+    match &e {
+        sqlx::Error::Database(db) if db.is_unique_violation() => {
+            ApiError::Conflict(format!("Alert rule named {name} already exists"))
+        }
+        _ => ApiError::Database(e),
+    }
+}
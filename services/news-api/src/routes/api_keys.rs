@@ -0,0 +1,134 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+use chrono::Utc;
+use serde::Deserialize;
+use validator::Validate;
+
+use crate::{
+    api_keys::{self, API_KEY_COLUMNS},
+    auth::AuthUser,
+    error::{ApiError, ApiResult},
+    models::{ApiKey, ApiKeyListResponse, ApiKeyRequest, CreatedApiKey},
+    AppState,
+};
+
+#[derive(Debug, Deserialize)]
+pub struct ApiKeyParams {
+    /// Also list revoked keys
+    #[serde(default)]
+    pub include_revoked: bool,
+}
+
+/// GET /api/admin/api-keys - List API keys, without their secrets
+pub async fn get_api_keys(
+    State(state): State<AppState>,
+    Query(params): Query<ApiKeyParams>,
+) -> ApiResult<Json<ApiKeyListResponse>> {
+    let api_keys: Vec<ApiKey> = sqlx::query_as(&format!(
+        r#"
+        SELECT {API_KEY_COLUMNS}
+        FROM api_keys
+        WHERE $1 OR revoked_at IS NULL
+        ORDER BY name, id
+        "#
+    ))
+    .bind(params.include_revoked)
+    .fetch_all(&state.db.pool)
+    .await?;
+
+    let total = api_keys.len() as i64;
+
+    Ok(Json(ApiKeyListResponse { api_keys, total }))
+}
+
+/// GET /api/admin/api-keys/:id - Get an API key by ID
+pub async fn get_api_key(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+) -> ApiResult<Json<ApiKey>> {
+    let api_key: Option<ApiKey> =
+        sqlx::query_as(&format!("SELECT {API_KEY_COLUMNS} FROM api_keys WHERE id = $1"))
+            .bind(id)
+            .fetch_optional(&state.db.pool)
+            .await?;
+
+    api_key
+        .map(Json)
+        .ok_or_else(|| ApiError::NotFound(format!("API key with id {id} not found")))
+}
+
+/// POST /api/admin/api-keys - Create an API key
+///
+/// The response is the only time the secret is shown.
+pub async fn create_api_key(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Json(mut req): Json<ApiKeyRequest>,
+) -> ApiResult<(StatusCode, Json<CreatedApiKey>)> {
+    req.validate()?;
+    if req.expires_at.is_some_and(|t| t <= Utc::now()) {
+        return Err(ApiError::BadRequest("expires_at must be in the future".to_string()));
+    }
+    req.scopes.sort();
+    req.scopes.dedup();
+
+    let (key, prefix, hash) = api_keys::generate();
+
+    let api_key: ApiKey = sqlx::query_as(&format!(
+        r#"
+        INSERT INTO api_keys
+            (name, prefix, key_hash, scopes, rate_limit_per_minute, expires_at, created_by)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        RETURNING {API_KEY_COLUMNS}
+        "#
+    ))
+    .bind(&req.name)
+    .bind(&prefix)
+    .bind(&hash)
+    .bind(&req.scopes)
+    .bind(req.rate_limit_per_minute)
+    .bind(req.expires_at)
+    .bind(&user.username)
+    .fetch_one(&state.db.pool)
+    .await?;
+
+    tracing::info!(
+        key = api_key.name,
+        prefix = api_key.prefix,
+        created_by = user.username,
+        "API key created"
+    );
+
+    Ok((StatusCode::CREATED, Json(CreatedApiKey { api_key, key })))
+}
+
+/// DELETE /api/admin/api-keys/:id - Revoke an API key
+///
+/// The key stays listed with `revoked_at` set so its use remains traceable.
+pub async fn revoke_api_key(
+    State(state): State<AppState>,
+    user: AuthUser,
+    Path(id): Path<i32>,
+) -> ApiResult<StatusCode> {
+    let name: Option<String> = sqlx::query_scalar(
+        r#"
+        UPDATE api_keys
+        SET revoked_at = COALESCE(revoked_at, NOW())
+        WHERE id = $1
+        RETURNING name
+        "#,
+    )
+    .bind(id)
+    .fetch_optional(&state.db.pool)
+    .await?;
+
+    let Some(name) = name else {
+        return Err(ApiError::NotFound(format!("API key with id {id} not found")));
+    };
+    tracing::info!(key = name, revoked_by = user.username, "API key revoked");
+
+    Ok(StatusCode::NO_CONTENT)
+}
@@ -0,0 +1,63 @@
+use axum::{extract::State, http::StatusCode, Json};
+use validator::Validate;
+
+use crate::{
+    auth::AuthUser,
+    error::ApiResult,
+    models::{LoginRequest, RefreshRequest, StreamTicketResponse, TokenResponse},
+    AppState,
+};
+
+/// POST /api/auth/login - Exchange a username and password for tokens
+pub async fn login(
+    State(state): State<AppState>,
+    Json(req): Json<LoginRequest>,
+) -> ApiResult<Json<TokenResponse>> {
+    req.validate()?;
+
+    let tokens = state
+        .auth
+        .login(&state.db, &req.username, &req.password)
+        .await?;
+
+    Ok(Json(tokens))
+}
+
+/// POST /api/auth/refresh - Exchange a refresh token for a new token pair
+pub async fn refresh_token(
+    State(state): State<AppState>,
+    Json(req): Json<RefreshRequest>,
+) -> ApiResult<Json<TokenResponse>> {
+    req.validate()?;
+
+    let tokens = state.auth.refresh(&state.db, &req.refresh_token).await?;
+
+    Ok(Json(tokens))
+}
+
+/// POST /api/auth/logout - Revoke a refresh token
+pub async fn logout(
+    State(state): State<AppState>,
+    Json(req): Json<RefreshRequest>,
+) -> ApiResult<StatusCode> {
+    req.validate()?;
+
+    state.auth.logout(&state.db, &req.refresh_token).await?;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// GET /api/auth/me - The user the access token belongs to
+pub async fn get_current_user(user: AuthUser) -> Json<AuthUser> {
+    Json(user)
+}
+
+/// POST /api/auth/stream-ticket - Short-lived ticket for /api/admin/logs/stream
+pub async fn create_stream_ticket(
+    State(state): State<AppState>,
+    user: AuthUser,
+) -> ApiResult<Json<StreamTicketResponse>> {
+    let ticket = state.auth.stream_ticket(&user)?;
+
+    Ok(Json(ticket))
+}
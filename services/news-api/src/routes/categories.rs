@@ -1,26 +1,156 @@
-use axum::{extract::State, Json};
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use validator::Validate;
 
 use crate::{
-    error::ApiResult,
-    models::{Category, CategoryListResponse},
+    category_sync,
+    category_tree::CategoryTree,
+    error::{ApiError, ApiResult},
+    models::{
+        CategoryDriftResponse, CategoryListResponse, CategorySyncReport, CategoryUpdateRequest,
+    },
+    response_cache::CacheRoute,
     AppState,
 };
 
-/// GET /api/categories - List categories
+/// GET /api/categories - Category tree with rolled-up counts
 pub async fn get_categories(
     State(state): State<AppState>,
 ) -> ApiResult<Json<CategoryListResponse>> {
-    let categories: Vec<Category> = sqlx::query_as(
-        r#"
-        SELECT id, name, article_count
-        FROM categories
-        ORDER BY article_count DESC
-        "#,
+    let load = || async {
+        let tree = CategoryTree::load(&state.db.pool).await?;
+
+        let total = tree.len() as i64;
+        let categories = tree.into_nodes();
+
+        Ok(CategoryListResponse { categories, total })
+    };
+    let response = state
+        .cache
+        .get_or_load(CacheRoute::Categories, String::new(), load)
+        .await?;
+
+    Ok(Json(response))
+}
+
+/// PUT /api/admin/categories/:id - Set a category's parent and aliases
+pub async fn update_category(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+    Json(req): Json<CategoryUpdateRequest>,
+) -> ApiResult<StatusCode> {
+    req.validate()?;
+
+    let tree = CategoryTree::load(&state.db.pool).await?;
+
+    if !tree.contains(id) {
+        return Err(ApiError::NotFound(format!("Category with id {id} not found")));
+    }
+
+    // Labels are matched exactly, so aliases are compared and stored trimmed
+    let mut aliases: Vec<&str> = Vec::new();
+    for alias in req.aliases.iter().map(|a| a.trim()) {
+        if !aliases.contains(&alias) {
+            aliases.push(alias);
+        }
+    }
+
+    // The count sync creates a category for every unknown label; such a
+    // category is merged into this one when its name is listed as an alias
+    let mut merged = Vec::new();
+    for alias in &aliases {
+        let Some(owner) = tree.resolve(alias).filter(|c| c.id != id) else {
+            continue;
+        };
+        if owner.name == *alias && tree.is_mergeable(owner.id) {
+            merged.push(owner);
+        } else {
+            return Err(ApiError::Conflict(format!(
+                "Label {alias} already belongs to category {}",
+                owner.name
+            )));
+        }
+    }
+
+    if let Some(parent_id) = req.parent_id {
+        if !tree.contains(parent_id) {
+            return Err(ApiError::BadRequest(format!(
+                "Parent category with id {parent_id} not found"
+            )));
+        }
+        if tree.is_ancestor_or_self(id, parent_id) {
+            return Err(ApiError::BadRequest(
+                "A category cannot be its own ancestor".to_string(),
+            ));
+        }
+        if merged.iter().any(|c| c.id == parent_id) {
+            return Err(ApiError::BadRequest(
+                "A category cannot be both the parent and an alias".to_string(),
+            ));
+        }
+    }
+
+    let mut tx = state.db.pool.begin().await?;
+
+    // Merged articles count towards this category until the next sync
+    let merged_count: i32 = merged.iter().map(|c| c.article_count).sum();
+    for category in &merged {
+        sqlx::query("DELETE FROM categories WHERE id = $1")
+            .bind(category.id)
+            .execute(&mut *tx)
+            .await?;
+    }
+
+    sqlx::query(
+        "UPDATE categories SET parent_id = $2, article_count = article_count + $3 WHERE id = $1",
     )
-    .fetch_all(&state.db.pool)
+    .bind(id)
+    .bind(req.parent_id)
+    .bind(merged_count)
+    .execute(&mut *tx)
     .await?;
 
-    let total = categories.len() as i64;
+    sqlx::query("DELETE FROM category_aliases WHERE category_id = $1")
+        .bind(id)
+        .execute(&mut *tx)
+        .await?;
+
+    for alias in &aliases {
+        sqlx::query("INSERT INTO category_aliases (alias, category_id) VALUES ($1, $2)")
+            .bind(alias)
+            .bind(id)
+            .execute(&mut *tx)
+            .await?;
+    }
+
+    tx.commit().await?;
+
+    // News lists expand categories through the tree
+    state
+        .cache
+        .invalidate(&[CacheRoute::Categories, CacheRoute::News]);
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// POST /api/admin/categories/sync - Recompute category counts from the search index
+pub async fn sync_categories(
+    State(state): State<AppState>,
+) -> ApiResult<Json<CategorySyncReport>> {
+    let report = state.category_sync.trigger(&state.db, &state.search).await?;
+    state.cache.invalidate(&[CacheRoute::Categories]);
+
+    Ok(Json(report))
+}
+
+/// GET /api/admin/categories/drift - Differences between stored and indexed counts
+pub async fn get_category_drift(
+    State(state): State<AppState>,
+) -> ApiResult<Json<CategoryDriftResponse>> {
+    let report = category_sync::category_drift(&state.db, &state.search).await?;
 
-    Ok(Json(CategoryListResponse { categories, total }))
+    Ok(Json(report))
 }
@@ -0,0 +1,183 @@
+use axum::{
+    extract::{Query, State},
+    http::StatusCode,
+    Json,
+};
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use validator::Validate;
+
+use crate::{
+    db,
+    error::ApiResult,
+    models::{
+        CrawlRun, CrawlRunListResponse, CrawlRunRequest, MAX_ERROR_SAMPLES, MAX_ERROR_SAMPLE_BYTES,
+    },
+    response_cache::CacheRoute,
+    AppState,
+};
+
+const CRAWL_RUN_COLUMNS: &str = "r.id, r.source_id, s.name AS source_name, r.started_at, \
+     r.finished_at, r.pages_fetched, r.articles_new, r.articles_updated, r.articles_failed, \
+     r.error_samples, r.created_at";
+
+#[derive(Debug, Deserialize)]
+pub struct CrawlRunParams {
+    pub source_id: Option<i32>,
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+    /// Only return runs with at least one failed article
+    #[serde(default)]
+    pub failed: bool,
+    #[serde(default = "default_page")]
+    pub page: i64,
+    #[serde(default = "default_limit")]
+    pub limit: i64,
+}
+
+fn default_page() -> i64 {
+    1
+}
+
+fn default_limit() -> i64 {
+    50
+}
+
+/// POST /api/ingest/crawl-runs - Record a crawler run
+pub async fn report_crawl_run(
+    State(state): State<AppState>,
+    Json(mut req): Json<CrawlRunRequest>,
+) -> ApiResult<(StatusCode, Json<CrawlRun>)> {
+    req.validate()?;
+    req.error_samples.truncate(MAX_ERROR_SAMPLES);
+    for sample in &mut req.error_samples {
+        truncate_sample(sample);
+    }
+
+    let mut tx = state.db.pool.begin().await?;
+
+    let id: i64 = sqlx::query_scalar(
+        r#"
+        INSERT INTO crawl_runs
+            (source_id, started_at, finished_at, pages_fetched, articles_new,
+             articles_updated, articles_failed, error_samples)
+        VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+        RETURNING id
+        "#,
+    )
+    .bind(req.source_id)
+    .bind(req.started_at)
+    .bind(req.finished_at)
+    .bind(req.pages_fetched)
+    .bind(req.articles_new)
+    .bind(req.articles_updated)
+    .bind(req.articles_failed)
+    .bind(&req.error_samples)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    db::derive_crawl_stats(&mut tx, req.started_at.date_naive()).await?;
+
+    let run: CrawlRun = sqlx::query_as(&format!(
+        r#"
+        SELECT {CRAWL_RUN_COLUMNS}
+        FROM crawl_runs r
+        LEFT JOIN sources s ON s.id = r.source_id
+        WHERE r.id = $1
+        "#
+    ))
+    .bind(id)
+    .fetch_one(&mut *tx)
+    .await?;
+
+    tx.commit().await?;
+
+    // A run means new articles as well as new crawl stats
+    state.cache.invalidate(&CacheRoute::ALL);
+
+    Ok((StatusCode::CREATED, Json(run)))
+}
+
+/// Cut an error sample to `MAX_ERROR_SAMPLE_BYTES`, on a character boundary
+fn truncate_sample(sample: &mut String) {
+    if sample.len() > MAX_ERROR_SAMPLE_BYTES {
+        let end = (0..=MAX_ERROR_SAMPLE_BYTES)
+            .rev()
+            .find(|&i| sample.is_char_boundary(i))
+            .unwrap_or(0);
+        sample.truncate(end);
+    }
+}
+
+/// GET /api/stats/crawl-runs - List crawler runs with filtering
+pub async fn get_crawl_runs(
+    State(state): State<AppState>,
+    Query(params): Query<CrawlRunParams>,
+) -> ApiResult<Json<CrawlRunListResponse>> {
+    let page = params.page.max(1);
+    let limit = params.limit.clamp(1, 500);
+    let offset = (page - 1) * limit;
+
+    let filter = r#"
+        WHERE ($1::INT IS NULL OR r.source_id = $1)
+          AND ($2::TIMESTAMPTZ IS NULL OR r.started_at >= $2)
+          AND ($3::TIMESTAMPTZ IS NULL OR r.started_at < $3)
+          AND (NOT $4 OR r.articles_failed > 0)
+    "#;
+
+    let total: i64 = sqlx::query_scalar(&format!("SELECT COUNT(*) FROM crawl_runs r {filter}"))
+        .bind(params.source_id)
+        .bind(params.from)
+        .bind(params.to)
+        .bind(params.failed)
+        .fetch_one(&state.db.pool)
+        .await?;
+
+    let runs: Vec<CrawlRun> = sqlx::query_as(&format!(
+        r#"
+        SELECT {CRAWL_RUN_COLUMNS}
+        FROM crawl_runs r
+        LEFT JOIN sources s ON s.id = r.source_id
+        {filter}
+        ORDER BY r.started_at DESC
+        LIMIT $5 OFFSET $6
+        "#
+    ))
+    .bind(params.source_id)
+    .bind(params.from)
+    .bind(params.to)
+    .bind(params.failed)
+    .bind(limit)
+    .bind(offset)
+    .fetch_all(&state.db.pool)
+    .await?;
+
+    Ok(Json(CrawlRunListResponse {
+        runs,
+        total,
+        page,
+        limit,
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn samples_are_cut_on_a_character_boundary() {
+        let mut short = "timeout".to_string();
+        truncate_sample(&mut short);
+        assert_eq!(short, "timeout");
+
+        let mut exact = "a".repeat(MAX_ERROR_SAMPLE_BYTES);
+        truncate_sample(&mut exact);
+        assert_eq!(exact.len(), MAX_ERROR_SAMPLE_BYTES);
+
+        // 3-byte characters; 1024 is not a multiple of 3
+        let mut korean = "기사".repeat(400);
+        truncate_sample(&mut korean);
+        assert_eq!(korean.len(), 1023);
+        assert!(korean.ends_with('기'));
+    }
+}
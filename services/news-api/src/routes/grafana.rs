@@ -0,0 +1,80 @@
+use axum::{
+    extract::{Query, State},
+    Json,
+};
+use serde::Deserialize;
+
+use crate::{
+    error::{ApiError, ApiResult},
+    grafana,
+    models::{GrafanaCatalogResponse, GrafanaDashboard, HealthStatus},
+    AppState,
+};
+
+#[derive(Debug, Deserialize)]
+pub struct GrafanaDashboardParams {
+    /// Grafana time such as `now-6h` or epoch milliseconds
+    #[serde(default = "default_from")]
+    pub from: String,
+    #[serde(default = "default_to")]
+    pub to: String,
+    /// Query Grafana even if the cached catalog has not expired
+    #[serde(default)]
+    pub refresh: bool,
+}
+
+fn default_from() -> String {
+    "now-6h".to_string()
+}
+
+fn default_to() -> String {
+    "now".to_string()
+}
+
+/// GET /api/admin/grafana/dashboards - Dashboard catalog with embed links
+pub async fn get_grafana_dashboards(
+    State(state): State<AppState>,
+    Query(params): Query<GrafanaDashboardParams>,
+) -> ApiResult<Json<GrafanaCatalogResponse>> {
+    for (name, value) in [("from", &params.from), ("to", &params.to)] {
+        if !grafana::is_time_expression(value) {
+            return Err(ApiError::BadRequest(format!(
+                "{name} must be like now, now-6h, now-1d/d or epoch milliseconds"
+            )));
+        }
+    }
+
+    let catalog = state.grafana.catalog(params.refresh).await;
+    let config = &state.config.grafana;
+    let base = config.public_url.trim_end_matches('/');
+    // `+` would read as a space in the query string
+    let (from, to) = (params.from.replace('+', "%2B"), params.to.replace('+', "%2B"));
+
+    let dashboards = catalog
+        .dashboards
+        .into_iter()
+        .map(|d| GrafanaDashboard {
+            url: format!(
+                "{base}/d/{}/{}?orgId={}&from={from}&to={to}&kiosk",
+                d.uid, d.slug, config.org_id
+            ),
+            uid: d.uid,
+            title: d.title,
+            tags: d.tags,
+            folder: d.folder,
+        })
+        .collect::<Vec<_>>();
+
+    let status = match &catalog.error {
+        None => HealthStatus::Healthy,
+        Some(_) if catalog.fetched_at.is_some() => HealthStatus::Degraded,
+        Some(_) => HealthStatus::Down,
+    };
+
+    Ok(Json(GrafanaCatalogResponse {
+        status,
+        dashboards,
+        fetched_at: catalog.fetched_at,
+        message: catalog.error,
+    }))
+}
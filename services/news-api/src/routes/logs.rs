@@ -0,0 +1,296 @@
+use axum::{
+    extract::{Query, State},
+    http::HeaderMap,
+    response::sse::{Event, KeepAlive, Sse},
+    Json,
+};
+use chrono::{DateTime, Utc};
+use futures_util::stream::{self, Stream};
+use serde::Deserialize;
+use std::collections::VecDeque;
+use tokio::sync::broadcast::{error::RecvError, Receiver};
+
+use crate::{
+    error::ApiResult,
+    log_capture::LogFilter,
+    models::{LogEntry, LogLevel},
+    AppState,
+};
+
+const LOG_ENTRY_COLUMNS: &str = "id, logged_at, level, service, target, message, metadata";
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LogStore {
+    /// Recent entries captured by this process
+    #[default]
+    Memory,
+    /// Entries persisted by any API instance with `[logs] persist = true`
+    Postgres,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct LogParams {
+    pub service: Option<String>,
+    /// Least severe level to include
+    pub level: Option<LogLevel>,
+    /// Module path prefix
+    pub target: Option<String>,
+    pub from: Option<DateTime<Utc>>,
+    pub to: Option<DateTime<Utc>>,
+    /// Case-insensitive text the message must contain
+    pub q: Option<String>,
+    #[serde(default)]
+    pub store: LogStore,
+    #[serde(default = "default_limit")]
+    pub limit: i64,
+}
+
+fn default_limit() -> i64 {
+    100
+}
+
+#[derive(Debug, Deserialize)]
+pub struct LogStreamParams {
+    pub service: Option<String>,
+    /// Least severe level to include
+    pub level: Option<LogLevel>,
+    /// Module path prefix
+    pub target: Option<String>,
+}
+
+/// GET /api/admin/logs - Latest captured log entries, oldest first
+pub async fn get_logs(
+    State(state): State<AppState>,
+    Query(params): Query<LogParams>,
+) -> ApiResult<Json<Vec<LogEntry>>> {
+    let limit = params.limit.clamp(1, 1000);
+    let filter = LogFilter {
+        service: params.service.filter(|s| !s.is_empty()),
+        level: params.level,
+        target: params.target.filter(|t| !t.is_empty()),
+        from: params.from,
+        to: params.to,
+        text: params.q.filter(|q| !q.is_empty()),
+    };
+
+    let entries = match params.store {
+        LogStore::Memory => state.logs.query(&filter, limit as usize),
+        LogStore::Postgres => {
+            let mut entries: Vec<LogEntry> = sqlx::query_as(&format!(
+                r#"
+                SELECT {LOG_ENTRY_COLUMNS}
+                FROM log_entries
+                WHERE ($1::VARCHAR IS NULL OR service = $1)
+                  AND level = ANY($2)
+                  AND ($3::VARCHAR IS NULL OR starts_with(target, $3))
+                  AND ($4::TIMESTAMPTZ IS NULL OR logged_at >= $4)
+                  AND ($5::TIMESTAMPTZ IS NULL OR logged_at < $5)
+                  AND ($6::TEXT IS NULL OR strpos(lower(message), lower($6)) > 0)
+                ORDER BY logged_at DESC, id DESC
+                LIMIT $7
+                "#
+            ))
+            .bind(&filter.service)
+            .bind(filter.level.unwrap_or(LogLevel::Trace).and_above())
+            .bind(&filter.target)
+            .bind(filter.from)
+            .bind(filter.to)
+            .bind(&filter.text)
+            .bind(limit)
+            .fetch_all(&state.db.pool)
+            .await?;
+            entries.reverse();
+            entries
+        }
+    };
+
+    Ok(Json(entries))
+}
+
+/// GET /api/admin/logs/stream - Server-Sent Events of new log entries
+///
+/// Each entry is sent as a message whose ID is the entry ID, so a reconnecting
+/// `EventSource` resumes from the ring buffer through `Last-Event-ID`. Entries
+/// a slow client misses, or that left the buffer before it reconnected, are
+/// reported by a `dropped` event instead of holding up the server.
+pub async fn stream_logs(
+    State(state): State<AppState>,
+    headers: HeaderMap,
+    Query(params): Query<LogStreamParams>,
+) -> Sse<impl Stream<Item = Result<Event, axum::Error>>> {
+    let last_id = headers
+        .get("last-event-id")
+        .and_then(|v| v.to_str().ok())
+        .and_then(|v| v.trim().parse::<i64>().ok());
+    let filter = LogFilter {
+        service: params.service.filter(|s| !s.is_empty()),
+        level: params.level,
+        target: params.target.filter(|t| !t.is_empty()),
+        ..LogFilter::default()
+    };
+
+    let subscription = state.logs.subscribe(last_id);
+    let mut pending = VecDeque::new();
+    if subscription.missed > 0 {
+        pending.push_back(dropped_event(subscription.missed));
+    }
+    pending.extend(
+        subscription
+            .backlog
+            .iter()
+            .filter(|e| filter.matches(e))
+            .map(log_event),
+    );
+
+    let tail = Tail {
+        pending,
+        receiver: subscription.receiver,
+        filter,
+    };
+
+    Sse::new(stream::unfold(tail, next_event)).keep_alive(KeepAlive::default())
+}
+
+struct Tail {
+    /// Replayed entries and markers sent before live ones
+    pending: VecDeque<Result<Event, axum::Error>>,
+    receiver: Receiver<LogEntry>,
+    filter: LogFilter,
+}
+
+async fn next_event(mut tail: Tail) -> Option<(Result<Event, axum::Error>, Tail)> {
+    if let Some(event) = tail.pending.pop_front() {
+        return Some((event, tail));
+    }
+
+    loop {
+        match tail.receiver.recv().await {
+            Ok(entry) if tail.filter.matches(&entry) => return Some((log_event(&entry), tail)),
+            Ok(_) => continue,
+            Err(RecvError::Lagged(missed)) => return Some((dropped_event(missed), tail)),
+            Err(RecvError::Closed) => return None,
+        }
+    }
+}
+
+fn log_event(entry: &LogEntry) -> Result<Event, axum::Error> {
+    Event::default().id(entry.id.to_string()).json_data(entry)
+}
+
+/// Marker for entries the client will never receive; carries no ID so resuming
+/// continues from the last delivered entry
+fn dropped_event(count: u64) -> Result<Event, axum::Error> {
+    Event::default()
+        .event("dropped")
+        .json_data(serde_json::json!({ "dropped": count }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{config::Config, log_capture::LIVE_CAPACITY};
+    use axum::response::IntoResponse;
+    use http_body_util::BodyExt;
+    use std::time::Duration;
+    use tracing_subscriber::layer::SubscriberExt;
+
+    fn state(buffer_size: usize) -> AppState {
+        let mut config = Config::default();
+        config.auth.jwt_secret = "0123456789abcdef0123456789abcdef-test".to_string();
+        config.logs.buffer_size = buffer_size;
+        let state = AppState::for_tests(config);
+        state.logs.configure(&state.config.logs);
+        state
+    }
+
+    /// Capture `count` info entries through the tracing layer
+    fn log(state: &AppState, count: usize) {
+        let subscriber = tracing_subscriber::registry().with(state.logs.layer());
+        tracing::subscriber::with_default(subscriber, || {
+            for i in 0..count {
+                tracing::info!("entry {i}");
+            }
+        });
+    }
+
+    async fn stream(state: &AppState, last_id: Option<i64>) -> axum::body::Body {
+        let mut headers = HeaderMap::new();
+        if let Some(id) = last_id {
+            headers.insert("last-event-id", id.to_string().parse().unwrap());
+        }
+        let params = LogStreamParams {
+            service: None,
+            level: None,
+            target: None,
+        };
+        stream_logs(State(state.clone()), headers, Query(params))
+            .await
+            .into_response()
+            .into_body()
+    }
+
+    /// Next event as `(id or event name, data)`
+    async fn next(body: &mut axum::body::Body) -> (String, serde_json::Value) {
+        let frame = tokio::time::timeout(Duration::from_secs(1), body.frame())
+            .await
+            .expect("an event within a second")
+            .expect("an open stream")
+            .unwrap();
+        let text = String::from_utf8(frame.into_data().unwrap().to_vec()).unwrap();
+
+        let field = |name: &str| {
+            text.lines()
+                .find_map(|line| line.strip_prefix(name))
+                .map(|value| value.trim().to_string())
+        };
+        let name = field("id:").or_else(|| field("event:")).unwrap();
+        let data = serde_json::from_str(&field("data:").unwrap()).unwrap();
+        (name, data)
+    }
+
+    #[tokio::test]
+    async fn resumes_after_the_last_event_id() {
+        let state = state(10);
+        log(&state, 5);
+
+        let mut body = stream(&state, Some(3)).await;
+        assert_eq!(next(&mut body).await.0, "4");
+        assert_eq!(next(&mut body).await.0, "5");
+
+        log(&state, 1);
+        let (id, entry) = next(&mut body).await;
+        assert_eq!(id, "6");
+        assert_eq!(entry["message"], "entry 0");
+    }
+
+    #[tokio::test]
+    async fn reports_entries_evicted_before_resuming() {
+        let state = state(3);
+        log(&state, 6);
+
+        let mut body = stream(&state, Some(1)).await;
+        assert_eq!(
+            next(&mut body).await,
+            ("dropped".to_string(), serde_json::json!({ "dropped": 2 }))
+        );
+        for id in ["4", "5", "6"] {
+            assert_eq!(next(&mut body).await.0, id);
+        }
+    }
+
+    #[tokio::test]
+    async fn lagging_subscriber_gets_a_dropped_marker() {
+        let state = state(10);
+        let mut body = stream(&state, None).await;
+
+        // Nothing is read while the live channel overflows
+        log(&state, LIVE_CAPACITY + 10);
+
+        assert_eq!(
+            next(&mut body).await,
+            ("dropped".to_string(), serde_json::json!({ "dropped": 10 }))
+        );
+        assert_eq!(next(&mut body).await.0, "11");
+    }
+}
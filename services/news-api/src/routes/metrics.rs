@@ -0,0 +1,230 @@
+use axum::{
+    extract::{Query, State},
+    http::header,
+    response::IntoResponse,
+    Json,
+};
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use serde::Deserialize;
+use serde_json::Value;
+use std::time::Duration;
+use tokio::task::JoinSet;
+
+use crate::{
+    error::{ApiError, ApiResult},
+    metrics,
+    models::{MetricData, MetricPoint},
+    prometheus::{self, Range, CURATED_QUERIES, MAX_POINTS},
+    AppState,
+};
+
+/// Longest the scrape waits for OpenSearch article counts
+const ARTICLE_COUNT_TIMEOUT: Duration = Duration::from_secs(2);
+
+/// Samples per series when no step is given
+const TARGET_POINTS: i64 = 120;
+
+#[derive(Debug, Deserialize)]
+pub struct AdminMetricsParams {
+    #[serde(default = "default_minutes")]
+    pub minutes: i64,
+    /// Seconds between samples; sized for about 120 samples by default
+    pub step: Option<i64>,
+}
+
+fn default_minutes() -> i64 {
+    60
+}
+
+#[derive(Debug, Deserialize)]
+pub struct QueryRangeParams {
+    pub query: String,
+    /// Defaults to an hour before `end`
+    pub start: Option<DateTime<Utc>>,
+    /// Defaults to now
+    pub end: Option<DateTime<Utc>>,
+    /// Seconds between samples
+    #[serde(default = "default_step")]
+    pub step: i64,
+}
+
+fn default_step() -> i64 {
+    60
+}
+
+/// GET /metrics - Prometheus metrics
+pub async fn get_metrics(State(state): State<AppState>) -> impl IntoResponse {
+    let mut out = String::new();
+    metrics::render(&mut out);
+
+    // sqlx does not expose how many tasks wait for a connection; a saturated
+    // pool (every connection in use, none can be added) is where they queue
+    let pool = &state.db.pool;
+    let size = pool.size();
+    let idle = pool.num_idle() as u32;
+    let max = state.config.database.max_connections;
+    let in_use = size.saturating_sub(idle);
+
+    let name = "db_pool_connections";
+    metrics::write_gauge(
+        &mut out,
+        name,
+        "Open database connections by state",
+        &[("state", "idle")],
+        f64::from(idle),
+    );
+    metrics::write_sample(&mut out, name, &[("state", "in_use")], f64::from(in_use));
+    metrics::write_gauge(
+        &mut out,
+        "db_pool_max_connections",
+        "Configured database pool size",
+        &[],
+        f64::from(max),
+    );
+    metrics::write_gauge(
+        &mut out,
+        "db_pool_saturated",
+        "1 when every pooled connection is in use and new acquires wait",
+        &[],
+        if idle == 0 && size >= max { 1.0 } else { 0.0 },
+    );
+
+    let enabled_sources: Result<i64, sqlx::Error> =
+        sqlx::query_scalar("SELECT COUNT(*) FROM sources WHERE enabled")
+            .fetch_one(pool)
+            .await;
+    if let Ok(count) = enabled_sources {
+        metrics::write_gauge(
+            &mut out,
+            "news_sources_enabled",
+            "News sources enabled for crawling",
+            &[],
+            count as f64,
+        );
+    }
+
+    // Omitted rather than reported as 0 when OpenSearch is unavailable
+    let counts =
+        tokio::time::timeout(ARTICLE_COUNT_TIMEOUT, state.search.get_article_counts()).await;
+    if let Ok(Ok((total, today))) = counts {
+        metrics::write_gauge(
+            &mut out,
+            "news_articles_total",
+            "Articles in the search index",
+            &[],
+            total as f64,
+        );
+        metrics::write_gauge(
+            &mut out,
+            "news_articles_today",
+            "Articles crawled since midnight UTC",
+            &[],
+            today as f64,
+        );
+    }
+
+    ([(header::CONTENT_TYPE, "text/plain; version=0.0.4")], out)
+}
+
+/// GET /api/admin/metrics - Curated series from Prometheus for the Metrics page
+///
+/// Queries that fail are left out; the request only fails when all of them do.
+pub async fn get_admin_metrics(
+    State(state): State<AppState>,
+    Query(params): Query<AdminMetricsParams>,
+) -> ApiResult<Json<Vec<MetricData>>> {
+    let range = Range::last_minutes(params.minutes, params.step, TARGET_POINTS);
+    check_range(&state, &range)?;
+
+    let mut queries = JoinSet::new();
+    for (index, curated) in CURATED_QUERIES.iter().enumerate() {
+        let client = state.prometheus.clone();
+        queries.spawn(async move { (index, client.query_range(curated.query, range).await) });
+    }
+
+    let mut results = Vec::with_capacity(CURATED_QUERIES.len());
+    while let Some(joined) = queries.join_next().await {
+        match joined {
+            Ok(result) => results.push(result),
+            Err(e) => tracing::warn!("Metrics query task failed: {e}"),
+        }
+    }
+    results.sort_by_key(|(index, _)| *index);
+
+    let mut metrics = Vec::new();
+    let mut first_error = None;
+    for (index, result) in results {
+        let curated = &CURATED_QUERIES[index];
+        match result {
+            Ok(series) => metrics.extend(series.into_iter().filter_map(|series| {
+                let &(timestamp, value) = series.points.last()?;
+                Some(MetricData {
+                    id: curated.id.to_string(),
+                    name: curated.name.to_string(),
+                    value,
+                    unit: curated.unit.to_string(),
+                    timestamp,
+                    labels: series.labels,
+                    points: series
+                        .points
+                        .into_iter()
+                        .map(|(timestamp, value)| MetricPoint { timestamp, value })
+                        .collect(),
+                })
+            })),
+            Err(e) => {
+                tracing::warn!(query = curated.id, "Metrics query failed: {e}");
+                first_error.get_or_insert(e);
+            }
+        }
+    }
+
+    match first_error {
+        Some(e) if metrics.is_empty() => Err(e),
+        _ => Ok(Json(metrics)),
+    }
+}
+
+/// GET /api/admin/metrics/query_range - Allowlisted PromQL range query
+///
+/// Returns the `data` object of the Prometheus response unchanged.
+pub async fn query_metrics_range(
+    State(state): State<AppState>,
+    Query(params): Query<QueryRangeParams>,
+) -> ApiResult<Json<Value>> {
+    prometheus::check_query(&params.query, &state.config.prometheus.allowed_metrics)
+        .map_err(ApiError::Forbidden)?;
+
+    let end = params.end.unwrap_or_else(Utc::now);
+    let range = Range {
+        start: params.start.unwrap_or(end - ChronoDuration::hours(1)),
+        end,
+        step_secs: params.step,
+    };
+    check_range(&state, &range)?;
+
+    let data = state.prometheus.query_range_raw(&params.query, range).await?;
+    Ok(Json(data))
+}
+
+fn check_range(state: &AppState, range: &Range) -> ApiResult<()> {
+    let max_hours = state.config.prometheus.max_range_hours;
+
+    if range.step_secs < 1 {
+        return Err(ApiError::BadRequest("step must be at least 1 second".to_string()));
+    }
+    if range.end <= range.start {
+        return Err(ApiError::BadRequest("end must be after start".to_string()));
+    }
+    if range.end - range.start > ChronoDuration::hours(max_hours) {
+        return Err(ApiError::BadRequest(format!(
+            "Range must not exceed {max_hours} hours"
+        )));
+    }
+    if range.points() > MAX_POINTS {
+        return Err(ApiError::BadRequest(format!(
+            "Range and step give more than {MAX_POINTS} samples; increase step"
+        )));
+    }
+    Ok(())
+}
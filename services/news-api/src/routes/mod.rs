@@ -1,9 +1,39 @@
 pub mod health;
 pub mod news;
 pub mod stats;
+pub mod alerts;
+pub mod api_keys;
+pub mod auth;
 pub mod categories;
+pub mod crawl_runs;
+pub mod grafana;
+pub mod logs;
+pub mod metrics;
+pub mod services;
+pub mod sources;
+pub mod system;
+pub mod webhooks;
 
 pub use health::health_check;
 pub use news::{get_news_list, get_news_detail, search_news};
-pub use stats::{get_stats, get_status, get_daily_stats};
-pub use categories::get_categories;
+pub use stats::{get_stats, get_status, get_daily_stats, get_stats_breakdown};
+pub use alerts::{
+    create_alert_rule, delete_alert_rule, evaluate_alerts, get_alert_events, get_alert_rule,
+    get_alert_rules, get_alerts, update_alert_rule,
+};
+pub use api_keys::{create_api_key, get_api_key, get_api_keys, revoke_api_key};
+pub use auth::{create_stream_ticket, get_current_user, login, logout, refresh_token};
+pub use categories::{get_categories, get_category_drift, sync_categories, update_category};
+pub use crawl_runs::{get_crawl_runs, report_crawl_run};
+pub use grafana::get_grafana_dashboards;
+pub use logs::{get_logs, stream_logs};
+pub use metrics::{get_admin_metrics, get_metrics, query_metrics_range};
+pub use sources::{create_source, delete_source, get_source, get_sources, update_source};
+pub use services::{
+    get_service, get_service_actions, get_services, restart_service, start_service, stop_service,
+};
+pub use system::get_admin_status;
+pub use webhooks::{
+    create_webhook, delete_webhook, get_webhook, get_webhook_deliveries, get_webhooks,
+    test_webhook, update_webhook,
+};
@@ -3,10 +3,14 @@ use axum::{
     Json,
 };
 use serde::Deserialize;
+use std::sync::Arc;
 
 use crate::{
+    category_tree::CategoryTree,
     error::{ApiError, ApiResult},
     models::{Article, ArticleListResponse},
+    response_cache::CacheRoute,
+    search::ArticleFilter,
     AppState,
 };
 
@@ -16,6 +20,9 @@ pub struct PaginationParams {
     pub page: i64,
     #[serde(default = "default_limit")]
     pub limit: i64,
+    pub source_id: Option<i32>,
+    /// Category name or alias; includes all child categories
+    pub category: Option<String>,
 }
 
 fn default_page() -> i64 {
@@ -36,17 +43,63 @@ pub struct SearchParams {
 }
 
 /// GET /api/news - List news with pagination
+///
+/// The first `cache.news_max_page` pages of each filter on a known category
+/// (or none) are cached.
 pub async fn get_news_list(
     State(state): State<AppState>,
     Query(params): Query<PaginationParams>,
 ) -> ApiResult<Json<ArticleListResponse>> {
     let page = params.page.max(1);
     let limit = params.limit.clamp(1, 100);
+    let category = params.category.as_deref().map(str::trim).unwrap_or_default();
+
+    // Names and aliases of one category share cache entries; unknown labels
+    // are passed through to the index uncached, as there is no end to them
+    let (categories, category_id) = if category.is_empty() {
+        (Vec::new(), None)
+    } else {
+        let tree = category_tree(&state).await?;
+        (tree.expand(category), tree.resolve(category).map(|c| c.id))
+    };
+    let cached = category.is_empty() || category_id.is_some();
+
+    let filter = ArticleFilter {
+        source_id: params.source_id,
+        categories,
+    };
+    let response = if cached && state.cache.caches_news_page(page) {
+        let key = format!("{page}:{limit}:{:?}:{category_id:?}", params.source_id);
+        let load = || list_news(&state, page, limit, &filter);
+        state.cache.get_or_load(CacheRoute::News, key, load).await?
+    } else {
+        list_news(&state, page, limit, &filter).await?
+    };
+
+    Ok(Json(response))
+}
+
+/// Category tree for resolving filters, cached alongside `/api/categories`
+/// so category edits and syncs drop it too
+async fn category_tree(state: &AppState) -> ApiResult<Arc<CategoryTree>> {
+    let load = || async { Ok(Arc::new(CategoryTree::load(&state.db.pool).await?)) };
+    state
+        .cache
+        .get_or_load(CacheRoute::Categories, "tree".to_string(), load)
+        .await
+}
+
+async fn list_news(
+    state: &AppState,
+    page: i64,
+    limit: i64,
+    filter: &ArticleFilter,
+) -> ApiResult<ArticleListResponse> {
     let from = (page - 1) * limit;
 
-    let (articles, total) = state.search.get_articles(from, limit).await?;
+    let (articles, total) = state.search.get_articles(from, limit, filter).await?;
 
-    Ok(Json(ArticleListResponse::new(articles, total, page, limit)))
+    Ok(ArticleListResponse::new(articles, total, page, limit))
 }
 
 /// GET /api/news/:id - Get news detail by ID
@@ -0,0 +1,254 @@
+use axum::{
+    extract::{ConnectInfo, Path, Query, State},
+    Json,
+};
+use serde::Deserialize;
+use std::net::SocketAddr;
+use std::time::{Duration, Instant};
+
+use crate::{
+    auth::AuthUser,
+    docker::{ActionOutcome, ContainerAction},
+    error::{ApiError, ApiResult},
+    models::{ServiceAction, ServiceActionListResponse, ServiceDetail, ServiceInfo},
+    AppState,
+};
+
+const SERVICE_ACTION_COLUMNS: &str = "id, service_id, container, action, outcome, message, \
+     client_addr, actor, duration_ms, created_at";
+
+#[derive(Debug, Deserialize)]
+pub struct ServiceQuery {
+    /// Probe every service before answering instead of using cached results
+    #[serde(default)]
+    pub refresh: bool,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ServiceActionParams {
+    #[serde(default = "default_action_limit")]
+    pub limit: i64,
+}
+
+fn default_action_limit() -> i64 {
+    50
+}
+
+/// GET /api/admin/services - Registered services with their latest probe result
+pub async fn get_services(
+    State(state): State<AppState>,
+    Query(params): Query<ServiceQuery>,
+) -> ApiResult<Json<Vec<ServiceInfo>>> {
+    refresh(&state, params.refresh).await;
+
+    Ok(Json(state.services.list().await))
+}
+
+/// GET /api/admin/services/:id - Service detail with probe configuration and history
+pub async fn get_service(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Query(params): Query<ServiceQuery>,
+) -> ApiResult<Json<ServiceDetail>> {
+    if !state.services.contains(&id) {
+        return Err(ApiError::NotFound(format!("Service {id} not found")));
+    }
+
+    refresh(&state, params.refresh).await;
+
+    state
+        .services
+        .get(&id)
+        .await
+        .map(Json)
+        .ok_or_else(|| ApiError::NotFound(format!("Service {id} not found")))
+}
+
+async fn refresh(state: &AppState, force: bool) {
+    if force {
+        state.services.probe_all(&state.db, &state.search).await;
+    } else {
+        state.services.ensure_probed(&state.db, &state.search).await;
+    }
+}
+
+/// POST /api/admin/services/:id/start - Start the service's container
+pub async fn start_service(
+    State(state): State<AppState>,
+    ConnectInfo(client): ConnectInfo<SocketAddr>,
+    user: AuthUser,
+    Path(id): Path<String>,
+) -> ApiResult<Json<ServiceAction>> {
+    control(&state, client, &user, &id, ContainerAction::Start).await
+}
+
+/// POST /api/admin/services/:id/stop - Stop the service's container
+pub async fn stop_service(
+    State(state): State<AppState>,
+    ConnectInfo(client): ConnectInfo<SocketAddr>,
+    user: AuthUser,
+    Path(id): Path<String>,
+) -> ApiResult<Json<ServiceAction>> {
+    control(&state, client, &user, &id, ContainerAction::Stop).await
+}
+
+/// POST /api/admin/services/:id/restart - Restart the service's container
+pub async fn restart_service(
+    State(state): State<AppState>,
+    ConnectInfo(client): ConnectInfo<SocketAddr>,
+    user: AuthUser,
+    Path(id): Path<String>,
+) -> ApiResult<Json<ServiceAction>> {
+    control(&state, client, &user, &id, ContainerAction::Restart).await
+}
+
+/// GET /api/admin/services/:id/actions - Audit trail of lifecycle requests
+pub async fn get_service_actions(
+    State(state): State<AppState>,
+    Path(id): Path<String>,
+    Query(params): Query<ServiceActionParams>,
+) -> ApiResult<Json<ServiceActionListResponse>> {
+    let limit = params.limit.clamp(1, 500);
+
+    let actions: Vec<ServiceAction> = sqlx::query_as(&format!(
+        r#"
+        SELECT {SERVICE_ACTION_COLUMNS}
+        FROM service_actions
+        WHERE service_id = $1
+        ORDER BY created_at DESC, id DESC
+        LIMIT $2
+        "#
+    ))
+    .bind(&id)
+    .bind(limit)
+    .fetch_all(&state.db.pool)
+    .await?;
+
+    let total: i64 =
+        sqlx::query_scalar("SELECT COUNT(*) FROM service_actions WHERE service_id = $1")
+            .bind(&id)
+            .fetch_one(&state.db.pool)
+            .await?;
+
+    Ok(Json(ServiceActionListResponse { actions, total }))
+}
+
+/// Run a lifecycle action and record it, whether it is denied, fails or succeeds
+async fn control(
+    state: &AppState,
+    client: SocketAddr,
+    user: &AuthUser,
+    id: &str,
+    action: ContainerAction,
+) -> ApiResult<Json<ServiceAction>> {
+    let service = state
+        .services
+        .service(id)
+        .ok_or_else(|| ApiError::NotFound(format!("Service {id} not found")))?;
+    let container = service.container.clone();
+    let audit = Audit {
+        service_id: id,
+        container: container.as_deref(),
+        action,
+        client,
+        actor: &user.username,
+    };
+
+    let Some(container) = container.as_deref() else {
+        let message = format!("Service {id} has no container configured");
+        audit.record(state, "denied", Some(&message), Duration::ZERO).await?;
+        return Err(ApiError::Forbidden(message));
+    };
+    if !state.config.docker.is_allowed(container) {
+        let message = format!("Container {container} is not in docker.allowed_containers");
+        audit.record(state, "denied", Some(&message), Duration::ZERO).await?;
+        return Err(ApiError::Forbidden(message));
+    }
+
+    if action == ContainerAction::Restart {
+        state.services.set_restarting(id, true).await;
+    }
+    let started = Instant::now();
+    let result = state.docker.container_action(container, action).await;
+    let elapsed = started.elapsed();
+    if action == ContainerAction::Restart {
+        state.services.set_restarting(id, false).await;
+    }
+
+    // Refresh the cached status in the background; the container may still be booting
+    let probe_state = state.clone();
+    let probe_id = id.to_string();
+    tokio::spawn(async move {
+        probe_state
+            .services
+            .probe_one(&probe_id, &probe_state.db, &probe_state.search)
+            .await;
+    });
+
+    match result {
+        Ok(outcome) => {
+            let (outcome, message) = match outcome {
+                ActionOutcome::Done => ("succeeded", None),
+                ActionOutcome::Unchanged => (
+                    "unchanged",
+                    Some(format!("Container {container} was already in that state")),
+                ),
+            };
+            tracing::info!(
+                service = id,
+                container,
+                action = action.as_str(),
+                outcome,
+                "Container action completed"
+            );
+            let entry = audit.record(state, outcome, message.as_deref(), elapsed).await?;
+            Ok(Json(entry))
+        }
+        Err(e) => {
+            let message = e.to_string();
+            tracing::warn!(service = id, container, action = action.as_str(), "{message}");
+            audit.record(state, "failed", Some(&message), elapsed).await?;
+            Err(e)
+        }
+    }
+}
+
+struct Audit<'a> {
+    service_id: &'a str,
+    container: Option<&'a str>,
+    action: ContainerAction,
+    client: SocketAddr,
+    actor: &'a str,
+}
+
+impl Audit<'_> {
+    async fn record(
+        &self,
+        state: &AppState,
+        outcome: &str,
+        message: Option<&str>,
+        duration: Duration,
+    ) -> ApiResult<ServiceAction> {
+        let entry = sqlx::query_as(&format!(
+            r#"
+            INSERT INTO service_actions
+                (service_id, container, action, outcome, message, client_addr, actor,
+                 duration_ms)
+            VALUES ($1, $2, $3, $4, $5, $6, $7, $8)
+            RETURNING {SERVICE_ACTION_COLUMNS}
+            "#
+        ))
+        .bind(self.service_id)
+        .bind(self.container)
+        .bind(self.action.as_str())
+        .bind(outcome)
+        .bind(message)
+        .bind(self.client.ip().to_string())
+        .bind(self.actor)
+        .bind(i32::try_from(duration.as_millis()).unwrap_or(i32::MAX))
+        .fetch_one(&state.db.pool)
+        .await?;
+
+        Ok(entry)
+    }
+}
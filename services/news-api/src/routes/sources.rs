@@ -0,0 +1,148 @@
+use axum::{
+    extract::{Path, State},
+    http::StatusCode,
+    Json,
+};
+use validator::Validate;
+
+use crate::{
+    error::{ApiError, ApiResult},
+    models::{Source, SourceListResponse, SourceRequest},
+    AppState,
+};
+
+const SOURCE_COLUMNS: &str = "id, name, homepage, feed_urls, language, default_category, \
+     crawl_interval_minutes, enabled, created_at, updated_at";
+
+/// GET /api/admin/sources - List registered sources
+pub async fn get_sources(State(state): State<AppState>) -> ApiResult<Json<SourceListResponse>> {
+    let sources: Vec<Source> =
+        sqlx::query_as(&format!("SELECT {SOURCE_COLUMNS} FROM sources ORDER BY name"))
+            .fetch_all(&state.db.pool)
+            .await?;
+
+    let total = sources.len() as i64;
+
+    Ok(Json(SourceListResponse { sources, total }))
+}
+
+/// GET /api/admin/sources/:id - Get a source by ID
+pub async fn get_source(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+) -> ApiResult<Json<Source>> {
+    let source: Option<Source> =
+        sqlx::query_as(&format!("SELECT {SOURCE_COLUMNS} FROM sources WHERE id = $1"))
+            .bind(id)
+            .fetch_optional(&state.db.pool)
+            .await?;
+
+    source
+        .map(Json)
+        .ok_or_else(|| ApiError::NotFound(format!("Source with id {id} not found")))
+}
+
+/// POST /api/admin/sources - Register a new source
+pub async fn create_source(
+    State(state): State<AppState>,
+    Json(req): Json<SourceRequest>,
+) -> ApiResult<(StatusCode, Json<Source>)> {
+    req.validate()?;
+
+    let source: Source = sqlx::query_as(&format!(
+        r#"
+        INSERT INTO sources
+            (name, homepage, feed_urls, language, default_category, crawl_interval_minutes, enabled)
+        VALUES ($1, $2, $3, $4, $5, $6, $7)
+        RETURNING {SOURCE_COLUMNS}
+        "#
+    ))
+    .bind(&req.name)
+    .bind(&req.homepage)
+    .bind(&req.feed_urls)
+    .bind(&req.language)
+    .bind(&req.default_category)
+    .bind(req.crawl_interval_minutes)
+    .bind(req.enabled)
+    .fetch_one(&state.db.pool)
+    .await
+    .map_err(|e| unique_violation(e, &req.name))?;
+
+    sync_source_pipeline(&state).await;
+
+    Ok((StatusCode::CREATED, Json(source)))
+}
+
+/// PUT /api/admin/sources/:id - Replace a source
+pub async fn update_source(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+    Json(req): Json<SourceRequest>,
+) -> ApiResult<Json<Source>> {
+    req.validate()?;
+
+    let source: Option<Source> = sqlx::query_as(&format!(
+        r#"
+        UPDATE sources
+        SET name = $2, homepage = $3, feed_urls = $4, language = $5, default_category = $6,
+            crawl_interval_minutes = $7, enabled = $8, updated_at = NOW()
+        WHERE id = $1
+        RETURNING {SOURCE_COLUMNS}
+        "#
+    ))
+    .bind(id)
+    .bind(&req.name)
+    .bind(&req.homepage)
+    .bind(&req.feed_urls)
+    .bind(&req.language)
+    .bind(&req.default_category)
+    .bind(req.crawl_interval_minutes)
+    .bind(req.enabled)
+    .fetch_optional(&state.db.pool)
+    .await
+    .map_err(|e| unique_violation(e, &req.name))?;
+
+    let source =
+        source.ok_or_else(|| ApiError::NotFound(format!("Source with id {id} not found")))?;
+    sync_source_pipeline(&state).await;
+
+    Ok(Json(source))
+}
+
+/// DELETE /api/admin/sources/:id - Remove a source
+pub async fn delete_source(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+) -> ApiResult<StatusCode> {
+    let result = sqlx::query("DELETE FROM sources WHERE id = $1")
+        .bind(id)
+        .execute(&state.db.pool)
+        .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(ApiError::NotFound(format!("Source with id {id} not found")));
+    }
+
+    sync_source_pipeline(&state).await;
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// Update the ingest pipeline that gives crawled articles their `source_id`
+///
+/// The source change itself is saved either way; a failure only means new
+/// articles of the source stay without an ID until the next update.
+async fn sync_source_pipeline(state: &AppState) {
+    if let Err(e) = state.search.sync_source_pipeline(&state.db).await {
+        tracing::warn!("Failed to update the source pipeline: {e}");
+    }
+}
+
+fn unique_violation(e: sqlx::Error, name: &str) -> ApiError {
+    match &e {
+        sqlx::Error::Database(db) if db.is_unique_violation() => {
+            ApiError::Conflict(format!("Source named {name} already exists"))
+        }
+        _ => ApiError::Database(e),
+    }
+}
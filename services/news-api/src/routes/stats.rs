@@ -1,113 +1,276 @@
-use axum::{extract::State, Json};
+use axum::{
+    extract::{Query, State},
+    Json,
+};
+use chrono::{Duration, NaiveDate, Utc};
+use serde::Deserialize;
 use std::collections::HashMap;
 
 use crate::{
-    error::ApiResult,
-    models::{DailyCount, DailyCrawlStats, DailyStatsResponse, HourlyCount, StatsResponse, SystemStatusResponse},
-    AppState,
+    db,
+    error::{ApiError, ApiResult},
+    host_metrics,
+    models::{
+        BreakdownResponse, BreakdownSeries, DailyCount, DailyCrawlStats, DailyStatsResponse,
+        HealthStatus, HourlyCount, StatsResponse, SystemStatusResponse, TimeSeriesPoint,
+    },
+    response_cache::CacheRoute,
+    status, AppState,
 };
 
+#[derive(Debug, Clone, Copy, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum BreakdownBy {
+    Category,
+    Source,
+}
+
+impl BreakdownBy {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Category => "category",
+            Self::Source => "source",
+        }
+    }
+
+    /// Index field the series are split on; sources by their stable ID
+    fn field(self) -> &'static str {
+        match self {
+            Self::Category => "category",
+            Self::Source => "source_id",
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum StatsInterval {
+    Hour,
+    #[default]
+    Day,
+    Week,
+    Month,
+}
+
+impl StatsInterval {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Hour => "hour",
+            Self::Day => "day",
+            Self::Week => "week",
+            Self::Month => "month",
+        }
+    }
+}
+
+#[derive(Debug, Deserialize)]
+pub struct BreakdownParams {
+    pub by: BreakdownBy,
+    #[serde(default)]
+    pub interval: StatsInterval,
+    #[serde(default = "default_breakdown_days")]
+    pub days: i64,
+    pub category: Option<String>,
+    pub source: Option<String>,
+    pub source_id: Option<i32>,
+}
+
+fn default_breakdown_days() -> i64 {
+    30
+}
+
+const MAX_DAILY_WINDOW_DAYS: i64 = 366;
+
+#[derive(Debug, Deserialize)]
+pub struct DailyStatsParams {
+    #[serde(default = "default_daily_days")]
+    pub days: i64,
+    pub from: Option<NaiveDate>,
+    pub to: Option<NaiveDate>,
+}
+
+fn default_daily_days() -> i64 {
+    30
+}
+
 /// GET /api/stats - Dashboard statistics from OpenSearch aggregations
 pub async fn get_stats(State(state): State<AppState>) -> ApiResult<Json<StatsResponse>> {
-    let body = state.search.get_dashboard_stats().await?;
+    let load = || load_stats(&state);
+    let stats = state.cache.get_or_load(CacheRoute::Stats, String::new(), load).await?;
+
+    Ok(Json(stats))
+}
+
+async fn load_stats(state: &AppState) -> ApiResult<StatsResponse> {
+    let response = state.search.get_dashboard_stats().await?;
+    let aggregations = response.aggregations();
 
     // Total articles
-    let total_articles = body["hits"]["total"]["value"].as_i64().unwrap_or(0);
+    let total_articles = response.total_hits();
 
     // Today's articles
-    let today_articles = body["aggregations"]["today_articles"]["doc_count"]
-        .as_i64()
-        .unwrap_or(0);
+    let today_articles = aggregations.single("today_articles")?.doc_count;
 
     // Categories
-    let mut categories = HashMap::new();
-    if let Some(buckets) = body["aggregations"]["categories"]["buckets"].as_array() {
-        for bucket in buckets {
-            if let (Some(key), Some(count)) = (bucket["key"].as_str(), bucket["doc_count"].as_i64())
-            {
-                if !key.is_empty() {
-                    categories.insert(key.to_string(), count);
-                }
-            }
-        }
-    }
+    let categories: HashMap<String, i64> = aggregations
+        .buckets("categories")?
+        .into_iter()
+        .filter(|bucket| !bucket.key.is_empty())
+        .map(|bucket| (bucket.key, bucket.doc_count))
+        .collect();
 
     // Publishers
-    let mut publishers = HashMap::new();
-    if let Some(buckets) = body["aggregations"]["publishers"]["buckets"].as_array() {
-        for bucket in buckets {
-            if let (Some(key), Some(count)) = (bucket["key"].as_str(), bucket["doc_count"].as_i64())
-            {
-                if !key.is_empty() {
-                    publishers.insert(key.to_string(), count);
-                }
-            }
-        }
-    }
+    let publishers: HashMap<String, i64> = aggregations
+        .buckets("publishers")?
+        .into_iter()
+        .filter(|bucket| !bucket.key.is_empty())
+        .map(|bucket| (bucket.key, bucket.doc_count))
+        .collect();
 
     // Daily counts (last 30 days)
-    let mut daily_counts = Vec::new();
-    if let Some(buckets) = body["aggregations"]["daily_counts"]["buckets"].as_array() {
-        for bucket in buckets.iter().take(30) {
-            if let (Some(date), Some(count)) =
-                (bucket["key_as_string"].as_str(), bucket["doc_count"].as_i64())
-            {
-                daily_counts.push(DailyCount {
-                    date: date.to_string(),
-                    count,
-                });
-            }
-        }
-    }
+    let daily_counts = aggregations
+        .buckets("daily_counts")?
+        .into_iter()
+        .take(30)
+        .map(|bucket| DailyCount {
+            date: bucket.key,
+            count: bucket.doc_count,
+        })
+        .collect();
 
     // Hourly counts (last 24h)
-    let mut hourly_counts = Vec::new();
-    if let Some(buckets) = body["aggregations"]["hourly_counts"]["hours"]["buckets"].as_array() {
-        for bucket in buckets {
-            if let (Some(hour), Some(count)) =
-                (bucket["key_as_string"].as_str(), bucket["doc_count"].as_i64())
-            {
-                hourly_counts.push(HourlyCount {
-                    hour: hour.to_string(),
-                    count,
-                });
-            }
-        }
-    }
+    let hourly_counts = aggregations
+        .single("hourly_counts")?
+        .aggregations
+        .buckets("hours")?
+        .into_iter()
+        .map(|bucket| HourlyCount {
+            hour: bucket.key,
+            count: bucket.doc_count,
+        })
+        .collect();
 
-    Ok(Json(StatsResponse {
+    // Crawl outcomes over the default daily stats window
+    let today = Utc::now().date_naive();
+    let from = today - Duration::days(default_daily_days() - 1);
+    let (total_crawled, success_count): (i64, i64) = sqlx::query_as(
+        r#"
+        SELECT COALESCE(SUM(total_crawled), 0)::BIGINT,
+               COALESCE(SUM(success_count), 0)::BIGINT
+        FROM crawl_stats
+        WHERE date BETWEEN $1 AND $2
+        "#,
+    )
+    .bind(from)
+    .bind(today)
+    .fetch_one(&state.db.pool)
+    .await?;
+    let total_crawled_today = crawled_on(state, today).await?;
+
+    Ok(StatsResponse {
         total_articles,
         today_articles,
+        total_crawled_today,
+        success_rate: success_rate(success_count, total_crawled),
         categories,
         publishers,
         hourly_counts,
         daily_counts,
+    })
+}
+
+/// Articles crawled on `date`, failures included
+async fn crawled_on(state: &AppState, date: NaiveDate) -> ApiResult<i64> {
+    let total = sqlx::query_scalar(
+        "SELECT COALESCE(SUM(total_crawled), 0)::BIGINT FROM crawl_stats WHERE date = $1",
+    )
+    .bind(date)
+    .fetch_one(&state.db.pool)
+    .await?;
+
+    Ok(total)
+}
+
+/// Percentage of crawled articles that succeeded
+fn success_rate(success_count: i64, total_crawled: i64) -> f64 {
+    if total_crawled > 0 {
+        success_count as f64 / total_crawled as f64 * 100.0
+    } else {
+        0.0
+    }
+}
+
+/// GET /api/stats/breakdown?by=category|source&interval=day - Per-key time series
+pub async fn get_stats_breakdown(
+    State(state): State<AppState>,
+    Query(params): Query<BreakdownParams>,
+) -> ApiResult<Json<BreakdownResponse>> {
+    let days = params.days.clamp(1, 365);
+
+    let source_id = params.source_id.map(|id| id.to_string());
+
+    let mut filters = Vec::new();
+    if let Some(category) = params.category.as_deref().filter(|c| !c.is_empty()) {
+        filters.push(("category", category));
+    }
+    if let Some(source) = params.source.as_deref().filter(|s| !s.is_empty()) {
+        filters.push(("source", source));
+    }
+    if let Some(source_id) = source_id.as_deref() {
+        filters.push(("source_id", source_id));
+    }
+
+    let source_names: HashMap<String, String> = match params.by {
+        BreakdownBy::Source => db::source_ids(&state.db.pool)
+            .await?
+            .into_iter()
+            .map(|(name, id)| (id.to_string(), name))
+            .collect(),
+        BreakdownBy::Category => HashMap::new(),
+    };
+
+    let series = state
+        .search
+        .get_breakdown(params.by.field(), params.interval.as_str(), days, &filters)
+        .await?
+        .into_iter()
+        .map(|s| BreakdownSeries {
+            name: source_names.get(&s.key).cloned(),
+            key: s.key,
+            total: s.total,
+            points: s
+                .points
+                .into_iter()
+                .map(|(timestamp, count)| TimeSeriesPoint { timestamp, count })
+                .collect(),
+        })
+        .collect();
+
+    Ok(Json(BreakdownResponse {
+        by: params.by.as_str().to_string(),
+        interval: params.interval.as_str().to_string(),
+        days,
+        series,
     }))
 }
 
 /// GET /api/status - System status
 pub async fn get_status(State(state): State<AppState>) -> ApiResult<Json<SystemStatusResponse>> {
-    // Check database
-    let db_status = if sqlx::query("SELECT 1")
-        .fetch_one(&state.db.pool)
-        .await
-        .is_ok()
-    {
-        "healthy"
-    } else {
-        "unhealthy"
+    let (database, llm, root) = tokio::join!(
+        status::check_database(&state.db, state.config.database.max_connections),
+        status::check_llm(&state.config.llm),
+        host_metrics::filesystem_usage("/"),
+    );
+
+    let db_status = match database.status {
+        HealthStatus::Down => "unhealthy",
+        _ => "healthy",
     };
 
-    // Check LLM (vLLM on port 8002)
-    let llm_status = match reqwest::Client::new()
-        .get("http://baram-vllm:8002/health")
-        .timeout(std::time::Duration::from_secs(3))
-        .send()
-        .await
-    {
-        Ok(resp) if resp.status().is_success() => "healthy",
-        Ok(_) => "unhealthy",
-        Err(_) => "unavailable",
+    let llm_status = match llm.status {
+        HealthStatus::Healthy => "healthy",
+        HealthStatus::Degraded => "unhealthy",
+        HealthStatus::Down => "unavailable",
     };
 
     // Get uptime from /proc/uptime
@@ -121,63 +284,111 @@ pub async fn get_status(State(state): State<AppState>) -> ApiResult<Json<SystemS
         Err(_) => 0,
     };
 
-    // Disk usage: check /app or root filesystem
-    let disk_usage = get_disk_usage().await;
-
     Ok(Json(SystemStatusResponse {
         database: db_status.to_string(),
         llm: llm_status.to_string(),
-        disk_usage,
+        disk_usage: root.map(|fs| fs.percentage()).unwrap_or(0.0),
         uptime,
     }))
 }
 
-async fn get_disk_usage() -> f64 {
-    // Use statvfs-like approach via df command
-    match tokio::process::Command::new("df")
-        .args(["--output=pcent", "/"])
-        .output()
-        .await
-    {
-        Ok(output) => {
-            let text = String::from_utf8_lossy(&output.stdout);
-            text.lines()
-                .nth(1)
-                .and_then(|line| line.trim().trim_end_matches('%').parse::<f64>().ok())
-                .unwrap_or(0.0)
-        }
-        Err(_) => 0.0,
-    }
-}
-
-/// GET /api/stats/daily - Daily crawling stats from PostgreSQL
+/// GET /api/stats/daily?days=30 | ?from=&to= - Daily crawling stats from PostgreSQL
+///
+/// Dates without a `crawl_stats` row are returned as zero rows so charts get a
+/// contiguous series.
 pub async fn get_daily_stats(
     State(state): State<AppState>,
+    Query(params): Query<DailyStatsParams>,
 ) -> ApiResult<Json<DailyStatsResponse>> {
-    let stats: Vec<crate::models::CrawlStats> = sqlx::query_as(
+    let today = Utc::now().date_naive();
+    let (from, to) = daily_window(&params, today)?;
+
+    let key = format!("{from}:{to}:{today}");
+    let load = || load_daily_stats(&state, from, to, today);
+    let stats = state.cache.get_or_load(CacheRoute::DailyStats, key, load).await?;
+
+    Ok(Json(stats))
+}
+
+async fn load_daily_stats(
+    state: &AppState,
+    from: NaiveDate,
+    to: NaiveDate,
+    today: NaiveDate,
+) -> ApiResult<DailyStatsResponse> {
+    let rows: Vec<DailyCrawlStats> = sqlx::query_as(
         r#"
-        SELECT id, date, total_crawled, success_count, failed_count, created_at
+        SELECT date,
+               SUM(total_crawled)::INT AS total_crawled,
+               SUM(success_count)::INT AS success_count,
+               SUM(failed_count)::INT AS failed_count
         FROM crawl_stats
-        ORDER BY date DESC
+        WHERE date BETWEEN $1 AND $2
+        GROUP BY date
         "#,
     )
+    .bind(from)
+    .bind(to)
     .fetch_all(&state.db.pool)
     .await?;
 
-    let total_days = stats.len() as i64;
+    let total_crawled_today = crawled_on(state, today).await?;
 
-    let daily_stats: Vec<DailyCrawlStats> = stats
-        .into_iter()
-        .map(|s| DailyCrawlStats {
-            date: s.date,
-            total_crawled: s.total_crawled,
-            success_count: s.success_count,
-            failed_count: s.failed_count,
+    let mut by_date: HashMap<NaiveDate, DailyCrawlStats> =
+        rows.into_iter().map(|row| (row.date, row)).collect();
+
+    let stats: Vec<DailyCrawlStats> = to
+        .iter_days()
+        .rev()
+        .take_while(|date| *date >= from)
+        .map(|date| {
+            by_date.remove(&date).unwrap_or(DailyCrawlStats {
+                date,
+                total_crawled: 0,
+                success_count: 0,
+                failed_count: 0,
+            })
         })
         .collect();
 
-    Ok(Json(DailyStatsResponse {
-        stats: daily_stats,
-        total_days,
-    }))
+    let total_crawled: i64 = stats.iter().map(|s| s.total_crawled as i64).sum();
+    let success_count: i64 = stats.iter().map(|s| s.success_count as i64).sum();
+    let failed_count: i64 = stats.iter().map(|s| s.failed_count as i64).sum();
+
+    Ok(DailyStatsResponse {
+        total_days: stats.len() as i64,
+        stats,
+        from,
+        to,
+        total_crawled,
+        success_count,
+        failed_count,
+        success_rate: success_rate(success_count, total_crawled),
+        total_crawled_today,
+    })
+}
+
+/// Resolve the inclusive date window of a daily stats request
+///
+/// `to` defaults to today and `from` to `days - 1` days before `to`.
+fn daily_window(
+    params: &DailyStatsParams,
+    today: NaiveDate,
+) -> ApiResult<(NaiveDate, NaiveDate)> {
+    let days = params.days.clamp(1, MAX_DAILY_WINDOW_DAYS);
+    let to = params.to.unwrap_or(today);
+    let from = params.from.unwrap_or(to - Duration::days(days - 1));
+
+    if from > to {
+        return Err(ApiError::BadRequest(format!(
+            "`from` ({from}) must not be after `to` ({to})"
+        )));
+    }
+    if (to - from).num_days() >= MAX_DAILY_WINDOW_DAYS {
+        return Err(ApiError::BadRequest(format!(
+            "Date window cannot exceed {MAX_DAILY_WINDOW_DAYS} days"
+        )));
+    }
+
+    Ok((from, to))
 }
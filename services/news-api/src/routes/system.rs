@@ -0,0 +1,90 @@
+use axum::{extract::State, Json};
+use chrono::Utc;
+use std::time::Duration;
+
+use crate::{
+    error::{ApiError, ApiResult},
+    host_metrics::{self, FilesystemUsage},
+    models::{AdminStatusResponse, CpuMetrics, FilesystemMetrics, ResourceUsage, SystemMetrics},
+    status, AppState,
+};
+
+/// Window over which CPU usage is measured
+const CPU_SAMPLE_WINDOW: Duration = Duration::from_millis(250);
+
+const BYTES_PER_GB: f64 = 1024.0 * 1024.0 * 1024.0;
+
+/// GET /api/admin/status - Service health and host resource usage
+pub async fn get_admin_status(
+    State(state): State<AppState>,
+) -> ApiResult<Json<AdminStatusResponse>> {
+    let api = status::check_api(state.started_at);
+
+    // Probes run while the CPU sample window elapses
+    let (database, opensearch, llm, metrics) = tokio::join!(
+        status::check_database(&state.db, state.config.database.max_connections),
+        status::check_opensearch(&state.search),
+        status::check_llm(&state.config.llm),
+        system_metrics(),
+    );
+
+    let overall = status::overall(&[&api, &database, &opensearch], &[&llm]);
+
+    Ok(Json(AdminStatusResponse {
+        overall,
+        services: vec![api, database, opensearch, llm],
+        metrics: metrics?,
+        timestamp: Utc::now(),
+    }))
+}
+
+async fn system_metrics() -> ApiResult<SystemMetrics> {
+    let (cpu, memory, root, filesystems) = tokio::join!(
+        host_metrics::cpu_usage(CPU_SAMPLE_WINDOW),
+        host_metrics::memory_usage(),
+        host_metrics::filesystem_usage("/"),
+        host_metrics::filesystems(),
+    );
+
+    let cpu = cpu.map_err(|e| metrics_error("CPU", e))?;
+    let memory = memory.map_err(|e| metrics_error("memory", e))?;
+    let root = root.map_err(|e| metrics_error("disk", e))?;
+    let filesystems = filesystems.map_err(|e| metrics_error("mount", e))?;
+
+    Ok(SystemMetrics {
+        cpu: CpuMetrics {
+            usage: cpu.percentage,
+            cores: cpu.cores,
+        },
+        memory: ResourceUsage {
+            used: memory.used_bytes() as f64 / BYTES_PER_GB,
+            total: memory.total_bytes as f64 / BYTES_PER_GB,
+            percentage: if memory.total_bytes == 0 {
+                0.0
+            } else {
+                memory.used_bytes() as f64 / memory.total_bytes as f64 * 100.0
+            },
+        },
+        disk: ResourceUsage {
+            used: root.used_bytes as f64 / BYTES_PER_GB,
+            total: root.total_bytes as f64 / BYTES_PER_GB,
+            percentage: root.percentage(),
+        },
+        filesystems: filesystems.iter().map(filesystem_metrics).collect(),
+    })
+}
+
+fn filesystem_metrics(fs: &FilesystemUsage) -> FilesystemMetrics {
+    FilesystemMetrics {
+        mount_point: fs.mount_point.clone(),
+        device: fs.device.clone(),
+        fs_type: fs.fs_type.clone(),
+        used: fs.used_bytes as f64 / BYTES_PER_GB,
+        total: fs.total_bytes as f64 / BYTES_PER_GB,
+        percentage: fs.percentage(),
+    }
+}
+
+fn metrics_error(kind: &str, error: std::io::Error) -> ApiError {
+    ApiError::InternalServerError(format!("Failed to read {kind} metrics: {error}"))
+}
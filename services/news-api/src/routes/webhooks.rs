@@ -0,0 +1,205 @@
+use axum::{
+    extract::{Path, Query, State},
+    http::StatusCode,
+    Json,
+};
+use serde::Deserialize;
+use validator::Validate;
+
+use crate::{
+    error::{ApiError, ApiResult},
+    models::{
+        Webhook, WebhookDelivery, WebhookDeliveryListResponse, WebhookListResponse,
+        WebhookRequest,
+    },
+    webhooks::WEBHOOK_DELIVERY_COLUMNS,
+    AppState,
+};
+
+const WEBHOOK_COLUMNS: &str = "id, name, url, format, secret IS NOT NULL AS signed, events, \
+     enabled, created_at, updated_at";
+
+#[derive(Debug, Deserialize)]
+pub struct DeliveryParams {
+    /// `pending`, `succeeded` or `failed`
+    pub status: Option<String>,
+    #[serde(default = "default_delivery_limit")]
+    pub limit: i64,
+}
+
+fn default_delivery_limit() -> i64 {
+    50
+}
+
+/// GET /api/admin/webhooks - List webhooks
+pub async fn get_webhooks(State(state): State<AppState>) -> ApiResult<Json<WebhookListResponse>> {
+    let webhooks: Vec<Webhook> =
+        sqlx::query_as(&format!("SELECT {WEBHOOK_COLUMNS} FROM webhooks ORDER BY name"))
+            .fetch_all(&state.db.pool)
+            .await?;
+
+    let total = webhooks.len() as i64;
+
+    Ok(Json(WebhookListResponse { webhooks, total }))
+}
+
+/// GET /api/admin/webhooks/:id - Get a webhook by ID
+pub async fn get_webhook(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+) -> ApiResult<Json<Webhook>> {
+    let webhook: Option<Webhook> =
+        sqlx::query_as(&format!("SELECT {WEBHOOK_COLUMNS} FROM webhooks WHERE id = $1"))
+            .bind(id)
+            .fetch_optional(&state.db.pool)
+            .await?;
+
+    webhook
+        .map(Json)
+        .ok_or_else(|| ApiError::NotFound(format!("Webhook with id {id} not found")))
+}
+
+/// POST /api/admin/webhooks - Register a webhook
+pub async fn create_webhook(
+    State(state): State<AppState>,
+    Json(req): Json<WebhookRequest>,
+) -> ApiResult<(StatusCode, Json<Webhook>)> {
+    req.validate()?;
+
+    let webhook: Webhook = sqlx::query_as(&format!(
+        r#"
+        INSERT INTO webhooks (name, url, format, secret, events, enabled)
+        VALUES ($1, $2, $3, NULLIF($4, ''), $5, $6)
+        RETURNING {WEBHOOK_COLUMNS}
+        "#
+    ))
+    .bind(&req.name)
+    .bind(&req.url)
+    .bind(req.format.as_str())
+    .bind(&req.secret)
+    .bind(&req.events)
+    .bind(req.enabled)
+    .fetch_one(&state.db.pool)
+    .await
+    .map_err(|e| unique_violation(e, &req.name))?;
+
+    Ok((StatusCode::CREATED, Json(webhook)))
+}
+
+/// PUT /api/admin/webhooks/:id - Replace a webhook
+///
+/// An omitted `secret` keeps the current one; an empty one removes it.
+pub async fn update_webhook(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+    Json(req): Json<WebhookRequest>,
+) -> ApiResult<Json<Webhook>> {
+    req.validate()?;
+
+    let webhook: Option<Webhook> = sqlx::query_as(&format!(
+        r#"
+        UPDATE webhooks
+        SET name = $2, url = $3, format = $4,
+            secret = CASE WHEN $5::TEXT IS NULL THEN secret ELSE NULLIF($5, '') END,
+            events = $6, enabled = $7, updated_at = NOW()
+        WHERE id = $1
+        RETURNING {WEBHOOK_COLUMNS}
+        "#
+    ))
+    .bind(id)
+    .bind(&req.name)
+    .bind(&req.url)
+    .bind(req.format.as_str())
+    .bind(&req.secret)
+    .bind(&req.events)
+    .bind(req.enabled)
+    .fetch_optional(&state.db.pool)
+    .await
+    .map_err(|e| unique_violation(e, &req.name))?;
+
+    webhook
+        .map(Json)
+        .ok_or_else(|| ApiError::NotFound(format!("Webhook with id {id} not found")))
+}
+
+/// DELETE /api/admin/webhooks/:id - Remove a webhook and its delivery log
+pub async fn delete_webhook(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+) -> ApiResult<StatusCode> {
+    let result = sqlx::query("DELETE FROM webhooks WHERE id = $1")
+        .bind(id)
+        .execute(&state.db.pool)
+        .await?;
+
+    if result.rows_affected() == 0 {
+        return Err(ApiError::NotFound(format!("Webhook with id {id} not found")));
+    }
+
+    Ok(StatusCode::NO_CONTENT)
+}
+
+/// POST /api/admin/webhooks/:id/test - Send a sample notification now
+pub async fn test_webhook(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+) -> ApiResult<Json<WebhookDelivery>> {
+    let delivery = state.webhooks.send_test(&state.db, id).await?;
+
+    Ok(Json(delivery))
+}
+
+/// GET /api/admin/webhooks/:id/deliveries - Delivery log, newest first
+pub async fn get_webhook_deliveries(
+    State(state): State<AppState>,
+    Path(id): Path<i32>,
+    Query(params): Query<DeliveryParams>,
+) -> ApiResult<Json<WebhookDeliveryListResponse>> {
+    let limit = params.limit.clamp(1, 500);
+
+    let exists: bool = sqlx::query_scalar("SELECT EXISTS (SELECT 1 FROM webhooks WHERE id = $1)")
+        .bind(id)
+        .fetch_one(&state.db.pool)
+        .await?;
+    if !exists {
+        return Err(ApiError::NotFound(format!("Webhook with id {id} not found")));
+    }
+
+    let deliveries: Vec<WebhookDelivery> = sqlx::query_as(&format!(
+        r#"
+        SELECT {WEBHOOK_DELIVERY_COLUMNS}
+        FROM webhook_deliveries
+        WHERE webhook_id = $1 AND ($2::VARCHAR IS NULL OR status = $2)
+        ORDER BY created_at DESC, id DESC
+        LIMIT $3
+        "#
+    ))
+    .bind(id)
+    .bind(&params.status)
+    .bind(limit)
+    .fetch_all(&state.db.pool)
+    .await?;
+
+    let total: i64 = sqlx::query_scalar(
+        r#"
+        SELECT COUNT(*)
+        FROM webhook_deliveries
+        WHERE webhook_id = $1 AND ($2::VARCHAR IS NULL OR status = $2)
+        "#,
+    )
+    .bind(id)
+    .bind(&params.status)
+    .fetch_one(&state.db.pool)
+    .await?;
+
+    Ok(Json(WebhookDeliveryListResponse { deliveries, total }))
+}
+
+fn unique_violation(e: sqlx::Error, name: &str) -> ApiError {
+    match &e {
+        sqlx::Error::Database(db) if db.is_unique_violation() => {
+            ApiError::Conflict(format!("Webhook named {name} already exists"))
+        }
+        _ => ApiError::Database(e),
+    }
+}
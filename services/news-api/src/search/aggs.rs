@@ -0,0 +1,463 @@
+//! Typed OpenSearch aggregation builder and response reader.
+//!
+//! Requests are assembled from [`Aggregation`] values instead of hand-written
+//! JSON, and responses are read through [`AggregationResponse`], which turns a
+//! missing or malformed aggregation into an `ApiError::Search` naming the
+//! aggregation path (e.g. `hourly_counts.hours`).
+
+use serde::Deserialize;
+use serde_json::{json, Map, Value};
+
+use crate::error::{ApiError, ApiResult};
+
+#[derive(Debug, Clone, Copy)]
+pub enum SortOrder {
+    Asc,
+    Desc,
+}
+
+impl SortOrder {
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Asc => "asc",
+            Self::Desc => "desc",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+enum AggregationKind {
+    Terms {
+        field: String,
+        size: i64,
+    },
+    Filter {
+        query: Value,
+    },
+    DateHistogram {
+        field: String,
+        interval: String,
+        format: Option<String>,
+        min_doc_count: Option<i64>,
+        extended_bounds: Option<(String, String)>,
+        order: Option<SortOrder>,
+    },
+}
+
+/// A single aggregation with optional named sub-aggregations
+#[derive(Debug, Clone)]
+pub struct Aggregation {
+    kind: AggregationKind,
+    subs: Vec<(String, Aggregation)>,
+}
+
+impl Aggregation {
+    fn new(kind: AggregationKind) -> Self {
+        Self {
+            kind,
+            subs: Vec::new(),
+        }
+    }
+
+    /// `terms` bucket aggregation on a keyword field
+    pub fn terms(field: &str, size: i64) -> Self {
+        Self::new(AggregationKind::Terms {
+            field: field.to_string(),
+            size,
+        })
+    }
+
+    /// Single-bucket `filter` aggregation
+    pub fn filter(query: Value) -> Self {
+        Self::new(AggregationKind::Filter { query })
+    }
+
+    /// `date_histogram` aggregation with a calendar interval (`hour`, `day`, ...)
+    pub fn date_histogram(field: &str, interval: &str) -> Self {
+        Self::new(AggregationKind::DateHistogram {
+            field: field.to_string(),
+            interval: interval.to_string(),
+            format: None,
+            min_doc_count: None,
+            extended_bounds: None,
+            order: None,
+        })
+    }
+
+    /// Key format of a date_histogram (ignored for other kinds)
+    pub fn format(mut self, value: &str) -> Self {
+        if let AggregationKind::DateHistogram { format, .. } = &mut self.kind {
+            *format = Some(value.to_string());
+        }
+        self
+    }
+
+    /// Minimum bucket size of a date_histogram; `0` fills empty intervals
+    pub fn min_doc_count(mut self, value: i64) -> Self {
+        if let AggregationKind::DateHistogram { min_doc_count, .. } = &mut self.kind {
+            *min_doc_count = Some(value);
+        }
+        self
+    }
+
+    /// Range a date_histogram fills with empty buckets; `min`/`max` accept date math
+    pub fn extended_bounds(mut self, min: &str, max: &str) -> Self {
+        if let AggregationKind::DateHistogram {
+            extended_bounds, ..
+        } = &mut self.kind
+        {
+            *extended_bounds = Some((min.to_string(), max.to_string()));
+        }
+        self
+    }
+
+    /// Bucket order by key of a date_histogram
+    pub fn order_by_key(mut self, value: SortOrder) -> Self {
+        if let AggregationKind::DateHistogram { order, .. } = &mut self.kind {
+            *order = Some(value);
+        }
+        self
+    }
+
+    /// Attach a named sub-aggregation
+    pub fn sub(mut self, name: &str, aggregation: Aggregation) -> Self {
+        self.subs.push((name.to_string(), aggregation));
+        self
+    }
+
+    fn to_json(&self) -> Value {
+        let mut body = match &self.kind {
+            AggregationKind::Terms { field, size } => json!({
+                "terms": { "field": field, "size": size }
+            }),
+            AggregationKind::Filter { query } => json!({ "filter": query }),
+            AggregationKind::DateHistogram {
+                field,
+                interval,
+                format,
+                min_doc_count,
+                extended_bounds,
+                order,
+            } => {
+                let mut params = Map::new();
+                params.insert("field".into(), json!(field));
+                params.insert("calendar_interval".into(), json!(interval));
+                if let Some(format) = format {
+                    params.insert("format".into(), json!(format));
+                }
+                if let Some(min_doc_count) = min_doc_count {
+                    params.insert("min_doc_count".into(), json!(min_doc_count));
+                }
+                if let Some((min, max)) = extended_bounds {
+                    params.insert("extended_bounds".into(), json!({ "min": min, "max": max }));
+                }
+                if let Some(order) = order {
+                    params.insert("order".into(), json!({ "_key": order.as_str() }));
+                }
+                json!({ "date_histogram": params })
+            }
+        };
+
+        if !self.subs.is_empty() {
+            body["aggs"] = aggregations_json(&self.subs);
+        }
+
+        body
+    }
+}
+
+/// Build the `aggs` object of a search request from named aggregations
+pub fn aggregations_json(aggregations: &[(String, Aggregation)]) -> Value {
+    let map: Map<String, Value> = aggregations
+        .iter()
+        .map(|(name, aggregation)| (name.clone(), aggregation.to_json()))
+        .collect();
+    Value::Object(map)
+}
+
+#[derive(Debug, Deserialize)]
+struct ResponseHits {
+    total: ResponseTotal,
+}
+
+#[derive(Debug, Deserialize)]
+struct ResponseTotal {
+    value: i64,
+}
+
+/// Search response of an aggregation query
+#[derive(Debug, Deserialize)]
+pub struct AggregationResponse {
+    hits: ResponseHits,
+    #[serde(default)]
+    aggregations: Map<String, Value>,
+}
+
+impl AggregationResponse {
+    /// Parse a raw response body, reporting malformed JSON as a search error
+    pub fn from_value(value: Value) -> ApiResult<Self> {
+        serde_json::from_value(value)
+            .map_err(|e| ApiError::Search(format!("Malformed aggregation response: {e}")))
+    }
+
+    pub fn total_hits(&self) -> i64 {
+        self.hits.total.value
+    }
+
+    /// Top-level aggregations
+    pub fn aggregations(&self) -> Aggregations<'_> {
+        Aggregations {
+            path: String::new(),
+            map: &self.aggregations,
+        }
+    }
+}
+
+/// Named aggregation results at one level of the response tree
+#[derive(Debug, Clone)]
+pub struct Aggregations<'a> {
+    path: String,
+    map: &'a Map<String, Value>,
+}
+
+/// Result of a single-bucket aggregation such as `filter`
+#[derive(Debug)]
+pub struct SingleBucket<'a> {
+    pub doc_count: i64,
+    pub aggregations: Aggregations<'a>,
+}
+
+/// One bucket of a multi-bucket aggregation such as `terms` or `date_histogram`
+///
+/// `key` is `key_as_string` when OpenSearch provides it (formatted dates),
+/// otherwise the raw key rendered as a string.
+#[derive(Debug)]
+pub struct Bucket<'a> {
+    pub key: String,
+    pub doc_count: i64,
+    pub aggregations: Aggregations<'a>,
+}
+
+impl<'a> Aggregations<'a> {
+    fn child_path(&self, name: &str) -> String {
+        if self.path.is_empty() {
+            name.to_string()
+        } else {
+            format!("{}.{}", self.path, name)
+        }
+    }
+
+    fn object(&self, name: &str) -> ApiResult<(String, &'a Map<String, Value>)> {
+        let path = self.child_path(name);
+        let object = self
+            .map
+            .get(name)
+            .ok_or_else(|| ApiError::Search(format!("Aggregation `{path}` missing from response")))?
+            .as_object()
+            .ok_or_else(|| ApiError::Search(format!("Aggregation `{path}` is not an object")))?;
+        Ok((path, object))
+    }
+
+    /// Read a single-bucket aggregation
+    pub fn single(&self, name: &str) -> ApiResult<SingleBucket<'a>> {
+        let (path, object) = self.object(name)?;
+        Ok(SingleBucket {
+            doc_count: doc_count(&path, object)?,
+            aggregations: Aggregations { path, map: object },
+        })
+    }
+
+    /// Read the buckets of a multi-bucket aggregation
+    pub fn buckets(&self, name: &str) -> ApiResult<Vec<Bucket<'a>>> {
+        let (path, object) = self.object(name)?;
+        let buckets = object
+            .get("buckets")
+            .and_then(Value::as_array)
+            .ok_or_else(|| ApiError::Search(format!("Aggregation `{path}` has no bucket list")))?;
+
+        buckets
+            .iter()
+            .enumerate()
+            .map(|(i, bucket)| {
+                let bucket_path = format!("{path}[{i}]");
+                let object = bucket.as_object().ok_or_else(|| {
+                    ApiError::Search(format!("Bucket `{bucket_path}` is not an object"))
+                })?;
+                Ok(Bucket {
+                    key: bucket_key(&bucket_path, object)?,
+                    doc_count: doc_count(&bucket_path, object)?,
+                    aggregations: Aggregations {
+                        path: bucket_path,
+                        map: object,
+                    },
+                })
+            })
+            .collect()
+    }
+}
+
+fn doc_count(path: &str, object: &Map<String, Value>) -> ApiResult<i64> {
+    object
+        .get("doc_count")
+        .and_then(Value::as_i64)
+        .ok_or_else(|| ApiError::Search(format!("Aggregation `{path}` has no doc_count")))
+}
+
+fn bucket_key(path: &str, object: &Map<String, Value>) -> ApiResult<String> {
+    if let Some(key) = object.get("key_as_string").and_then(Value::as_str) {
+        return Ok(key.to_string());
+    }
+    match object.get("key") {
+        Some(Value::String(key)) => Ok(key.clone()),
+        Some(Value::Number(key)) => Ok(key.to_string()),
+        Some(Value::Bool(key)) => Ok(key.to_string()),
+        _ => Err(ApiError::Search(format!("Bucket `{path}` has no key"))),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Recorded response of `SearchClient::get_dashboard_stats`
+    const DASHBOARD_STATS: &str =
+        include_str!("../../tests/fixtures/opensearch/dashboard_stats.json");
+    const MALFORMED_BUCKETS: &str =
+        include_str!("../../tests/fixtures/opensearch/malformed_buckets.json");
+
+    fn fixture(text: &str) -> AggregationResponse {
+        AggregationResponse::from_value(serde_json::from_str(text).unwrap()).unwrap()
+    }
+
+    fn search_error<T: std::fmt::Debug>(result: ApiResult<T>) -> String {
+        match result {
+            Err(ApiError::Search(message)) => message,
+            other => panic!("expected a search error, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn reads_total_hits() {
+        assert_eq!(fixture(DASHBOARD_STATS).total_hits(), 15234);
+    }
+
+    #[test]
+    fn reads_terms_buckets() {
+        let response = fixture(DASHBOARD_STATS);
+        let buckets = response.aggregations().buckets("categories").unwrap();
+
+        let counts: Vec<(&str, i64)> =
+            buckets.iter().map(|b| (b.key.as_str(), b.doc_count)).collect();
+        assert_eq!(counts, [("politics", 5120), ("economy", 4011), ("", 3)]);
+    }
+
+    #[test]
+    fn date_histogram_prefers_formatted_key() {
+        let response = fixture(DASHBOARD_STATS);
+        let buckets = response.aggregations().buckets("daily_counts").unwrap();
+
+        assert_eq!(buckets[0].key, "2026-01-15");
+        assert_eq!(buckets[1].doc_count, 731);
+    }
+
+    #[test]
+    fn reads_nested_buckets_of_single_bucket() {
+        let response = fixture(DASHBOARD_STATS);
+        let hourly = response.aggregations().single("hourly_counts").unwrap();
+        assert_eq!(hourly.doc_count, 981);
+
+        let hours = hourly.aggregations.buckets("hours").unwrap();
+        let keys: Vec<&str> = hours.iter().map(|b| b.key.as_str()).collect();
+        assert_eq!(keys, ["08:00", "09:00"]);
+    }
+
+    #[test]
+    fn reports_missing_aggregation_with_path() {
+        let response = fixture(DASHBOARD_STATS);
+
+        let message = search_error(response.aggregations().buckets("sources"));
+        assert_eq!(message, "Aggregation `sources` missing from response");
+
+        let hourly = response.aggregations().single("hourly_counts").unwrap();
+        let message = search_error(hourly.aggregations.buckets("minutes"));
+        assert_eq!(message, "Aggregation `hourly_counts.minutes` missing from response");
+    }
+
+    #[test]
+    fn reports_single_bucket_without_doc_count() {
+        let response = fixture(DASHBOARD_STATS);
+
+        let message = search_error(response.aggregations().single("categories"));
+        assert_eq!(message, "Aggregation `categories` has no doc_count");
+    }
+
+    #[test]
+    fn reports_malformed_buckets_with_index() {
+        let response = fixture(MALFORMED_BUCKETS);
+        let aggregations = response.aggregations();
+
+        let message = search_error(aggregations.buckets("no_key"));
+        assert_eq!(message, "Bucket `no_key[1]` has no key");
+
+        let message = search_error(aggregations.buckets("no_doc_count"));
+        assert_eq!(message, "Aggregation `no_doc_count[0]` has no doc_count");
+
+        let message = search_error(aggregations.buckets("not_an_object"));
+        assert_eq!(message, "Bucket `not_an_object[0]` is not an object");
+
+        let message = search_error(aggregations.buckets("no_bucket_list"));
+        assert_eq!(message, "Aggregation `no_bucket_list` has no bucket list");
+    }
+
+    #[test]
+    fn renders_non_string_keys() {
+        let response = fixture(MALFORMED_BUCKETS);
+        let buckets = response.aggregations().buckets("numeric_keys").unwrap();
+
+        let keys: Vec<&str> = buckets.iter().map(|b| b.key.as_str()).collect();
+        assert_eq!(keys, ["42", "true"]);
+    }
+
+    #[test]
+    fn rejects_response_without_hits() {
+        let result = AggregationResponse::from_value(json!({ "aggregations": {} }));
+
+        assert!(search_error(result).starts_with("Malformed aggregation response"));
+    }
+
+    #[test]
+    fn builds_nested_request() {
+        let aggregations = vec![(
+            "hourly_counts".to_string(),
+            Aggregation::filter(json!({ "match_all": {} })).sub(
+                "hours",
+                Aggregation::date_histogram("crawled_at", "hour")
+                    .format("HH:mm")
+                    .min_doc_count(0)
+                    .extended_bounds("now-1d/h", "now/h")
+                    .order_by_key(SortOrder::Asc),
+            ),
+        )];
+
+        assert_eq!(
+            aggregations_json(&aggregations),
+            json!({
+                "hourly_counts": {
+                    "filter": { "match_all": {} },
+                    "aggs": {
+                        "hours": {
+                            "date_histogram": {
+                                "field": "crawled_at",
+                                "calendar_interval": "hour",
+                                "format": "HH:mm",
+                                "min_doc_count": 0,
+                                "extended_bounds": { "min": "now-1d/h", "max": "now/h" },
+                                "order": { "_key": "asc" }
+                            }
+                        }
+                    }
+                }
+            })
+        );
+    }
+}
@@ -0,0 +1,460 @@
+//! Index management and bulk operations used by the CLI.
+
+use reqwest::{Response, StatusCode};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use std::collections::HashMap;
+
+use super::SearchClient;
+use crate::db::{self, Database};
+use crate::error::{ApiError, ApiResult};
+use crate::models::Article;
+
+/// Field types the API relies on, checked by `index verify`
+pub const EXPECTED_FIELD_TYPES: &[(&str, &str)] = &[
+    ("title", "text"),
+    ("content", "text"),
+    ("summary", "text"),
+    ("category", "keyword"),
+    ("source", "keyword"),
+    ("source_id", "integer"),
+    ("url", "keyword"),
+    ("published_at", "date"),
+    ("crawled_at", "date"),
+];
+
+/// Painless script of the source pipeline; keeps a `source_id` the writer set
+const SOURCE_ID_SCRIPT: &str = "if (ctx.source_id == null && ctx.source != null) { \
+     def id = params.ids[ctx.source]; if (id != null) { ctx.source_id = id; } }";
+
+/// ID of the ingest pipeline filling in `source_id` for `index`
+pub fn source_pipeline_id(index: &str) -> String {
+    format!("{index}-source-ids")
+}
+
+/// Settings and mappings of the article index
+///
+/// `pipeline` becomes the default ingest pipeline, so articles written by the
+/// crawlers get their `source_id` from the registered source name.
+pub fn index_definition(pipeline: &str) -> Value {
+    json!({
+        "settings": {
+            "number_of_shards": 1,
+            "number_of_replicas": 0,
+            "default_pipeline": pipeline,
+            "analysis": {
+                "analyzer": {
+                    "korean": {
+                        "type": "custom",
+                        "tokenizer": "nori_tokenizer"
+                    }
+                }
+            }
+        },
+        "mappings": {
+            "properties": {
+                "title": { "type": "text", "analyzer": "korean" },
+                "content": { "type": "text", "analyzer": "korean" },
+                "summary": { "type": "text", "analyzer": "korean" },
+                "category": { "type": "keyword" },
+                "source": { "type": "keyword" },
+                "source_id": { "type": "integer" },
+                "url": { "type": "keyword" },
+                "author": { "type": "keyword" },
+                "image_url": { "type": "keyword", "index": false },
+                "published_at": { "type": "date" },
+                "crawled_at": { "type": "date" }
+            }
+        }
+    })
+}
+
+/// Ingest pipeline setting `source_id` from `source` for the registered sources
+pub fn source_pipeline(source_ids: &HashMap<String, i32>) -> Value {
+    json!({
+        "description": "Set source_id from the sources registered with news-api",
+        "processors": [{
+            "script": {
+                "lang": "painless",
+                "source": SOURCE_ID_SCRIPT,
+                "params": { "ids": source_ids }
+            }
+        }]
+    })
+}
+
+/// Outcome of a bulk indexing request
+#[derive(Debug, Default)]
+pub struct BulkReport {
+    pub indexed: usize,
+    pub failed: usize,
+    pub first_error: Option<String>,
+}
+
+/// One page of a scroll over all articles
+pub struct ScrollPage {
+    pub scroll_id: Option<String>,
+    pub articles: Vec<Article>,
+}
+
+#[derive(Deserialize)]
+struct ScrollResponse {
+    #[serde(rename = "_scroll_id")]
+    scroll_id: Option<String>,
+    hits: ScrollHits,
+}
+
+#[derive(Deserialize)]
+struct ScrollHits {
+    hits: Vec<ScrollHit>,
+}
+
+#[derive(Deserialize)]
+struct ScrollHit {
+    #[serde(rename = "_id")]
+    id: String,
+    #[serde(rename = "_source")]
+    source: Article,
+}
+
+impl SearchClient {
+    pub fn index_name(&self) -> &str {
+        &self.index_name
+    }
+
+    /// Create or replace the source pipeline of `index` with the current source names
+    pub async fn put_source_pipeline(
+        &self,
+        index: &str,
+        source_ids: &HashMap<String, i32>,
+    ) -> ApiResult<()> {
+        let url = format!("{}/_ingest/pipeline/{}", self.base_url, source_pipeline_id(index));
+
+        let response = self
+            .client
+            .put(&url)
+            .json(&source_pipeline(source_ids))
+            .send()
+            .await
+            .map_err(|e| ApiError::Search(format!("Failed to update source pipeline: {e}")))?;
+
+        check(response, "Update source pipeline").await?;
+
+        Ok(())
+    }
+
+    /// Rewrite the configured index's source pipeline from the sources table
+    pub async fn sync_source_pipeline(&self, db: &Database) -> ApiResult<()> {
+        let source_ids = db::source_ids(&db.pool).await?;
+        self.put_source_pipeline(&self.index_name, &source_ids).await
+    }
+
+    /// Make the index's source pipeline its default pipeline
+    pub async fn set_default_pipeline(&self, index: &str) -> ApiResult<()> {
+        let url = format!("{}/{}/_settings", self.base_url, index);
+
+        let response = self
+            .client
+            .put(&url)
+            .json(&json!({ "index": { "default_pipeline": source_pipeline_id(index) } }))
+            .send()
+            .await
+            .map_err(|e| ApiError::Search(format!("Failed to update index settings: {e}")))?;
+
+        check(response, "Update index settings").await?;
+
+        Ok(())
+    }
+
+    /// Run the source pipeline over articles without a `source_id`
+    ///
+    /// Returns the number of documents updated.
+    pub async fn backfill_source_ids(&self, index: &str) -> ApiResult<i64> {
+        let url = format!(
+            "{}/{}/_update_by_query?pipeline={}&conflicts=proceed&wait_for_completion=true",
+            self.base_url,
+            index,
+            source_pipeline_id(index)
+        );
+
+        let response = self
+            .client
+            .post(&url)
+            .timeout(std::time::Duration::from_secs(3600))
+            .json(&json!({
+                "query": {
+                    "bool": {
+                        "must": { "exists": { "field": "source" } },
+                        "must_not": { "exists": { "field": "source_id" } }
+                    }
+                }
+            }))
+            .send()
+            .await
+            .map_err(|e| ApiError::Search(format!("Failed to backfill source IDs: {e}")))?;
+
+        let body: Value = check(response, "Backfill source IDs").await?.json().await?;
+
+        if let Some(failure) = body["failures"].as_array().and_then(|f| f.first()) {
+            return Err(ApiError::Search(format!("Backfill failed: {failure}")));
+        }
+
+        Ok(body["updated"].as_i64().unwrap_or(0))
+    }
+
+    /// Whether an index exists
+    pub async fn index_exists(&self, index: &str) -> ApiResult<bool> {
+        let url = format!("{}/{}", self.base_url, index);
+
+        let response = self
+            .client
+            .head(&url)
+            .send()
+            .await
+            .map_err(|e| ApiError::Search(format!("Failed to check index {index}: {e}")))?;
+
+        match response.status() {
+            StatusCode::NOT_FOUND => Ok(false),
+            status if status.is_success() => Ok(true),
+            status => Err(ApiError::Search(format!(
+                "Index check for {index} failed with status {status}"
+            ))),
+        }
+    }
+
+    /// Create an index with the article mapping; returns false if it already exists
+    ///
+    /// The source pipeline is written first, since documents cannot be
+    /// indexed while the default pipeline is missing.
+    pub async fn create_index(
+        &self,
+        index: &str,
+        source_ids: &HashMap<String, i32>,
+    ) -> ApiResult<bool> {
+        if self.index_exists(index).await? {
+            return Ok(false);
+        }
+
+        self.put_source_pipeline(index, source_ids).await?;
+
+        let url = format!("{}/{}", self.base_url, index);
+
+        let response = self
+            .client
+            .put(&url)
+            .json(&index_definition(&source_pipeline_id(index)))
+            .send()
+            .await
+            .map_err(|e| ApiError::Search(format!("Failed to create index {index}: {e}")))?;
+
+        check(response, "Create index").await?;
+
+        Ok(true)
+    }
+
+    /// Field name to mapped type of an index
+    pub async fn get_field_types(&self, index: &str) -> ApiResult<Vec<(String, String)>> {
+        let url = format!("{}/{}/_mapping", self.base_url, index);
+
+        let response = self
+            .client
+            .get(&url)
+            .send()
+            .await
+            .map_err(|e| ApiError::Search(format!("Failed to fetch mapping: {e}")))?;
+
+        let body: Value = check(response, "Fetch mapping").await?.json().await?;
+
+        // The response is keyed by the concrete index name, which differs from
+        // `index` when an alias is used
+        let properties = body
+            .as_object()
+            .and_then(|indices| indices.values().next())
+            .and_then(|index| index["mappings"]["properties"].as_object())
+            .ok_or_else(|| ApiError::Search(format!("Mapping of {index} has no properties")))?;
+
+        Ok(properties
+            .iter()
+            .map(|(field, def)| {
+                let field_type = def["type"].as_str().unwrap_or("object").to_string();
+                (field.clone(), field_type)
+            })
+            .collect())
+    }
+
+    /// Number of documents in an index
+    pub async fn count(&self, index: &str) -> ApiResult<i64> {
+        let url = format!("{}/{}/_count", self.base_url, index);
+
+        let response = self
+            .client
+            .get(&url)
+            .send()
+            .await
+            .map_err(|e| ApiError::Search(format!("Failed to count documents: {e}")))?;
+
+        let body: Value = check(response, "Count").await?.json().await?;
+
+        body["count"]
+            .as_i64()
+            .ok_or_else(|| ApiError::Search("Count response has no count".to_string()))
+    }
+
+    /// Copy all documents from one index to another, waiting for completion
+    ///
+    /// Returns the number of documents created or updated in `dest`.
+    pub async fn reindex(&self, source: &str, dest: &str) -> ApiResult<i64> {
+        let url = format!("{}/_reindex?wait_for_completion=true", self.base_url);
+
+        let response = self
+            .client
+            .post(&url)
+            .timeout(std::time::Duration::from_secs(3600))
+            .json(&json!({
+                "source": { "index": source },
+                "dest": { "index": dest }
+            }))
+            .send()
+            .await
+            .map_err(|e| ApiError::Search(format!("Failed to reindex: {e}")))?;
+
+        let body: Value = check(response, "Reindex").await?.json().await?;
+
+        if let Some(failure) = body["failures"].as_array().and_then(|f| f.first()) {
+            return Err(ApiError::Search(format!("Reindex failed: {failure}")));
+        }
+
+        Ok(body["created"].as_i64().unwrap_or(0) + body["updated"].as_i64().unwrap_or(0))
+    }
+
+    /// Index articles through the `_bulk` API
+    ///
+    /// Articles with an `id` keep it as their document ID, so re-running is idempotent.
+    pub async fn bulk_index(&self, articles: &[Article]) -> ApiResult<BulkReport> {
+        if articles.is_empty() {
+            return Ok(BulkReport::default());
+        }
+
+        let mut body = String::new();
+        for article in articles {
+            let action = match &article.id {
+                Some(id) => json!({ "index": { "_id": id } }),
+                None => json!({ "index": {} }),
+            };
+            body.push_str(&action.to_string());
+            body.push('\n');
+            body.push_str(&serde_json::to_string(article)?);
+            body.push('\n');
+        }
+
+        let url = format!("{}/{}/_bulk", self.base_url, self.index_name);
+
+        let response = self
+            .client
+            .post(&url)
+            .header("Content-Type", "application/x-ndjson")
+            .body(body)
+            .send()
+            .await
+            .map_err(|e| ApiError::Search(format!("Failed to send bulk request: {e}")))?;
+
+        let result: Value = check(response, "Bulk").await?.json().await?;
+
+        let mut report = BulkReport::default();
+        for item in result["items"].as_array().into_iter().flatten() {
+            let error = &item["index"]["error"];
+            if error.is_null() {
+                report.indexed += 1;
+            } else {
+                report.failed += 1;
+                report.first_error.get_or_insert_with(|| error.to_string());
+            }
+        }
+
+        Ok(report)
+    }
+
+    /// Start a scroll over all articles in the index
+    pub async fn scroll_start(&self, size: i64) -> ApiResult<ScrollPage> {
+        let url = format!("{}/{}/_search?scroll=2m", self.base_url, self.index_name);
+
+        let response = self
+            .client
+            .post(&url)
+            .json(&json!({
+                "size": size,
+                "query": { "match_all": {} },
+                "sort": ["_doc"]
+            }))
+            .send()
+            .await
+            .map_err(|e| ApiError::Search(format!("Failed to start scroll: {e}")))?;
+
+        scroll_page(check(response, "Scroll").await?).await
+    }
+
+    /// Fetch the next page of a scroll
+    pub async fn scroll_next(&self, scroll_id: &str) -> ApiResult<ScrollPage> {
+        let url = format!("{}/_search/scroll", self.base_url);
+
+        let response = self
+            .client
+            .post(&url)
+            .json(&json!({ "scroll": "2m", "scroll_id": scroll_id }))
+            .send()
+            .await
+            .map_err(|e| ApiError::Search(format!("Failed to continue scroll: {e}")))?;
+
+        scroll_page(check(response, "Scroll").await?).await
+    }
+
+    /// Release a scroll context early
+    pub async fn scroll_clear(&self, scroll_id: &str) -> ApiResult<()> {
+        let url = format!("{}/_search/scroll", self.base_url);
+
+        self.client
+            .delete(&url)
+            .json(&json!({ "scroll_id": scroll_id }))
+            .send()
+            .await
+            .map_err(|e| ApiError::Search(format!("Failed to clear scroll: {e}")))?;
+
+        Ok(())
+    }
+}
+
+async fn scroll_page(response: Response) -> ApiResult<ScrollPage> {
+    let body: ScrollResponse = response
+        .json()
+        .await
+        .map_err(|e| ApiError::Search(format!("Failed to parse scroll response: {e}")))?;
+
+    let articles = body
+        .hits
+        .hits
+        .into_iter()
+        .map(|hit| {
+            let mut article = hit.source;
+            article.id.get_or_insert(hit.id);
+            article
+        })
+        .collect();
+
+    Ok(ScrollPage {
+        scroll_id: body.scroll_id,
+        articles,
+    })
+}
+
+/// Turn a non-success response into a search error carrying its body
+async fn check(response: Response, context: &str) -> ApiResult<Response> {
+    if response.status().is_success() {
+        return Ok(response);
+    }
+
+    let status = response.status();
+    let text = response.text().await.unwrap_or_default();
+    Err(ApiError::Search(format!(
+        "{context} request failed with status {status}: {text}"
+    )))
+}
@@ -0,0 +1,525 @@
+pub mod aggs;
+pub mod index;
+
+use crate::config::OpenSearchConfig;
+use crate::error::{ApiError, ApiResult};
+use crate::metrics::SearchTimer;
+use crate::models::Article;
+use aggs::{aggregations_json, Aggregation, AggregationResponse, SortOrder};
+use reqwest::Client;
+use serde::{Deserialize, Serialize};
+use serde_json::{json, Value};
+
+/// Upper bound on distinct categories read from the index
+const MAX_CATEGORY_BUCKETS: i64 = 1000;
+
+#[derive(Clone)]
+pub struct SearchClient {
+    client: Client,
+    base_url: String,
+    index_name: String,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct SearchResponse {
+    hits: SearchHits,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct SearchHits {
+    total: SearchTotal,
+    hits: Vec<SearchHit>,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct SearchTotal {
+    value: i64,
+}
+
+#[derive(Debug, Serialize, Deserialize)]
+struct SearchHit {
+    #[serde(rename = "_source")]
+    source: Article,
+}
+
+/// Exact-match filters for article listings
+#[derive(Debug, Default)]
+pub struct ArticleFilter {
+    pub source_id: Option<i32>,
+    /// Category labels to match; any of them qualifies
+    pub categories: Vec<String>,
+}
+
+impl ArticleFilter {
+    fn to_query(&self) -> Value {
+        let mut filters = Vec::new();
+        if let Some(source_id) = self.source_id {
+            filters.push(json!({ "term": { "source_id": source_id } }));
+        }
+        if !self.categories.is_empty() {
+            filters.push(json!({ "terms": { "category": self.categories } }));
+        }
+
+        if filters.is_empty() {
+            json!({ "match_all": {} })
+        } else {
+            json!({ "bool": { "filter": filters } })
+        }
+    }
+}
+
+/// One time series of a breakdown: the document count per interval for a single key
+#[derive(Debug)]
+pub struct KeySeries {
+    pub key: String,
+    pub total: i64,
+    pub points: Vec<(String, i64)>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ClusterHealth {
+    /// `green`, `yellow` or `red`
+    pub status: String,
+    pub number_of_nodes: i64,
+}
+
+impl SearchClient {
+    pub fn new(config: &OpenSearchConfig) -> Self {
+        let client = Client::builder()
+            .timeout(config.timeout())
+            .build()
+            .unwrap_or_default();
+
+        Self {
+            client,
+            base_url: config.url.trim_end_matches('/').to_string(),
+            index_name: config.index.clone(),
+        }
+    }
+
+    /// Search for articles by keyword
+    pub async fn search_articles(&self, query: &str, from: i64, size: i64) -> ApiResult<(Vec<Article>, i64)> {
+        let timer = SearchTimer::start("search_articles");
+        let url = format!("{}/{}/_search", self.base_url, self.index_name);
+
+        let search_query = json!({
+            "from": from,
+            "size": size,
+            "track_total_hits": true,
+            "query": {
+                "multi_match": {
+                    "query": query,
+                    "fields": ["title^3", "content", "category"],
+                    "type": "best_fields",
+                    "fuzziness": "AUTO"
+                }
+            },
+            "sort": [
+                { "published_at": { "order": "desc" } }
+            ]
+        });
+
+        let response = self
+            .client
+            .post(&url)
+            .json(&search_query)
+            .send()
+            .await
+            .map_err(|e| ApiError::Search(format!("Failed to send search request: {e}")))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let text = response.text().await.unwrap_or_default();
+            return Err(ApiError::Search(format!(
+                "Search request failed with status {status}: {text}"
+            )));
+        }
+
+        let search_response: SearchResponse = response.json().await.map_err(|e| {
+            ApiError::Search(format!("Failed to parse search response: {e}"))
+        })?;
+
+        let articles: Vec<Article> = search_response
+            .hits
+            .hits
+            .into_iter()
+            .map(|hit| hit.source)
+            .collect();
+
+        let total = search_response.hits.total.value;
+
+        timer.finish(Ok((articles, total)))
+    }
+
+    /// Get articles with pagination
+    pub async fn get_articles(
+        &self,
+        from: i64,
+        size: i64,
+        filter: &ArticleFilter,
+    ) -> ApiResult<(Vec<Article>, i64)> {
+        let timer = SearchTimer::start("get_articles");
+        let url = format!("{}/{}/_search", self.base_url, self.index_name);
+
+        let query = json!({
+            "from": from,
+            "size": size,
+            "track_total_hits": true,
+            "query": filter.to_query(),
+            "sort": [
+                { "published_at": { "order": "desc" } }
+            ]
+        });
+
+        let response = self
+            .client
+            .post(&url)
+            .json(&query)
+            .send()
+            .await
+            .map_err(|e| ApiError::Search(format!("Failed to fetch articles: {e}")))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let text = response.text().await.unwrap_or_default();
+            return Err(ApiError::Search(format!(
+                "Fetch articles failed with status {status}: {text}"
+            )));
+        }
+
+        let search_response: SearchResponse = response.json().await.map_err(|e| {
+            ApiError::Search(format!("Failed to parse articles response: {e}"))
+        })?;
+
+        let articles: Vec<Article> = search_response
+            .hits
+            .hits
+            .into_iter()
+            .map(|hit| hit.source)
+            .collect();
+
+        let total = search_response.hits.total.value;
+
+        timer.finish(Ok((articles, total)))
+    }
+
+    /// Get article by ID
+    pub async fn get_article_by_id(&self, id: &str) -> ApiResult<Article> {
+        let timer = SearchTimer::start("get_article_by_id");
+        let url = format!("{}/{}/_doc/{}", self.base_url, self.index_name, id);
+
+        let response = self
+            .client
+            .get(&url)
+            .send()
+            .await
+            .map_err(|e| ApiError::Search(format!("Failed to fetch article: {e}")))?;
+
+        if response.status() == reqwest::StatusCode::NOT_FOUND {
+            let not_found = ApiError::NotFound(format!("Article with id {id} not found"));
+            return timer.finish(Err(not_found));
+        }
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let text = response.text().await.unwrap_or_default();
+            return Err(ApiError::Search(format!(
+                "Fetch article failed with status {status}: {text}"
+            )));
+        }
+
+        #[derive(Deserialize)]
+        struct GetResponse {
+            #[serde(rename = "_source")]
+            source: Article,
+        }
+
+        let get_response: GetResponse = response.json().await.map_err(|e| {
+            ApiError::Search(format!("Failed to parse article response: {e}"))
+        })?;
+
+        timer.finish(Ok(get_response.source))
+    }
+
+    /// Get dashboard statistics via OpenSearch aggregations
+    pub async fn get_dashboard_stats(&self) -> ApiResult<AggregationResponse> {
+        let timer = SearchTimer::start("get_dashboard_stats");
+        let aggregations = vec![
+            (
+                "today_articles".to_string(),
+                Aggregation::filter(json!({
+                    "range": {
+                        "crawled_at": {
+                            "gte": "now/d",
+                            "lt": "now+1d/d"
+                        }
+                    }
+                })),
+            ),
+            ("categories".to_string(), Aggregation::terms("category", 50)),
+            ("publishers".to_string(), Aggregation::terms("publisher", 50)),
+            (
+                "daily_counts".to_string(),
+                Aggregation::date_histogram("crawled_at", "day")
+                    .format("yyyy-MM-dd")
+                    .order_by_key(SortOrder::Desc),
+            ),
+            (
+                "hourly_counts".to_string(),
+                Aggregation::filter(json!({
+                    "range": {
+                        "crawled_at": {
+                            "gte": "now-24h"
+                        }
+                    }
+                }))
+                .sub(
+                    "hours",
+                    Aggregation::date_histogram("crawled_at", "hour").format("HH:mm"),
+                ),
+            ),
+        ];
+
+        let result = self
+            .aggregate(json!({ "match_all": {} }), &aggregations, "dashboard stats")
+            .await;
+        timer.finish(result)
+    }
+
+    /// Get article counts per category from a terms aggregation
+    pub async fn get_category_counts(&self) -> ApiResult<Vec<(String, i64)>> {
+        let timer = SearchTimer::start("get_category_counts");
+        let aggregations = vec![(
+            "categories".to_string(),
+            Aggregation::terms("category", MAX_CATEGORY_BUCKETS),
+        )];
+
+        let response = self
+            .aggregate(json!({ "match_all": {} }), &aggregations, "category counts")
+            .await?;
+
+        let counts = response
+            .aggregations()
+            .buckets("categories")?
+            .into_iter()
+            .filter(|bucket| !bucket.key.is_empty())
+            .map(|bucket| (bucket.key, bucket.doc_count))
+            .collect();
+
+        timer.finish(Ok(counts))
+    }
+
+    /// Get per-key time series via a terms aggregation with a nested date_histogram
+    ///
+    /// `field` is the field to split on (e.g. `category`, `source_id`) and
+    /// `interval` a calendar interval understood by OpenSearch (`hour`, `day`, ...).
+    /// `filters` are exact-match term filters applied before aggregating. Every
+    /// series has a point for each interval of the window, empty ones included.
+    pub async fn get_breakdown(
+        &self,
+        field: &str,
+        interval: &str,
+        days: i64,
+        filters: &[(&str, &str)],
+    ) -> ApiResult<Vec<KeySeries>> {
+        let timer = SearchTimer::start("get_breakdown");
+        let (format, last) = if interval == "hour" {
+            ("yyyy-MM-dd'T'HH:mm", "now/h")
+        } else {
+            ("yyyy-MM-dd", "now/d")
+        };
+        let from = format!("now-{days}d/d");
+
+        let mut must = vec![json!({
+            "range": {
+                "crawled_at": {
+                    "gte": from,
+                    "lt": "now+1d/d"
+                }
+            }
+        })];
+        for (name, value) in filters {
+            must.push(json!({ "term": { *name: value } }));
+        }
+
+        let aggregations = vec![(
+            "keys".to_string(),
+            Aggregation::terms(field, 50).sub(
+                "series",
+                Aggregation::date_histogram("crawled_at", interval)
+                    .format(format)
+                    .min_doc_count(0)
+                    .extended_bounds(&from, last)
+                    .order_by_key(SortOrder::Asc),
+            ),
+        )];
+
+        let response = self
+            .aggregate(json!({ "bool": { "filter": must } }), &aggregations, "breakdown stats")
+            .await?;
+
+        let mut series = Vec::new();
+        for bucket in response.aggregations().buckets("keys")? {
+            if bucket.key.is_empty() {
+                continue;
+            }
+            let points = bucket
+                .aggregations
+                .buckets("series")?
+                .into_iter()
+                .map(|b| (b.key, b.doc_count))
+                .collect();
+            series.push(KeySeries {
+                key: bucket.key,
+                total: bucket.doc_count,
+                points,
+            });
+        }
+
+        timer.finish(Ok(series))
+    }
+
+    /// Run a `size: 0` aggregation query and parse the typed response
+    async fn aggregate(
+        &self,
+        query: Value,
+        aggregations: &[(String, Aggregation)],
+        context: &str,
+    ) -> ApiResult<AggregationResponse> {
+        let url = format!("{}/{}/_search", self.base_url, self.index_name);
+
+        let body = json!({
+            "size": 0,
+            "track_total_hits": true,
+            "query": query,
+            "aggs": aggregations_json(aggregations),
+        });
+
+        let response = self
+            .client
+            .post(&url)
+            .json(&body)
+            .send()
+            .await
+            .map_err(|e| ApiError::Search(format!("Failed to fetch {context}: {e}")))?;
+
+        if !response.status().is_success() {
+            let status = response.status();
+            let text = response.text().await.unwrap_or_default();
+            return Err(ApiError::Search(format!(
+                "{context} request failed with status {status}: {text}"
+            )));
+        }
+
+        let body: Value = response
+            .json()
+            .await
+            .map_err(|e| ApiError::Search(format!("Failed to parse {context}: {e}")))?;
+
+        AggregationResponse::from_value(body)
+    }
+
+    /// Total articles and articles crawled today
+    pub async fn get_article_counts(&self) -> ApiResult<(i64, i64)> {
+        let timer = SearchTimer::start("get_article_counts");
+
+        let aggregations = vec![(
+            "today_articles".to_string(),
+            Aggregation::filter(json!({
+                "range": {
+                    "crawled_at": {
+                        "gte": "now/d",
+                        "lt": "now+1d/d"
+                    }
+                }
+            })),
+        )];
+
+        let response = self
+            .aggregate(json!({ "match_all": {} }), &aggregations, "article counts")
+            .await?;
+        let today = response.aggregations().single("today_articles")?.doc_count;
+
+        timer.finish(Ok((response.total_hits(), today)))
+    }
+
+    /// Articles crawled in the last `minutes`
+    pub async fn count_articles_since(&self, minutes: i64) -> ApiResult<i64> {
+        let timer = SearchTimer::start("count_articles_since");
+
+        let query = json!({
+            "range": {
+                "crawled_at": {
+                    "gte": format!("now-{minutes}m")
+                }
+            }
+        });
+        let response = self.aggregate(query, &[], "recent article count").await?;
+
+        timer.finish(Ok(response.total_hits()))
+    }
+
+    /// Health check for OpenSearch
+    pub async fn health_check(&self) -> ApiResult<bool> {
+        let timer = SearchTimer::start("health_check");
+        let url = format!("{}/_cluster/health", self.base_url);
+
+        let response = self.client.get(&url).send().await?;
+
+        timer.finish(Ok(response.status().is_success()))
+    }
+
+    /// Cluster health colour and node count
+    pub async fn cluster_health(&self) -> ApiResult<ClusterHealth> {
+        let timer = SearchTimer::start("cluster_health");
+        let url = format!("{}/_cluster/health", self.base_url);
+
+        let response = self
+            .client
+            .get(&url)
+            .send()
+            .await
+            .map_err(|e| ApiError::Search(format!("Failed to fetch cluster health: {e}")))?;
+
+        if !response.status().is_success() {
+            return Err(ApiError::Search(format!(
+                "Cluster health request failed with status {}",
+                response.status()
+            )));
+        }
+
+        let health = response
+            .json()
+            .await
+            .map_err(|e| ApiError::Search(format!("Failed to parse cluster health: {e}")));
+        timer.finish(health)
+    }
+
+    /// Seconds the longest-running node has been up
+    pub async fn uptime_secs(&self) -> ApiResult<i64> {
+        let timer = SearchTimer::start("uptime_secs");
+        let url = format!(
+            "{}/_nodes/stats/jvm?filter_path=nodes.*.jvm.uptime_in_millis",
+            self.base_url
+        );
+
+        let response = self
+            .client
+            .get(&url)
+            .send()
+            .await
+            .map_err(|e| ApiError::Search(format!("Failed to fetch node stats: {e}")))?;
+
+        let body: Value = response.json().await?;
+
+        let uptime_millis = body["nodes"]
+            .as_object()
+            .into_iter()
+            .flat_map(|nodes| nodes.values())
+            .filter_map(|node| node["jvm"]["uptime_in_millis"].as_i64())
+            .max()
+            .unwrap_or(0);
+
+        timer.finish(Ok(uptime_millis / 1000))
+    }
+}
@@ -0,0 +1,210 @@
+//! Registry of monitored services and the background prober.
+//!
+//! Every service from `[monitor]` is probed concurrently on a schedule and the
+//! latest result plus a bounded history are cached, so the admin endpoints
+//! answer from memory instead of probing on each request.
+
+use chrono::{DateTime, Duration as ChronoDuration, Utc};
+use std::collections::{HashMap, VecDeque};
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::RwLock;
+use tokio::task::JoinSet;
+
+use crate::{
+    config::{Config, ServiceConfig},
+    db::Database,
+    models::{HealthStatus, ProbeSample, ServiceDetail, ServiceHealth, ServiceInfo, ServiceState},
+    search::SearchClient,
+    status, AppState,
+};
+
+#[derive(Clone)]
+pub struct ServiceMonitor {
+    services: Arc<Vec<ServiceConfig>>,
+    records: Arc<RwLock<HashMap<String, ServiceRecord>>>,
+    history_size: usize,
+    max_connections: u32,
+}
+
+#[derive(Default)]
+struct ServiceRecord {
+    latest: Option<ServiceHealth>,
+    history: VecDeque<ProbeSample>,
+    /// First probe that found the service up after it was down or unknown
+    up_since: Option<DateTime<Utc>>,
+    /// Set while a container restart requested through the API is in flight
+    restarting: bool,
+}
+
+impl ServiceMonitor {
+    pub fn new(config: &Config) -> Self {
+        Self {
+            services: Arc::new(config.monitor.registry(config)),
+            records: Arc::default(),
+            history_size: config.monitor.history_size,
+            max_connections: config.database.max_connections,
+        }
+    }
+
+    pub fn contains(&self, id: &str) -> bool {
+        self.service(id).is_some()
+    }
+
+    pub fn service(&self, id: &str) -> Option<&ServiceConfig> {
+        self.services.iter().find(|s| s.id == id)
+    }
+
+    /// Report the service as `restarting` until cleared, regardless of probes
+    pub async fn set_restarting(&self, id: &str, restarting: bool) {
+        let mut records = self.records.write().await;
+        records.entry(id.to_string()).or_default().restarting = restarting;
+    }
+
+    /// Probe a single service and record the result
+    pub async fn probe_one(&self, id: &str, db: &Database, search: &SearchClient) {
+        if let Some(service) = self.service(id) {
+            let health = status::probe(service, db, search, self.max_connections).await;
+            self.record(service.id.clone(), health).await;
+        }
+    }
+
+    /// Probe every service concurrently and record the results
+    pub async fn probe_all(&self, db: &Database, search: &SearchClient) {
+        let mut probes = JoinSet::new();
+        for service in self.services.iter().cloned() {
+            let (db, search) = (db.clone(), search.clone());
+            let max_connections = self.max_connections;
+            probes.spawn(async move {
+                let health = status::probe(&service, &db, &search, max_connections).await;
+                (service.id, health)
+            });
+        }
+
+        while let Some(result) = probes.join_next().await {
+            match result {
+                Ok((id, health)) => self.record(id, health).await,
+                Err(e) => tracing::warn!("Service probe task failed: {e}"),
+            }
+        }
+    }
+
+    /// Probe now if any service has no result yet
+    pub async fn ensure_probed(&self, db: &Database, search: &SearchClient) {
+        let missing = {
+            let records = self.records.read().await;
+            self.services
+                .iter()
+                .any(|s| records.get(&s.id).is_none_or(|r| r.latest.is_none()))
+        };
+        if missing {
+            self.probe_all(db, search).await;
+        }
+    }
+
+    async fn record(&self, id: String, health: ServiceHealth) {
+        let mut records = self.records.write().await;
+        let record = records.entry(id).or_default();
+
+        let up = health.status != HealthStatus::Down;
+        let was_up = record
+            .latest
+            .as_ref()
+            .is_some_and(|h| h.status != HealthStatus::Down);
+        if !up {
+            record.up_since = None;
+        } else if !was_up {
+            record.up_since = Some(health.last_check);
+        }
+
+        record.history.push_back(ProbeSample {
+            timestamp: health.last_check,
+            status: health.status,
+            latency_ms: health.latency_ms,
+            message: health.message.clone(),
+        });
+        while record.history.len() > self.history_size {
+            record.history.pop_front();
+        }
+        record.latest = Some(health);
+    }
+
+    /// All services in registry order
+    pub async fn list(&self) -> Vec<ServiceInfo> {
+        let records = self.records.read().await;
+        self.services
+            .iter()
+            .map(|service| info(service, records.get(&service.id)))
+            .collect()
+    }
+
+    pub async fn get(&self, id: &str) -> Option<ServiceDetail> {
+        let service = self.service(id)?;
+        let records = self.records.read().await;
+        let record = records.get(id);
+
+        let history: Vec<ProbeSample> = record
+            .map(|r| r.history.iter().cloned().collect())
+            .unwrap_or_default();
+        let up = history
+            .iter()
+            .filter(|s| s.status != HealthStatus::Down)
+            .count();
+        let availability = if history.is_empty() {
+            0.0
+        } else {
+            up as f64 / history.len() as f64 * 100.0
+        };
+
+        Some(ServiceDetail {
+            service: info(service, record),
+            probe: service.probe.redacted(),
+            availability,
+            history,
+        })
+    }
+}
+
+fn info(service: &ServiceConfig, record: Option<&ServiceRecord>) -> ServiceInfo {
+    let health = record.and_then(|r| r.latest.clone());
+
+    let status = match &health {
+        _ if record.is_some_and(|r| r.restarting) => ServiceState::Restarting,
+        Some(h) if h.status == HealthStatus::Down => ServiceState::Stopped,
+        Some(_) => ServiceState::Running,
+        None => ServiceState::Stopped,
+    };
+
+    // Prefer the uptime the service reports over our own observation
+    let started_at = match &health {
+        Some(h) if h.status != HealthStatus::Down && h.uptime > 0 => {
+            Some(h.last_check - ChronoDuration::seconds(h.uptime))
+        }
+        _ => record.and_then(|r| r.up_since),
+    };
+
+    ServiceInfo {
+        id: service.id.clone(),
+        name: service.name.clone(),
+        status,
+        kind: service.kind,
+        port: service.port,
+        replicas: service.replicas,
+        version: service.version.clone(),
+        started_at,
+        health,
+    }
+}
+
+/// Spawn the periodic probe loop
+pub fn spawn(state: AppState, interval: Duration) {
+    tokio::spawn(async move {
+        let mut ticker = tokio::time::interval(interval);
+        ticker.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+
+        loop {
+            ticker.tick().await;
+            state.services.probe_all(&state.db, &state.search).await;
+        }
+    });
+}
@@ -0,0 +1,239 @@
+//! Health probes of the API itself and the services it depends on.
+//!
+//! Each probe records when it ran and how long it took, and explains anything
+//! short of healthy in `message`. Probes never fail: an unreachable service is
+//! reported as `down`.
+
+use chrono::Utc;
+use sqlx::Connection;
+use std::time::{Duration, Instant};
+
+use crate::{
+    config::{LlmConfig, OpenSearchConfig, ProbeConfig, ServiceConfig},
+    db::Database,
+    models::{HealthStatus, ServiceHealth},
+    search::SearchClient,
+};
+
+/// Query returning how long Postgres has been running, in seconds
+const PG_UPTIME_QUERY: &str =
+    "SELECT EXTRACT(EPOCH FROM NOW() - pg_postmaster_start_time())::BIGINT";
+
+/// Responses slower than this mark a service as degraded
+const SLOW_RESPONSE: Duration = Duration::from_secs(1);
+
+pub fn check_api(started_at: Instant) -> ServiceHealth {
+    let probe = Instant::now();
+    finish(
+        "API Server",
+        HealthStatus::Healthy,
+        started_at.elapsed().as_secs() as i64,
+        probe,
+        None,
+    )
+}
+
+pub async fn check_database(db: &Database, max_connections: u32) -> ServiceHealth {
+    let probe = Instant::now();
+
+    let uptime: Result<i64, sqlx::Error> =
+        sqlx::query_scalar(PG_UPTIME_QUERY).fetch_one(&db.pool).await;
+
+    match uptime {
+        Ok(uptime) => {
+            let idle = db.pool.num_idle() as u32;
+            let in_use = db.pool.size().saturating_sub(idle);
+            let pool = format!("Connections: {in_use} in use, {idle} idle, {max_connections} max");
+            let (status, message) = slow_or_healthy(probe, pool);
+            finish("PostgreSQL", status, uptime, probe, Some(message))
+        }
+        Err(e) => finish("PostgreSQL", HealthStatus::Down, 0, probe, Some(e.to_string())),
+    }
+}
+
+pub async fn check_opensearch(search: &SearchClient) -> ServiceHealth {
+    let probe = Instant::now();
+
+    let (health, uptime) = tokio::join!(search.cluster_health(), search.uptime_secs());
+
+    match health {
+        Ok(health) => {
+            let status = match health.status.as_str() {
+                "green" => HealthStatus::Healthy,
+                "yellow" => HealthStatus::Degraded,
+                _ => HealthStatus::Down,
+            };
+            let message = format!(
+                "Cluster health: {}, {} node(s)",
+                health.status, health.number_of_nodes
+            );
+            let (status, message) = match status {
+                HealthStatus::Healthy => slow_or_healthy(probe, message),
+                other => (other, message),
+            };
+            finish("OpenSearch", status, uptime.unwrap_or(0), probe, Some(message))
+        }
+        Err(e) => finish("OpenSearch", HealthStatus::Down, 0, probe, Some(e.to_string())),
+    }
+}
+
+/// Probe the vLLM health endpoint
+///
+/// An error status means the server is up but not serving (`degraded`); no
+/// response at all means `down`.
+pub async fn check_llm(config: &LlmConfig) -> ServiceHealth {
+    let probe = Instant::now();
+
+    let response = reqwest::Client::new()
+        .get(&config.health_url)
+        .timeout(config.timeout())
+        .send()
+        .await;
+
+    let (status, message) = match response {
+        Ok(resp) if resp.status().is_success() => {
+            slow_or_healthy(probe, format!("HTTP {}", resp.status()))
+        }
+        Ok(resp) => (HealthStatus::Degraded, format!("HTTP {}", resp.status())),
+        Err(e) => (HealthStatus::Down, e.to_string()),
+    };
+
+    finish("LLM Server", status, 0, probe, Some(message))
+}
+
+/// Probe a registered service according to its probe configuration
+///
+/// Postgres and OpenSearch probes without a URL reuse the API's own pool and
+/// client.
+pub async fn probe(
+    service: &ServiceConfig,
+    db: &Database,
+    search: &SearchClient,
+    max_connections: u32,
+) -> ServiceHealth {
+    let probe = Instant::now();
+    let timeout = service.probe.timeout();
+
+    let result = tokio::time::timeout(timeout, async {
+        match &service.probe {
+            ProbeConfig::Http {
+                url,
+                expected_status,
+                ..
+            } => check_http(url, *expected_status, timeout).await,
+            ProbeConfig::Tcp { address, .. } => check_tcp(address).await,
+            ProbeConfig::Postgres { url: None, .. } => check_database(db, max_connections).await,
+            ProbeConfig::Postgres { url: Some(url), .. } => check_postgres_url(url).await,
+            ProbeConfig::OpenSearch { url: None, .. } => check_opensearch(search).await,
+            ProbeConfig::OpenSearch { url: Some(url), .. } => {
+                let client = SearchClient::new(&OpenSearchConfig {
+                    url: url.clone(),
+                    index: String::new(),
+                    timeout_secs: timeout.as_secs(),
+                });
+                check_opensearch(&client).await
+            }
+        }
+    })
+    .await;
+
+    let mut health = result.unwrap_or_else(|_| {
+        let message = format!("No response within {} s", timeout.as_secs());
+        finish("", HealthStatus::Down, 0, probe, Some(message))
+    });
+    health.name = service.name.clone();
+    health
+}
+
+async fn check_http(url: &str, expected_status: Option<u16>, timeout: Duration) -> ServiceHealth {
+    let probe = Instant::now();
+
+    let response = reqwest::Client::new().get(url).timeout(timeout).send().await;
+
+    let (status, message) = match response {
+        Ok(resp) => {
+            let code = resp.status();
+            let expected = match expected_status {
+                Some(expected) => code.as_u16() == expected,
+                None => code.is_success(),
+            };
+            if expected {
+                slow_or_healthy(probe, format!("HTTP {code}"))
+            } else {
+                (HealthStatus::Degraded, format!("Unexpected HTTP {code}"))
+            }
+        }
+        Err(e) => (HealthStatus::Down, e.to_string()),
+    };
+
+    finish("", status, 0, probe, Some(message))
+}
+
+async fn check_tcp(address: &str) -> ServiceHealth {
+    let probe = Instant::now();
+
+    let (status, message) = match tokio::net::TcpStream::connect(address).await {
+        Ok(_) => slow_or_healthy(probe, format!("Accepted connection on {address}")),
+        Err(e) => (HealthStatus::Down, e.to_string()),
+    };
+
+    finish("", status, 0, probe, Some(message))
+}
+
+async fn check_postgres_url(url: &str) -> ServiceHealth {
+    let probe = Instant::now();
+
+    let uptime = async {
+        let mut conn = sqlx::PgConnection::connect(url).await?;
+        let uptime: i64 = sqlx::query_scalar(PG_UPTIME_QUERY).fetch_one(&mut conn).await?;
+        conn.close().await?;
+        Ok::<_, sqlx::Error>(uptime)
+    }
+    .await;
+
+    match uptime {
+        Ok(uptime) => {
+            let (status, message) = slow_or_healthy(probe, "Query succeeded".to_string());
+            finish("", status, uptime, probe, Some(message))
+        }
+        Err(e) => finish("", HealthStatus::Down, 0, probe, Some(e.to_string())),
+    }
+}
+
+/// Worst status of the required services; optional ones can only degrade it
+pub fn overall(required: &[&ServiceHealth], optional: &[&ServiceHealth]) -> HealthStatus {
+    let required = required.iter().map(|s| s.status);
+    let optional = optional.iter().map(|s| s.status.min(HealthStatus::Degraded));
+
+    required
+        .chain(optional)
+        .max()
+        .unwrap_or(HealthStatus::Healthy)
+}
+
+fn slow_or_healthy(probe: Instant, message: String) -> (HealthStatus, String) {
+    let elapsed = probe.elapsed();
+    if elapsed > SLOW_RESPONSE {
+        let message = format!("Slow response ({} ms); {message}", elapsed.as_millis());
+        (HealthStatus::Degraded, message)
+    } else {
+        (HealthStatus::Healthy, message)
+    }
+}
+
+fn finish(
+    name: &str,
+    status: HealthStatus,
+    uptime: i64,
+    probe: Instant,
+    message: Option<String>,
+) -> ServiceHealth {
+    ServiceHealth {
+        name: name.to_string(),
+        status,
+        uptime,
+        last_check: Utc::now(),
+        latency_ms: probe.elapsed().as_millis() as u64,
+        message,
+    }
+}
@@ -0,0 +1,666 @@
+//! Outbound webhook notifications.
+//!
+//! A notification is rendered once per subscribed webhook, in the Slack,
+//! Discord or plain JSON layout, and queued in `webhook_deliveries`. A
+//! background worker posts due deliveries, signing the body with the
+//! webhook's secret, and reschedules failed ones with exponential backoff
+//! until `webhooks.max_attempts` is reached. Deliveries are claimed with
+//! `FOR UPDATE SKIP LOCKED`, so several replicas can run the worker.
+
+use chrono::{DateTime, Utc};
+use hmac::{Hmac, Mac};
+use reqwest::{Client, StatusCode};
+use serde_json::{json, Value};
+use sha2::Sha256;
+use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::Notify;
+
+use crate::{
+    config::WebhooksConfig,
+    db::Database,
+    error::{ApiError, ApiResult},
+    models::{AlertEvent, WebhookDelivery, WebhookFormat},
+};
+
+pub const WEBHOOK_DELIVERY_COLUMNS: &str = "id, webhook_id, event, payload, status, attempts, \
+     next_attempt_at, response_status, last_error, created_at, delivered_at";
+
+/// Deliveries claimed per batch
+const BATCH_SIZE: i64 = 20;
+
+const PRUNE_INTERVAL: Duration = Duration::from_secs(3600);
+
+/// Something worth telling a webhook about, before it is rendered
+#[derive(Debug, Clone)]
+pub struct Notification {
+    pub event: &'static str,
+    pub title: String,
+    pub text: String,
+    /// `info`, `warning` or `critical`
+    pub severity: String,
+    pub fields: Vec<(&'static str, String)>,
+    pub timestamp: DateTime<Utc>,
+    /// Event details for the JSON format
+    pub data: Value,
+}
+
+impl Notification {
+    pub fn alert(event: &AlertEvent) -> Self {
+        let firing = event.state == "firing";
+        let mut fields = vec![("Severity", event.severity.clone())];
+        if let Some(value) = event.value {
+            fields.push(("Value", format!("{value:.2}")));
+        }
+
+        Self {
+            event: if firing { "alert.firing" } else { "alert.resolved" },
+            title: format!(
+                "[{}] {}",
+                if firing { "FIRING" } else { "RESOLVED" },
+                event.rule_name
+            ),
+            text: event.message.clone(),
+            severity: event.severity.clone(),
+            fields,
+            timestamp: event.created_at,
+            data: serde_json::to_value(event).unwrap_or_default(),
+        }
+    }
+
+    /// Sample sent by the test endpoint
+    pub fn test(webhook_name: &str) -> Self {
+        Self {
+            event: "test",
+            title: "Test notification".to_string(),
+            text: format!("Webhook {webhook_name} is set up to receive notifications."),
+            severity: "info".to_string(),
+            fields: vec![("Webhook", webhook_name.to_string())],
+            timestamp: Utc::now(),
+            data: json!({ "webhook": webhook_name }),
+        }
+    }
+
+    fn render(&self, format: WebhookFormat) -> Value {
+        // Resolved alerts are green whatever their severity
+        let color = match (self.event, self.severity.as_str()) {
+            ("alert.resolved", _) => 0x188038,
+            (_, "critical") => 0xd93025,
+            (_, "warning") => 0xf2a600,
+            _ => 0x1a73e8,
+        };
+
+        match format {
+            WebhookFormat::Slack => json!({
+                "text": self.title,
+                "attachments": [{
+                    "color": format!("#{color:06x}"),
+                    "title": self.title,
+                    "text": self.text,
+                    "fields": self.fields.iter().map(|(name, value)| json!({
+                        "title": name,
+                        "value": value,
+                        "short": true,
+                    })).collect::<Vec<_>>(),
+                    "ts": self.timestamp.timestamp(),
+                }],
+            }),
+            WebhookFormat::Discord => json!({
+                "embeds": [{
+                    "title": self.title,
+                    "description": self.text,
+                    "color": color,
+                    "fields": self.fields.iter().map(|(name, value)| json!({
+                        "name": name,
+                        "value": value,
+                        "inline": true,
+                    })).collect::<Vec<_>>(),
+                    "timestamp": self.timestamp.to_rfc3339(),
+                }],
+            }),
+            WebhookFormat::Json => json!({
+                "event": self.event,
+                "title": self.title,
+                "text": self.text,
+                "severity": self.severity,
+                "timestamp": self.timestamp,
+                "data": self.data,
+            }),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct WebhookDispatcher {
+    client: Client,
+    wake: Arc<Notify>,
+    max_attempts: i32,
+    retry_base: Duration,
+    retry_max: Duration,
+    /// How long a claimed delivery is hidden from other workers
+    lease: Duration,
+}
+
+/// Due delivery joined with its webhook
+#[derive(sqlx::FromRow)]
+struct Claimed {
+    id: i64,
+    event: String,
+    payload: Value,
+    attempts: i32,
+    url: String,
+    secret: Option<String>,
+}
+
+impl WebhookDispatcher {
+    pub fn new(config: &WebhooksConfig) -> Self {
+        let client = Client::builder()
+            .timeout(config.timeout())
+            .user_agent(concat!("news-api/", env!("CARGO_PKG_VERSION")))
+            .build()
+            .unwrap_or_default();
+
+        Self {
+            client,
+            wake: Arc::default(),
+            max_attempts: config.max_attempts,
+            retry_base: Duration::from_secs(config.retry_base_secs),
+            retry_max: Duration::from_secs(config.retry_max_secs),
+            lease: config.timeout() * 2,
+        }
+    }
+
+    /// Queue a notification for every enabled webhook subscribed to its event
+    pub async fn notify(&self, db: &Database, notification: &Notification) -> ApiResult<u64> {
+        let webhooks: Vec<(i32, String)> = sqlx::query_as(
+            "SELECT id, format FROM webhooks WHERE enabled AND $1 = ANY(events)",
+        )
+        .bind(notification.event)
+        .fetch_all(&db.pool)
+        .await?;
+
+        let mut queued = 0;
+        for (id, format) in webhooks {
+            let format = format.parse().unwrap_or_default();
+            sqlx::query(
+                r#"
+                INSERT INTO webhook_deliveries (webhook_id, event, payload, next_attempt_at)
+                VALUES ($1, $2, $3, NOW())
+                "#,
+            )
+            .bind(id)
+            .bind(notification.event)
+            .bind(notification.render(format))
+            .execute(&db.pool)
+            .await?;
+            queued += 1;
+        }
+
+        if queued > 0 {
+            self.wake.notify_one();
+        }
+        Ok(queued)
+    }
+
+    /// Send the sample notification to one webhook now
+    ///
+    /// The delivery is logged like any other and retried if it fails.
+    pub async fn send_test(&self, db: &Database, webhook_id: i32) -> ApiResult<WebhookDelivery> {
+        let (name, format): (String, String) =
+            sqlx::query_as("SELECT name, format FROM webhooks WHERE id = $1")
+                .bind(webhook_id)
+                .fetch_optional(&db.pool)
+                .await?
+                .ok_or_else(|| {
+                    ApiError::NotFound(format!("Webhook with id {webhook_id} not found"))
+                })?;
+
+        let notification = Notification::test(&name);
+        let format = format.parse().unwrap_or_default();
+
+        // Inserted as already claimed so the worker leaves it alone
+        let claimed: Claimed = sqlx::query_as(
+            r#"
+            WITH inserted AS (
+                INSERT INTO webhook_deliveries (webhook_id, event, payload, next_attempt_at)
+                VALUES ($1, $2, $3, NOW() + $4 * INTERVAL '1 second')
+                RETURNING id, webhook_id, event, payload, attempts
+            )
+            SELECT d.id, d.event, d.payload, d.attempts, w.url, w.secret
+            FROM inserted d
+            JOIN webhooks w ON w.id = d.webhook_id
+            "#,
+        )
+        .bind(webhook_id)
+        .bind(notification.event)
+        .bind(notification.render(format))
+        .bind(self.lease.as_secs_f64())
+        .fetch_one(&db.pool)
+        .await?;
+
+        self.attempt(db, claimed).await
+    }
+
+    /// Post every due delivery, one batch at a time
+    async fn deliver_due(&self, db: &Database) -> ApiResult<()> {
+        loop {
+            let batch: Vec<Claimed> = sqlx::query_as(
+                r#"
+                WITH due AS (
+                    SELECT id FROM webhook_deliveries
+                    WHERE status = 'pending' AND next_attempt_at <= NOW()
+                    ORDER BY next_attempt_at
+                    LIMIT $1
+                    FOR UPDATE SKIP LOCKED
+                )
+                UPDATE webhook_deliveries d
+                SET next_attempt_at = NOW() + $2 * INTERVAL '1 second'
+                FROM due, webhooks w
+                WHERE d.id = due.id AND w.id = d.webhook_id
+                RETURNING d.id, d.event, d.payload, d.attempts, w.url, w.secret
+                "#,
+            )
+            .bind(BATCH_SIZE)
+            .bind(self.lease.as_secs_f64())
+            .fetch_all(&db.pool)
+            .await?;
+
+            if batch.is_empty() {
+                return Ok(());
+            }
+
+            let mut attempts = tokio::task::JoinSet::new();
+            for claimed in batch {
+                let (dispatcher, db) = (self.clone(), db.clone());
+                attempts.spawn(async move { dispatcher.attempt(&db, claimed).await });
+            }
+            while let Some(result) = attempts.join_next().await {
+                match result {
+                    Ok(Ok(_)) => {}
+                    Ok(Err(e)) => tracing::warn!("Failed to record webhook delivery: {e}"),
+                    Err(e) => tracing::warn!("Webhook delivery task failed: {e}"),
+                }
+            }
+        }
+    }
+
+    /// Post one delivery and record the outcome
+    async fn attempt(&self, db: &Database, claimed: Claimed) -> ApiResult<WebhookDelivery> {
+        let Posted {
+            response_status,
+            error,
+            permanent,
+        } = self.post(&claimed).await?;
+
+        let attempts = claimed.attempts + 1;
+        let (status, next_attempt_at) = match &error {
+            None => ("succeeded", None),
+            Some(_) if permanent || attempts >= self.max_attempts => ("failed", None),
+            Some(_) => ("pending", Some(Utc::now() + self.backoff(attempts))),
+        };
+        if let Some(error) = &error {
+            tracing::warn!(
+                delivery = claimed.id,
+                attempts,
+                status,
+                "Webhook attempt failed: {error}"
+            );
+        }
+
+        let delivery = sqlx::query_as(&format!(
+            r#"
+            UPDATE webhook_deliveries
+            SET status = $2, attempts = $3, next_attempt_at = $4, response_status = $5,
+                last_error = $6,
+                delivered_at = CASE WHEN $2 = 'succeeded' THEN NOW() ELSE delivered_at END
+            WHERE id = $1
+            RETURNING {WEBHOOK_DELIVERY_COLUMNS}
+            "#
+        ))
+        .bind(claimed.id)
+        .bind(status)
+        .bind(attempts)
+        .bind(next_attempt_at)
+        .bind(response_status.map(i32::from))
+        .bind(&error)
+        .fetch_one(&db.pool)
+        .await?;
+
+        Ok(delivery)
+    }
+
+    /// Send a delivery to its receiver
+    async fn post(&self, claimed: &Claimed) -> ApiResult<Posted> {
+        let body = serde_json::to_vec(&claimed.payload)?;
+        let timestamp = Utc::now().timestamp().to_string();
+
+        let mut request = self
+            .client
+            .post(&claimed.url)
+            .header("Content-Type", "application/json")
+            .header("X-Baram-Event", &claimed.event)
+            .header("X-Baram-Delivery", claimed.id.to_string())
+            .header("X-Baram-Timestamp", &timestamp);
+        if let Some(secret) = claimed.secret.as_deref().filter(|s| !s.is_empty()) {
+            request = request.header("X-Baram-Signature", sign(secret, &timestamp, &body));
+        }
+
+        let posted = match request.body(body).send().await {
+            Ok(response) => {
+                let status = response.status();
+                if status.is_success() {
+                    Posted {
+                        response_status: Some(status.as_u16()),
+                        error: None,
+                        permanent: false,
+                    }
+                } else {
+                    let text = response.text().await.unwrap_or_default();
+                    let text: String = text.chars().take(500).collect();
+                    Posted {
+                        response_status: Some(status.as_u16()),
+                        error: Some(format!("Receiver answered {status}: {text}")),
+                        permanent: is_permanent(status),
+                    }
+                }
+            }
+            Err(e) => Posted {
+                response_status: None,
+                error: Some(format!("Failed to reach receiver: {e}")),
+                permanent: false,
+            },
+        };
+
+        Ok(posted)
+    }
+
+    /// Delay before the attempt after `attempts` failed ones
+    fn backoff(&self, attempts: i32) -> chrono::Duration {
+        let exponent = u32::try_from(attempts - 1).unwrap_or(0).min(20);
+        let delay = self.retry_base.saturating_mul(1 << exponent).min(self.retry_max);
+        chrono::Duration::from_std(delay).unwrap_or_else(|_| chrono::Duration::hours(1))
+    }
+}
+
+/// Answer of a receiver to one delivery attempt
+struct Posted {
+    response_status: Option<u16>,
+    error: Option<String>,
+    /// Retrying cannot change the answer
+    permanent: bool,
+}
+
+/// Whether a failed delivery's status rules out retries
+///
+/// Client errors will not go away by retrying, except timeouts (408), too
+/// early (425) and rate limits (429).
+fn is_permanent(status: StatusCode) -> bool {
+    status.is_client_error() && !matches!(status.as_u16(), 408 | 425 | 429)
+}
+
+/// `sha256=<hex>` HMAC of `<timestamp>.<body>`
+fn sign(secret: &str, timestamp: &str, body: &[u8]) -> String {
+    let mut mac =
+        Hmac::<Sha256>::new_from_slice(secret.as_bytes()).expect("HMAC accepts any key length");
+    mac.update(timestamp.as_bytes());
+    mac.update(b".");
+    mac.update(body);
+    format!("sha256={}", hex::encode(mac.finalize().into_bytes()))
+}
+
+/// Spawn the delivery worker
+///
+/// It wakes when a notification is queued and every `poll_interval` for
+/// retries; finished deliveries older than `retention_days` are pruned.
+pub fn spawn(
+    dispatcher: WebhookDispatcher,
+    db: Database,
+    poll_interval: Duration,
+    retention_days: u32,
+) {
+    tokio::spawn(async move {
+        let mut poll = tokio::time::interval(poll_interval);
+        poll.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+        let mut prune = tokio::time::interval(PRUNE_INTERVAL);
+
+        loop {
+            tokio::select! {
+                _ = dispatcher.wake.notified() => {}
+                _ = poll.tick() => {}
+                _ = prune.tick(), if retention_days > 0 => {
+                    if let Err(e) = prune_deliveries(&db, retention_days).await {
+                        tracing::warn!("Failed to prune webhook deliveries: {e}");
+                    }
+                    continue;
+                }
+            }
+
+            if let Err(e) = dispatcher.deliver_due(&db).await {
+                tracing::warn!("Webhook delivery failed: {e}");
+            }
+        }
+    });
+}
+
+async fn prune_deliveries(db: &Database, retention_days: u32) -> Result<(), sqlx::Error> {
+    let cutoff = Utc::now() - chrono::Duration::days(i64::from(retention_days));
+    let deleted =
+        sqlx::query("DELETE FROM webhook_deliveries WHERE status <> 'pending' AND created_at < $1")
+            .bind(cutoff)
+            .execute(&db.pool)
+            .await?
+            .rows_affected();
+
+    if deleted > 0 {
+        tracing::info!("Pruned {deleted} webhook deliveries older than {retention_days} days");
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use axum::{body::Bytes, http::HeaderMap, routing::post, Router};
+    use chrono::TimeZone;
+    use tokio::sync::oneshot;
+
+    fn dispatcher() -> WebhookDispatcher {
+        WebhookDispatcher::new(&WebhooksConfig {
+            timeout_secs: 5,
+            retry_base_secs: 30,
+            retry_max_secs: 3600,
+            ..WebhooksConfig::default()
+        })
+    }
+
+    fn alert(state: &str, severity: &str) -> Notification {
+        Notification::alert(&AlertEvent {
+            id: 7,
+            rule_id: 1,
+            rule_name: "Crawl failures".to_string(),
+            severity: severity.to_string(),
+            state: state.to_string(),
+            value: Some(12.5),
+            message: "Crawl failures: crawl_failure_rate is 12.5, > 10".to_string(),
+            created_at: Utc.with_ymd_and_hms(2026, 2, 1, 9, 0, 0).unwrap(),
+        })
+    }
+
+    #[test]
+    fn sign_matches_a_known_hmac() {
+        // python3 -c "import hmac, hashlib; print(hmac.new(b'topsecret',
+        //   b'1700000000.{\"event\":\"test\"}', hashlib.sha256).hexdigest())"
+        assert_eq!(
+            sign("topsecret", "1700000000", br#"{"event":"test"}"#),
+            "sha256=ef8083d5fbc4450dd431e4b70302ec614a29f0a293b62a59807f4994efbcc1b0"
+        );
+    }
+
+    #[test]
+    fn backoff_doubles_up_to_the_maximum() {
+        let dispatcher = dispatcher();
+        let delays: Vec<i64> = (1..=9)
+            .map(|attempts| dispatcher.backoff(attempts).num_seconds())
+            .collect();
+
+        assert_eq!(delays, [30, 60, 120, 240, 480, 960, 1920, 3600, 3600]);
+        // Huge attempt counts neither overflow nor exceed the cap
+        assert_eq!(dispatcher.backoff(i32::MAX).num_seconds(), 3600);
+        assert_eq!(dispatcher.backoff(0).num_seconds(), 30);
+    }
+
+    #[test]
+    fn only_lasting_client_errors_are_permanent() {
+        for status in [400, 401, 403, 404, 410, 413, 422] {
+            assert!(is_permanent(StatusCode::from_u16(status).unwrap()), "{status}");
+        }
+        for status in [408, 425, 429, 500, 502, 503, 504, 301] {
+            assert!(!is_permanent(StatusCode::from_u16(status).unwrap()), "{status}");
+        }
+    }
+
+    #[test]
+    fn slack_layout() {
+        let body = alert("firing", "critical").render(WebhookFormat::Slack);
+
+        assert_eq!(
+            body,
+            json!({
+                "text": "[FIRING] Crawl failures",
+                "attachments": [{
+                    "color": "#d93025",
+                    "title": "[FIRING] Crawl failures",
+                    "text": "Crawl failures: crawl_failure_rate is 12.5, > 10",
+                    "fields": [
+                        {"title": "Severity", "value": "critical", "short": true},
+                        {"title": "Value", "value": "12.50", "short": true},
+                    ],
+                    "ts": 1_769_936_400,
+                }],
+            })
+        );
+    }
+
+    #[test]
+    fn discord_layout() {
+        let body = alert("resolved", "critical").render(WebhookFormat::Discord);
+
+        assert_eq!(
+            body,
+            json!({
+                "embeds": [{
+                    "title": "[RESOLVED] Crawl failures",
+                    "description": "Crawl failures: crawl_failure_rate is 12.5, > 10",
+                    // Resolved alerts are green whatever their severity
+                    "color": 0x188038,
+                    "fields": [
+                        {"name": "Severity", "value": "critical", "inline": true},
+                        {"name": "Value", "value": "12.50", "inline": true},
+                    ],
+                    "timestamp": "2026-02-01T09:00:00+00:00",
+                }],
+            })
+        );
+    }
+
+    #[test]
+    fn json_layout() {
+        let body = alert("firing", "warning").render(WebhookFormat::Json);
+
+        assert_eq!(body["event"], "alert.firing");
+        assert_eq!(body["title"], "[FIRING] Crawl failures");
+        assert_eq!(body["severity"], "warning");
+        assert_eq!(body["timestamp"], "2026-02-01T09:00:00Z");
+        assert_eq!(body["data"]["rule_id"], 1);
+        assert_eq!(body["data"]["state"], "firing");
+        assert_eq!(body["data"]["value"], 12.5);
+    }
+
+    /// Receiver answering `status` to one delivery, handing over what it got
+    async fn one_shot_receiver(status: u16) -> (String, oneshot::Receiver<(HeaderMap, Bytes)>) {
+        let (tx, rx) = oneshot::channel();
+        let tx = Arc::new(std::sync::Mutex::new(Some(tx)));
+        let app = Router::new().route(
+            "/hook",
+            post(move |headers: HeaderMap, body: Bytes| {
+                if let Some(tx) = tx.lock().unwrap().take() {
+                    let _ = tx.send((headers, body));
+                }
+                async move { axum::http::StatusCode::from_u16(status).unwrap() }
+            }),
+        );
+
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        tokio::spawn(async move { axum::serve(listener, app).await });
+        (url, rx)
+    }
+
+    fn claimed(url: String, secret: Option<&str>) -> Claimed {
+        Claimed {
+            id: 42,
+            event: "alert.firing".to_string(),
+            payload: json!({"event": "alert.firing", "text": "결과"}),
+            attempts: 0,
+            url,
+            secret: secret.map(String::from),
+        }
+    }
+
+    #[tokio::test]
+    async fn receiver_gets_a_signed_delivery() {
+        let (url, received) = one_shot_receiver(204).await;
+
+        let posted = dispatcher().post(&claimed(url, Some("topsecret"))).await.unwrap();
+        assert_eq!(posted.response_status, Some(204));
+        assert_eq!(posted.error, None);
+
+        let (headers, body) = received.await.unwrap();
+        let header = |name: &str| headers[name].to_str().unwrap().to_string();
+        assert_eq!(header("content-type"), "application/json");
+        assert_eq!(header("x-baram-event"), "alert.firing");
+        assert_eq!(header("x-baram-delivery"), "42");
+        let timestamp = header("x-baram-timestamp");
+        assert_eq!(header("x-baram-signature"), sign("topsecret", &timestamp, &body));
+        assert_eq!(
+            serde_json::from_slice::<Value>(&body).unwrap(),
+            json!({"event": "alert.firing", "text": "결과"})
+        );
+    }
+
+    #[tokio::test]
+    async fn unsigned_without_a_secret() {
+        let (url, received) = one_shot_receiver(200).await;
+
+        dispatcher().post(&claimed(url, Some(""))).await.unwrap();
+
+        let (headers, _) = received.await.unwrap();
+        assert!(!headers.contains_key("x-baram-signature"));
+    }
+
+    #[tokio::test]
+    async fn receiver_errors_are_classified() {
+        for (status, permanent) in [(404, true), (429, false), (503, false)] {
+            let (url, _received) = one_shot_receiver(status).await;
+
+            let posted = dispatcher().post(&claimed(url, None)).await.unwrap();
+
+            assert_eq!(posted.response_status, Some(status));
+            assert!(posted.error.unwrap().starts_with("Receiver answered"));
+            assert_eq!(posted.permanent, permanent, "{status}");
+        }
+    }
+
+    #[tokio::test]
+    async fn unreachable_receiver_is_retried() {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let url = format!("http://{}/hook", listener.local_addr().unwrap());
+        drop(listener);
+
+        let posted = dispatcher().post(&claimed(url, None)).await.unwrap();
+
+        assert_eq!(posted.response_status, None);
+        assert!(posted.error.unwrap().starts_with("Failed to reach receiver"));
+        assert!(!posted.permanent);
+    }
+}
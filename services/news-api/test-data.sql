@@ -13,6 +13,22 @@ INSERT INTO categories (name, article_count) VALUES
     ('생활/문화', 95)
 ON CONFLICT (name) DO UPDATE SET article_count = EXCLUDED.article_count;
 
+-- Group IT labels used by different sources under one category
+INSERT INTO categories (name, article_count) VALUES ('IT', 0), ('과학', 0)
+ON CONFLICT (name) DO NOTHING;
+UPDATE categories SET parent_id = (SELECT id FROM categories WHERE name = 'IT/과학')
+WHERE name IN ('IT', '과학');
+INSERT INTO category_aliases (alias, category_id)
+SELECT 'IT과학', id FROM categories WHERE name = 'IT/과학'
+ON CONFLICT (alias) DO NOTHING;
+
+-- Insert sample sources
+INSERT INTO sources (name, homepage, feed_urls, language, default_category, crawl_interval_minutes) VALUES
+    ('뉴스1', 'https://www.news1.kr', ARRAY['https://www.news1.kr/rss'], 'ko', '정치', 30),
+    ('이코노미', 'https://economy.example.com', ARRAY['https://economy.example.com/rss'], 'ko', '경제', 60),
+    ('테크뉴스', 'https://tech.example.com', ARRAY['https://tech.example.com/feed'], 'ko', 'IT/과학', 60)
+ON CONFLICT (name) DO NOTHING;
+
 -- Insert sample crawl statistics
 INSERT INTO crawl_stats (date, total_crawled, success_count, failed_count) VALUES
     (CURRENT_DATE - INTERVAL '7 days', 500, 485, 15),
@@ -22,9 +38,15 @@ INSERT INTO crawl_stats (date, total_crawled, success_count, failed_count) VALUE
     (CURRENT_DATE - INTERVAL '3 days', 495, 490, 5),
     (CURRENT_DATE - INTERVAL '2 days', 505, 495, 10),
     (CURRENT_DATE - INTERVAL '1 days', 515, 510, 5),
-    (CURRENT_DATE, 250, 248, 2);
+    (CURRENT_DATE, 250, 248, 2)
+ON CONFLICT (date) DO UPDATE SET
+    total_crawled = EXCLUDED.total_crawled,
+    success_count = EXCLUDED.success_count,
+    failed_count = EXCLUDED.failed_count;
 
 -- Display results
 SELECT 'Categories' as table_name, COUNT(*) as row_count FROM categories
 UNION ALL
+SELECT 'Sources', COUNT(*) FROM sources
+UNION ALL
 SELECT 'Crawl Stats', COUNT(*) FROM crawl_stats;